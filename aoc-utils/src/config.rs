@@ -0,0 +1,47 @@
+//! Settings taken from the environment, so that the inputs can live outside of the crates of the days:
+//!
+//! - `AOC_INPUT_DIR`: the directory holding a `dayNN` directory with the inputs of each day, the calendar
+//!   itself (`dayNN/input`, next to the sources) by default
+//! - `AOC_SESSION`: the `session` cookie of adventofcode.com, read from the `.session` file otherwise
+//! - `AOC_YEAR`: the year of the puzzles, [`DEFAULT_YEAR`] by default
+use crate::input;
+use std::{
+    env::{self, VarError},
+    error::Error,
+    path::PathBuf,
+};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const YEAR_VAR: &str = "AOC_YEAR";
+
+/// Year of the calendar the inputs are downloaded from, unless `AOC_YEAR` says otherwise.
+pub const DEFAULT_YEAR: u16 = 2023;
+
+/// The directory holding the `dayNN` directories the inputs are stored in.
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => dir.into(),
+        _ => input::calendar_dir().to_path_buf(),
+    }
+}
+
+/// The `session` cookie of the default account given by `AOC_SESSION`, if any.
+pub fn session() -> Option<String> {
+    env::var(SESSION_VAR)
+        .ok()
+        .map(|session| session.trim().to_owned())
+        .filter(|session| !session.is_empty())
+}
+
+/// The year of the puzzles.
+pub fn year() -> Result<u16, Box<dyn Error>> {
+    match env::var(YEAR_VAR) {
+        Ok(year) => year
+            .trim()
+            .parse()
+            .map_err(|err| format!("{}={:?} is not a year: {}", YEAR_VAR, year, err).into()),
+        Err(VarError::NotPresent) => Ok(DEFAULT_YEAR),
+        Err(err) => Err(format!("{} is invalid: {}", YEAR_VAR, err).into()),
+    }
+}
@@ -0,0 +1,132 @@
+//! Cycles of deterministic processes, each state only depending on the one before: once a state comes back the
+//! states repeat forever, so the state after any number of steps only takes walking the prefix and the cycle.
+
+/// Finds where the states `start`, `step(start)`, `step(step(start))`... start repeating with Brent's algorithm,
+/// returning `(prefix, period)`: the states from `prefix` steps on come back every `period` steps, and the ones
+/// before never come back.
+///
+/// `step` moves a state to the next one in place. Only two states are kept at a time, so this never ends if the
+/// states never repeat.
+pub fn detect<S: Clone + PartialEq>(start: &S, mut step: impl FnMut(&mut S)) -> (u64, u64) {
+    // the tortoise waits at powers of two for the hare to come back to it, the period being found once it does
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    step(&mut hare);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        step(&mut hare);
+        period += 1;
+    }
+
+    // with the hare a period ahead, both meet at the first state of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..period {
+        step(&mut hare);
+    }
+
+    let mut prefix = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+
+    (prefix, period)
+}
+
+/// How many steps from the start lead to the same state as `steps` steps, given the `(prefix, period)` returned
+/// by [`detect`]: `steps` itself unless it goes past the prefix and a whole period.
+#[inline]
+pub fn equivalent_steps((prefix, period): (u64, u64), steps: u64) -> u64 {
+    if steps < prefix {
+        steps
+    } else {
+        prefix + (steps - prefix) % period
+    }
+}
+
+/// The state after `steps` steps from `start`, however many they are, see [`detect`].
+pub fn state_after<S: Clone + PartialEq>(start: &S, mut step: impl FnMut(&mut S), steps: u64) -> S {
+    let cycle = detect(start, &mut step);
+    let mut state = start.clone();
+    for _ in 0..equivalent_steps(cycle, steps) {
+        step(&mut state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts from 0 to `prefix + period - 1`, then back to `prefix`.
+    fn rho(prefix: u64, period: u64) -> impl Fn(&mut u64) + Copy {
+        move |state| {
+            *state = if *state + 1 < prefix + period {
+                *state + 1
+            } else {
+                prefix
+            }
+        }
+    }
+
+    #[test]
+    fn detect_every_shape() {
+        for prefix in 0..12 {
+            for period in 1..12 {
+                assert_eq!(detect(&0, rho(prefix, period)), (prefix, period));
+            }
+        }
+    }
+
+    #[test]
+    fn detect_fixed_point() {
+        assert_eq!(detect(&5, |_| ()), (0, 1));
+        assert_eq!(
+            detect(&0, |state: &mut u8| *state = (*state + 1).min(3)),
+            (3, 1)
+        );
+    }
+
+    #[test]
+    fn equivalent_steps_edges() {
+        let cycle = (3, 4);
+        assert_eq!(equivalent_steps(cycle, 0), 0);
+        assert_eq!(equivalent_steps(cycle, 2), 2);
+        assert_eq!(equivalent_steps(cycle, 3), 3);
+        assert_eq!(equivalent_steps(cycle, 6), 6);
+        assert_eq!(equivalent_steps(cycle, 7), 3);
+        assert_eq!(equivalent_steps(cycle, 11), 3);
+        // 2^64 - 4 steps after the prefix make a whole number of periods
+        assert_eq!(equivalent_steps(cycle, u64::MAX), 3);
+        assert_eq!(equivalent_steps((0, 1), u64::MAX), 0);
+    }
+
+    #[test]
+    fn state_after_matches_stepping() {
+        for (prefix, period) in [(0, 1), (0, 5), (4, 1), (3, 7)] {
+            let step = rho(prefix, period);
+            let mut state = 0;
+            for steps in 0..40 {
+                assert_eq!(state_after(&0, step, steps), state, "{} steps", steps);
+                step(&mut state);
+            }
+        }
+
+        // a billion steps around a cycle of 7 after 3 steps
+        assert_eq!(
+            state_after(&0, rho(3, 7), 1_000_000_000),
+            3 + (1_000_000_000 - 3) % 7
+        );
+        assert_eq!(state_after(&0, rho(3, 7), u64::MAX), 3 + (u64::MAX - 3) % 7);
+    }
+}
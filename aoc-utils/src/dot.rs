@@ -0,0 +1,128 @@
+//! Graphs written in the DOT language of GraphViz, to be drawn with `dot -Tsvg graph.gv -o graph.svg` (or
+//! `neato`, `sfdp`...).
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// Attributes of a graph, node or edge, written as `name="value"`.
+pub type Attributes = Vec<(&'static str, String)>;
+
+/// A graph that can be written in the DOT language: its nodes and edges, along with hooks to label and style
+/// them, only [`ToDot::nodes`], [`ToDot::edges`] and [`ToDot::node_id`] have to be written.
+pub trait ToDot {
+    type Node;
+    /// what an edge carries, given to [`ToDot::edge_label`] (`()` for unlabeled edges)
+    type Edge;
+
+    /// Whether the edges go one way, a `digraph` drawing them `a -> b`, or both ways, a `graph` drawing them
+    /// `a -- b`.
+    const DIRECTED: bool = true;
+
+    /// Every node, in the order they are written.
+    fn nodes(&self) -> impl Iterator<Item = Self::Node>;
+
+    /// Every edge with what it carries, each edge of an undirected graph only once.
+    fn edges(&self) -> impl Iterator<Item = (Self::Node, Self::Node, Self::Edge)>;
+
+    /// The name of `node` in the DOT file, written quoted so it can be any string, but unique to the node.
+    fn node_id(&self, node: &Self::Node) -> String;
+
+    /// What is drawn in `node`, its id when `None`.
+    fn node_label(&self, _node: &Self::Node) -> Option<String> {
+        None
+    }
+
+    /// The attributes of `node` apart from its label (`shape`, `fillcolor`...).
+    fn node_attributes(&self, _node: &Self::Node) -> Attributes {
+        vec![]
+    }
+
+    /// What is drawn along the edge from `from` to `to`, nothing when `None`.
+    fn edge_label(
+        &self,
+        _from: &Self::Node,
+        _to: &Self::Node,
+        _edge: &Self::Edge,
+    ) -> Option<String> {
+        None
+    }
+
+    /// The attributes of the whole graph, laid out by `dot` unless overridden.
+    fn graph_attributes(&self) -> Attributes {
+        vec![("layout", "dot".to_owned())]
+    }
+
+    /// Writes the graph attributes, then the nodes, then the edges.
+    fn write_dot<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let (keyword, edge_op) = if Self::DIRECTED {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(writer, "{} {{", keyword)?;
+        for (name, value) in self.graph_attributes() {
+            writeln!(writer, "    {}={}", name, quote(&value))?;
+        }
+        writeln!(writer)?;
+
+        for node in self.nodes() {
+            let mut attributes = self.node_attributes(&node);
+            if let Some(label) = self.node_label(&node) {
+                attributes.insert(0, ("label", label));
+            }
+            writeln!(
+                writer,
+                "    {}{}",
+                quote(&self.node_id(&node)),
+                attribute_list(&attributes)
+            )?;
+        }
+        writeln!(writer)?;
+
+        for (from, to, edge) in self.edges() {
+            let attributes = match self.edge_label(&from, &to, &edge) {
+                Some(label) => vec![("label", label)],
+                None => vec![],
+            };
+            writeln!(
+                writer,
+                "    {} {} {}{}",
+                quote(&self.node_id(&from)),
+                edge_op,
+                quote(&self.node_id(&to)),
+                attribute_list(&attributes)
+            )?;
+        }
+
+        writeln!(writer, "}}")
+    }
+
+    /// Writes the graph to the file at `path`, replacing it.
+    fn save_dot(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_dot(&mut writer)?;
+        writer.flush()
+    }
+}
+
+/// `text` as a quoted DOT string, its line breaks becoming `\n` escapes.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// ` [name="value", ...]`, nothing without attributes.
+fn attribute_list(attributes: &[(&str, String)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
+    }
+
+    let list = attributes
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, quote(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" [{}]", list)
+}
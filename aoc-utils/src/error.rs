@@ -0,0 +1,131 @@
+//! The error returned when reading and parsing puzzle inputs, telling I/O failures apart from malformed input
+//! (and saying where the input is malformed).
+use std::{error::Error, fmt, io, str::FromStr};
+
+#[derive(Debug)]
+pub enum AocError {
+    /// `what` (a file, or stdin) could not be read
+    Io {
+        what: String,
+        error: io::Error,
+    },
+    /// there is no input in that file
+    MissingInput(String),
+    Parse(ParseError),
+    /// the map of the input is not a rectangle of known tiles
+    InvalidGrid(String),
+}
+
+/// Malformed puzzle input, and where it is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    message: String,
+    /// 1-based, unknown until the error is placed in its line (see [`AocError::in_line`])
+    line: Option<usize>,
+    /// 1-based, in characters
+    column: Option<usize>,
+    /// the address of the text at fault, to find its column once its line is known
+    at: usize,
+}
+
+impl ParseError {
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[inline]
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    #[inline]
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl AocError {
+    /// The input is malformed at `at`, a slice of the input: the parser finding the fault does not need to
+    /// know which line it is in, the caller splitting the input into lines places it with [`Self::in_line`].
+    pub fn parse(at: &str, message: impl Into<String>) -> Self {
+        Self::Parse(ParseError {
+            message: message.into(),
+            line: None,
+            column: None,
+            at: at.as_ptr() as usize,
+        })
+    }
+
+    /// Places a parse error in `line`, the line at `index` (from 0) of the input. Its column is where the text
+    /// at fault starts in `line`, if it comes from it.
+    pub fn in_line(self, index: usize, line: &str) -> Self {
+        match self {
+            Self::Parse(error) if error.line.is_none() => {
+                let offset = error.at.wrapping_sub(line.as_ptr() as usize);
+                let column = line.get(..offset).map(|before| before.chars().count() + 1);
+                Self::Parse(ParseError {
+                    line: Some(index + 1),
+                    column,
+                    ..error
+                })
+            }
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { what, error } => write!(f, "Could not read {}: {}", what, error),
+            Self::MissingInput(what) => write!(f, "There is no input in {}", what),
+            Self::Parse(error) => write!(f, "{}", error),
+            Self::InvalidGrid(message) => write!(f, "Invalid grid: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "Line {}, column {}: {}", line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "Line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `field`, a slice of the input, reporting where it is when it is not a valid `T`.
+pub fn parse_field<T>(field: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    field
+        .parse()
+        .map_err(|err| AocError::parse(field, format!("{:?} is invalid: {}", field, err)))
+}
+
+/// Parses every line of `input` with `parse`, placing the errors in their line.
+pub fn parse_lines<'s, T>(
+    input: &'s str,
+    mut parse: impl FnMut(&'s str) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|err| err.in_line(index, line)))
+        .collect()
+}
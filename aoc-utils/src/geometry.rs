@@ -0,0 +1,354 @@
+//! Integer plane geometry: points, the four cardinal directions (and the moves they make on a grid) and
+//! segments between points.
+use std::ops::{Add, Sub};
+
+/// A point of the integer plane, `row` growing southwards and `col` growing eastwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[inline]
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    /// The point `distance` steps away in `direction`, or `None` on overflow.
+    #[inline]
+    pub fn checked_step(&self, direction: Direction, distance: i64) -> Option<Self> {
+        let (row, col) = direction.delta();
+        Some(Self::new(
+            self.row.checked_add(row.checked_mul(distance)?)?,
+            self.col.checked_add(col.checked_mul(distance)?)?,
+        ))
+    }
+
+    /// Cross product of the two points seen as vectors from the origin
+    /// (twice the signed area of the triangle they form with the origin).
+    #[inline]
+    pub const fn cross(&self, other: &Self) -> i128 {
+        self.row as i128 * other.col as i128 - other.row as i128 * self.col as i128
+    }
+
+    #[inline]
+    pub const fn manhattan_distance(&self, other: &Self) -> u64 {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    #[inline]
+    fn from((row, col): (i64, i64)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::North, Self::South, Self::East, Self::West];
+
+    #[inline]
+    pub const fn opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    /// Position in [`Self::ALL`], to index arrays by direction.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        match self {
+            Self::North => 0,
+            Self::South => 1,
+            Self::East => 2,
+            Self::West => 3,
+        }
+    }
+
+    /// The direction a quarter turn counterclockwise.
+    #[inline]
+    pub const fn turn_left(&self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::South,
+            Self::South => Self::East,
+            Self::East => Self::North,
+        }
+    }
+
+    /// The direction a quarter turn clockwise.
+    #[inline]
+    pub const fn turn_right(&self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    /// Unit movement in (rows, columns).
+    #[inline]
+    pub const fn delta(&self) -> (i64, i64) {
+        match self {
+            Self::North => (-1, 0),
+            Self::South => (1, 0),
+            Self::East => (0, 1),
+            Self::West => (0, -1),
+        }
+    }
+
+    /// The cell next to `(row, col)` in this direction, or `None` past row or column 0 (the far edges of a grid
+    /// are left to the caller).
+    #[inline]
+    pub fn translate_coordinates(&self, row: usize, col: usize) -> Option<(usize, usize)> {
+        Some(match self {
+            Self::North => (row.checked_sub(1)?, col),
+            Self::South => (row.checked_add(1)?, col),
+            Self::East => (row, col.checked_add(1)?),
+            Self::West => (row, col.checked_sub(1)?),
+        })
+    }
+
+    /// The cell next to `(row, col)` in this direction on a grid of `rows` by `cols` cells whose edges wrap
+    /// around, leaving one edge entering on the opposite one.
+    #[inline]
+    pub const fn wrapping_translate_coordinates(
+        &self,
+        row: usize,
+        col: usize,
+        rows: usize,
+        cols: usize,
+    ) -> (usize, usize) {
+        match self {
+            Self::North => ((row + rows - 1) % rows, col),
+            Self::South => ((row + 1) % rows, col),
+            Self::East => (row, (col + 1) % cols),
+            Self::West => (row, (col + cols - 1) % cols),
+        }
+    }
+}
+
+/// Accepts the `NSEW` and `UDRL` letters as well as the `^v><` arrows.
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' | 'U' | '^' => Ok(Self::North),
+            'S' | 'D' | 'v' => Ok(Self::South),
+            'E' | 'R' | '>' => Ok(Self::East),
+            'W' | 'L' | '<' => Ok(Self::West),
+            other => Err(format!("Char wasn't a direction ({:?})", other)),
+        }
+    }
+}
+
+/// A segment between two points, in no particular order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Segment {
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Segment {
+    #[inline]
+    pub const fn new(from: Point, to: Point) -> Self {
+        Self { from, to }
+    }
+
+    #[inline]
+    pub const fn is_horizontal(&self) -> bool {
+        self.from.row == self.to.row
+    }
+
+    #[inline]
+    pub const fn is_vertical(&self) -> bool {
+        self.from.col == self.to.col
+    }
+
+    /// Horizontal or vertical, but not a single point.
+    #[inline]
+    pub const fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() != self.is_vertical()
+    }
+
+    /// Number of unit steps between both ends.
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.from.manhattan_distance(&self.to)
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn checked_step() {
+        let point = Point::new(3, -2);
+        assert_eq!(point.checked_step(North, 5), Some(Point::new(-2, -2)));
+        assert_eq!(point.checked_step(South, 5), Some(Point::new(8, -2)));
+        assert_eq!(point.checked_step(East, 5), Some(Point::new(3, 3)));
+        assert_eq!(point.checked_step(West, 5), Some(Point::new(3, -7)));
+        assert_eq!(point.checked_step(East, -5), point.checked_step(West, 5));
+        assert_eq!(point.checked_step(North, 0), Some(point));
+    }
+
+    #[test]
+    fn checked_step_overflow() {
+        let corner = Point::new(i64::MAX, i64::MIN);
+        assert_eq!(corner.checked_step(South, 1), None);
+        assert_eq!(corner.checked_step(West, 1), None);
+        assert_eq!(
+            corner.checked_step(North, 1),
+            Some(Point::new(i64::MAX - 1, i64::MIN))
+        );
+        assert_eq!(
+            corner.checked_step(East, i64::MAX),
+            Some(Point::new(i64::MAX, -1))
+        );
+        // -1 * i64::MIN overflows before reaching the point
+        assert_eq!(Point::ORIGIN.checked_step(North, i64::MIN), None);
+        assert_eq!(
+            Point::ORIGIN.checked_step(South, i64::MIN),
+            Some(Point::new(i64::MIN, 0))
+        );
+    }
+
+    #[test]
+    fn translate_coordinates_edges() {
+        assert_eq!(North.translate_coordinates(0, 4), None);
+        assert_eq!(West.translate_coordinates(4, 0), None);
+        assert_eq!(South.translate_coordinates(usize::MAX, 0), None);
+        assert_eq!(East.translate_coordinates(0, usize::MAX), None);
+        assert_eq!(South.translate_coordinates(0, 0), Some((1, 0)));
+        assert_eq!(East.translate_coordinates(0, 0), Some((0, 1)));
+    }
+
+    #[test]
+    fn wrapping_translate_coordinates() {
+        let (rows, cols) = (3, 4);
+        // every corner wraps to the opposite edge on its two outer sides
+        assert_eq!(
+            North.wrapping_translate_coordinates(0, 0, rows, cols),
+            (2, 0)
+        );
+        assert_eq!(
+            West.wrapping_translate_coordinates(0, 0, rows, cols),
+            (0, 3)
+        );
+        assert_eq!(
+            North.wrapping_translate_coordinates(0, 3, rows, cols),
+            (2, 3)
+        );
+        assert_eq!(
+            East.wrapping_translate_coordinates(0, 3, rows, cols),
+            (0, 0)
+        );
+        assert_eq!(
+            South.wrapping_translate_coordinates(2, 0, rows, cols),
+            (0, 0)
+        );
+        assert_eq!(
+            West.wrapping_translate_coordinates(2, 0, rows, cols),
+            (2, 3)
+        );
+        assert_eq!(
+            South.wrapping_translate_coordinates(2, 3, rows, cols),
+            (0, 3)
+        );
+        assert_eq!(
+            East.wrapping_translate_coordinates(2, 3, rows, cols),
+            (2, 0)
+        );
+
+        // inside the grid it moves like translate_coordinates
+        for direction in Direction::ALL {
+            let expected = direction.translate_coordinates(1, 1);
+            assert_eq!(
+                Some(direction.wrapping_translate_coordinates(1, 1, rows, cols)),
+                expected
+            );
+        }
+
+        // a single cell only leads back to itself
+        for direction in Direction::ALL {
+            assert_eq!(direction.wrapping_translate_coordinates(0, 0, 1, 1), (0, 0));
+        }
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.turn_left(), direction.turn_right().opposite());
+            assert_eq!(
+                direction
+                    .turn_right()
+                    .turn_right()
+                    .turn_right()
+                    .turn_right(),
+                direction
+            );
+
+            // clockwise with rows growing southwards: (row, col) becomes (col, -row)
+            let (row, col) = direction.delta();
+            assert_eq!(direction.turn_right().delta(), (col, -row));
+        }
+    }
+
+    #[test]
+    fn direction_from_char() {
+        for (chars, direction) in [("NU^", North), ("SDv", South), ("ER>", East), ("WL<", West)] {
+            for c in chars.chars() {
+                assert_eq!(Direction::try_from(c), Ok(direction));
+            }
+        }
+
+        for c in ['n', 'V', 'x', ' ', '.'] {
+            assert!(Direction::try_from(c).is_err(), "{:?}", c);
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Algorithms on implicit graphs, described by a successor closure rather than stored nodes and edges,
+//! and a union-find to group the nodes of graphs given as a list of edges.
+use fnv::FnvHashMap;
+use std::{cmp, collections::BinaryHeap, hash::Hash};
+
+/// Dijkstra's shortest path from any of the `starts` nodes to the first node accepted by `is_goal`.
+///
+/// `successors` returns the neighbours of a node together with the (non-negative) cost of the edge
+/// leading to them. Returns `None` if no goal node can be reached.
+pub fn dijkstra<N, S, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+    is_goal: G,
+) -> Option<u64>
+where
+    N: Clone + Eq + Hash + Ord,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    dijkstra_with(FnvHashMap::default(), starts, successors, is_goal)
+}
+
+/// Same as [`dijkstra`] on a graph whose nodes `index` numbers from 0 to `node_count` (excluded), the best
+/// known costs being kept in a `Vec` rather than hashing the nodes.
+pub fn dense_dijkstra<N, S, I, G>(
+    node_count: usize,
+    index: impl Fn(&N) -> usize,
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+    is_goal: G,
+) -> Option<u64>
+where
+    N: Ord,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    let best = DenseCosts {
+        costs: vec![DenseCosts::<()>::UNREACHED; node_count],
+        index,
+    };
+    dijkstra_with(best, starts, successors, is_goal)
+}
+
+/// The best known cost of the nodes reached by a search.
+trait Costs<N> {
+    fn get(&self, node: &N) -> Option<u64>;
+
+    fn set(&mut self, node: &N, cost: u64);
+}
+
+impl<N: Clone + Eq + Hash> Costs<N> for FnvHashMap<N, u64> {
+    #[inline]
+    fn get(&self, node: &N) -> Option<u64> {
+        FnvHashMap::get(self, node).copied()
+    }
+
+    #[inline]
+    fn set(&mut self, node: &N, cost: u64) {
+        self.insert(node.clone(), cost);
+    }
+}
+
+/// The costs of nodes numbered by `index`, see [`dense_dijkstra`].
+struct DenseCosts<F> {
+    costs: Vec<u64>,
+    index: F,
+}
+
+impl<F> DenseCosts<F> {
+    const UNREACHED: u64 = u64::MAX;
+}
+
+impl<N, F: Fn(&N) -> usize> Costs<N> for DenseCosts<F> {
+    #[inline]
+    fn get(&self, node: &N) -> Option<u64> {
+        let cost = self.costs[(self.index)(node)];
+        (cost != Self::UNREACHED).then_some(cost)
+    }
+
+    #[inline]
+    fn set(&mut self, node: &N, cost: u64) {
+        self.costs[(self.index)(node)] = cost;
+    }
+}
+
+fn dijkstra_with<N, S, I, G>(
+    mut best: impl Costs<N>,
+    starts: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut is_goal: G,
+) -> Option<u64>
+where
+    N: Ord,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        best.set(&start, 0);
+        queue.push((cmp::Reverse(0), start));
+    }
+
+    while let Some((cmp::Reverse(cost), node)) = queue.pop() {
+        if is_goal(&node) {
+            return Some(cost);
+        }
+
+        if best.get(&node).is_some_and(|known| cost > known) {
+            continue;
+        }
+
+        for (next, edge_cost) in successors(&node) {
+            let next_cost = cost + edge_cost;
+            if best.get(&next).is_some_and(|known| next_cost >= known) {
+                continue;
+            }
+
+            best.set(&next, next_cost);
+            queue.push((cmp::Reverse(next_cost), next));
+        }
+    }
+
+    None
+}
+
+/// Bidirectional variant of [`dijkstra`]: searches forward from `starts` and backward from `goals`
+/// at the same time, stopping once the two frontiers cannot produce a shorter path than the best
+/// meeting point found so far.
+///
+/// `predecessors` must describe the reversed graph, i.e. return every node with an edge *into*
+/// the given one, with that edge's cost.
+pub fn bidirectional_dijkstra<N, S, SI, P, PI>(
+    starts: impl IntoIterator<Item = N>,
+    goals: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut predecessors: P,
+) -> Option<u64>
+where
+    N: Clone + Eq + Hash + Ord,
+    S: FnMut(&N) -> SI,
+    SI: IntoIterator<Item = (N, u64)>,
+    P: FnMut(&N) -> PI,
+    PI: IntoIterator<Item = (N, u64)>,
+{
+    let mut forward = Frontier::new(starts);
+    let mut backward = Frontier::new(goals);
+    let mut shortest = forward
+        .best
+        .keys()
+        .any(|node| backward.best.contains_key(node))
+        .then_some(0);
+
+    while let (Some(forward_top), Some(backward_top)) = (forward.peek(), backward.peek()) {
+        if shortest.is_some_and(|shortest| forward_top + backward_top >= shortest) {
+            break;
+        }
+
+        let meeting = if forward_top <= backward_top {
+            forward.expand(&mut successors, &backward)
+        } else {
+            backward.expand(&mut predecessors, &forward)
+        };
+
+        if let Some(meeting) = meeting {
+            shortest = Some(shortest.map_or(meeting, |shortest| shortest.min(meeting)));
+        }
+    }
+
+    shortest
+}
+
+/// One direction of [`bidirectional_dijkstra`].
+struct Frontier<N> {
+    queue: BinaryHeap<(cmp::Reverse<u64>, N)>,
+    best: FnvHashMap<N, u64>,
+}
+
+impl<N: Clone + Eq + Hash + Ord> Frontier<N> {
+    fn new(roots: impl IntoIterator<Item = N>) -> Self {
+        let mut frontier = Self {
+            queue: BinaryHeap::new(),
+            best: FnvHashMap::default(),
+        };
+
+        for root in roots {
+            frontier.best.insert(root.clone(), 0);
+            frontier.queue.push((cmp::Reverse(0), root));
+        }
+
+        frontier
+    }
+
+    fn peek(&self) -> Option<u64> {
+        self.queue.peek().map(|(cmp::Reverse(cost), _)| *cost)
+    }
+
+    /// Settles the closest node of the frontier, returning the length of the shortest path
+    /// found through one of its neighbours already reached by the `other` frontier.
+    fn expand<F, I>(&mut self, neighbours: &mut F, other: &Self) -> Option<u64>
+    where
+        F: FnMut(&N) -> I,
+        I: IntoIterator<Item = (N, u64)>,
+    {
+        let (cmp::Reverse(cost), node) = self.queue.pop()?;
+        if self.best.get(&node).is_some_and(|&known| cost > known) {
+            return None;
+        }
+
+        let mut shortest: Option<u64> = None;
+        for (next, edge_cost) in neighbours(&node) {
+            let next_cost = cost + edge_cost;
+            if self
+                .best
+                .get(&next)
+                .is_some_and(|&known| next_cost >= known)
+            {
+                continue;
+            }
+
+            if let Some(&other_cost) = other.best.get(&next) {
+                let through = next_cost + other_cost;
+                shortest = Some(shortest.map_or(through, |shortest| shortest.min(through)));
+            }
+
+            self.best.insert(next.clone(), next_cost);
+            self.queue.push((cmp::Reverse(next_cost), next));
+        }
+
+        shortest
+    }
+}
+
+/// Union-find over the elements `0..len`, each starting in a set of its own.
+///
+/// Uses union by size and path halving, so any sequence of operations is close to linear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    /// the size of the set of every root (meaningless for other elements)
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            set_count: len,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint sets left.
+    #[inline]
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// The representative of the set of `element`.
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+
+        element
+    }
+
+    /// Merges the sets of `a` and `b`, returning whether they were apart.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (larger, smaller) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+        self.set_count -= 1;
+        true
+    }
+
+    #[inline]
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set of `element`.
+    #[inline]
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// The size of every set, largest first.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disjoint_set_empty() {
+        let sets = DisjointSet::new(0);
+        assert!(sets.is_empty());
+        assert_eq!(sets.set_count(), 0);
+        assert_eq!(sets.set_sizes(), Vec::<usize>::new());
+        assert_eq!(DisjointSet::default(), sets);
+    }
+
+    #[test]
+    fn disjoint_set_unions() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(sets.len(), 6);
+        assert_eq!(sets.set_sizes(), [1; 6]);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2), "0 and 2 were merged through 1 and 3");
+        assert!(!sets.union(4, 4));
+
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.set_sizes(), [4, 1, 1]);
+        assert_eq!(sets.size_of(2), 4);
+        assert_eq!(sets.size_of(5), 1);
+        assert!(sets.same_set(0, 3));
+        assert!(!sets.same_set(0, 4));
+        assert!(!sets.same_set(4, 5));
+    }
+
+    #[test]
+    fn disjoint_set_long_chain() {
+        let len = 1000;
+        let mut sets = DisjointSet::new(len);
+        for element in 1..len {
+            // always merging the growing set into a single element
+            assert!(sets.union(element, element - 1));
+        }
+
+        assert_eq!(sets.set_count(), 1);
+        assert_eq!(sets.set_sizes(), [len]);
+        let root = sets.find(0);
+        assert!((0..len).all(|element| sets.find(element) == root));
+    }
+
+    #[test]
+    fn disjoint_set_matches_labels() {
+        // the same unions applied to a label per element, relabeling a whole set at each merge
+        let len = 16;
+        let mut sets = DisjointSet::new(len);
+        let mut labels: Vec<usize> = (0..len).collect();
+        let mut seed = 7u64;
+        for _ in 0..40 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let (a, b) = ((seed >> 33) as usize % len, (seed >> 45) as usize % len);
+
+            let (label_a, label_b) = (labels[a], labels[b]);
+            assert_eq!(sets.union(a, b), label_a != label_b);
+            labels
+                .iter_mut()
+                .filter(|label| **label == label_b)
+                .for_each(|label| *label = label_a);
+
+            let mut expected_sizes: Vec<usize> = (0..len)
+                .map(|label| labels.iter().filter(|&&other| other == label).count())
+                .filter(|&size| size > 0)
+                .collect();
+            expected_sizes.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(sets.set_sizes(), expected_sizes);
+            assert_eq!(sets.set_count(), expected_sizes.len());
+            for (element, &label) in labels.iter().enumerate() {
+                assert_eq!(
+                    sets.size_of(element),
+                    labels.iter().filter(|&&other| other == label).count()
+                );
+                assert_eq!(sets.same_set(element, a), label == labels[a]);
+            }
+        }
+    }
+}
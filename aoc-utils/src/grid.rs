@@ -0,0 +1,498 @@
+//! Rectangular grids of cells, the maps drawn in the puzzle inputs, stored row by row in a single allocation, and
+//! the cells next to a cell.
+use crate::{error::AocError, geometry::Direction};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// `rows()` rows of `cols()` cells, indexed by `(row, col)` from the top left corner. A grid has at least one
+/// column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Box<[T]>,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// `rows` rows of `cols` cells, all of them `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        assert!(cols > 0, "A grid has at least one column");
+        Self {
+            cells: vec![value; rows * cols].into_boxed_slice(),
+            cols,
+        }
+    }
+
+    /// The grid of `rows`, an error unless they all have as many cells.
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<Self, AocError> {
+        let mut cells = vec![];
+        let mut cols = None;
+        for (index, row) in rows.into_iter().enumerate() {
+            let start = cells.len();
+            cells.extend(row);
+            let len = cells.len() - start;
+            match cols {
+                None => cols = Some(len),
+                Some(cols) if cols != len => {
+                    return Err(AocError::InvalidGrid(format!(
+                        "row {} has {} cells, the first one {}",
+                        index + 1,
+                        len,
+                        cols
+                    )))
+                }
+                Some(_) => (),
+            }
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Self {
+                cells: cells.into_boxed_slice(),
+                cols,
+            }),
+            _ => Err(AocError::InvalidGrid("there is no cell".to_owned())),
+        }
+    }
+
+    /// Parses every character of `lines` (without their trailing whitespace) into a cell with `cell`, its errors
+    /// saying where the character is.
+    pub fn from_lines<'s, E: fmt::Display>(
+        lines: impl IntoIterator<Item = &'s str>,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, AocError> {
+        let rows = lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line.trim_end();
+                line.char_indices()
+                    .map(|(at, c)| {
+                        cell(c).map_err(|err| {
+                            AocError::parse(&line[at..at + c.len_utf8()], err.to_string())
+                                .in_line(index, line)
+                        })
+                    })
+                    .collect::<Result<Vec<T>, AocError>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.cells.len() / self.cols
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows(), cols())`, the bounds of the neighbours of a cell.
+    #[inline]
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.rows(), self.cols)
+    }
+
+    /// The cells next to `position` to the north, south, east and west, see [`neighbours4`].
+    #[inline]
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbours4(position, self.bounds())
+    }
+
+    /// The cells around `position`, diagonals included, see [`neighbours8`].
+    #[inline]
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        neighbours8(position, self.bounds())
+    }
+
+    /// Whether `(row, col)` is a cell of the grid.
+    #[inline]
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.rows() && col < self.cols
+    }
+
+    #[inline]
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        if col < self.cols {
+            self.cells.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        if col < self.cols {
+            self.cells.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// The cells of row `row`, from west to east.
+    #[inline]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The rows, from north to south.
+    #[inline]
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.cells.chunks_exact(self.cols)
+    }
+
+    /// The cells of column `col`, from north to south.
+    #[inline]
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    /// The cells, row by row.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// The cells with their `(row, col)`, row by row.
+    #[inline]
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index / cols, index % cols), cell))
+    }
+
+    /// Where the first cell (row by row) matching `predicate` is.
+    #[inline]
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let index = self.cells.iter().position(predicate)?;
+        Some((index / self.cols, index % self.cols))
+    }
+
+    /// The grid of the cells mapped by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            col < self.cols,
+            "column {} out of a grid of {} columns",
+            col,
+            self.cols
+        );
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            col < self.cols,
+            "column {} out of a grid of {} columns",
+            col,
+            self.cols
+        );
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+impl<'g, T> IntoIterator for &'g Grid<T> {
+    type Item = &'g T;
+    type IntoIter = std::slice::Iter<'g, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The cells drawn row by row, a line each.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The cells next to `(row, col)` in each of the directions of [`Direction::ALL`], with the direction taken to
+/// reach them, leaving out those outside of `rows` by `cols` cells.
+#[inline]
+pub fn directed_neighbours4(
+    (row, col): (usize, usize),
+    (rows, cols): (usize, usize),
+) -> impl Iterator<Item = ((usize, usize), Direction)> {
+    Direction::ALL.into_iter().filter_map(move |direction| {
+        let (next_row, next_col) = direction.translate_coordinates(row, col)?;
+        (next_row < rows && next_col < cols).then_some(((next_row, next_col), direction))
+    })
+}
+
+/// The cells next to `(row, col)` to the north, south, east and west (in that order), leaving out those outside
+/// of `rows` by `cols` cells.
+#[inline]
+pub fn neighbours4(
+    position: (usize, usize),
+    bounds: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    directed_neighbours4(position, bounds).map(|(position, _)| position)
+}
+
+/// The eight cells around `(row, col)`, row by row, leaving out those outside of `rows` by `cols` cells.
+#[inline]
+pub fn neighbours8(
+    (row, col): (usize, usize),
+    (rows, cols): (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    let row_range = row.saturating_sub(1)..(row + 2).min(rows);
+    let col_range = col.saturating_sub(1)..(col + 2).min(cols);
+    row_range
+        .flat_map(move |next_row| col_range.clone().map(move |next_col| (next_row, next_col)))
+        .filter(move |&position| position != (row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2 rows of 3 cells, numbered row by row.
+    fn numbered() -> Grid<u8> {
+        Grid::from_rows([[0, 1, 2], [3, 4, 5]]).unwrap()
+    }
+
+    #[test]
+    fn dimensions() {
+        let grid = numbered();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.bounds(), (2, 3));
+        assert_eq!(Grid::new(4, 1, '.').bounds(), (4, 1));
+        assert_eq!(Grid::new(0, 5, '.').bounds(), (0, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one column")]
+    fn no_column() {
+        Grid::new(3, 0, '.');
+    }
+
+    #[test]
+    fn from_rows_errors() {
+        let ragged = Grid::from_rows([vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(
+            ragged.to_string(),
+            "Invalid grid: row 2 has 1 cells, the first one 2"
+        );
+        assert!(matches!(
+            Grid::<u8>::from_rows(Vec::<Vec<u8>>::new()),
+            Err(AocError::InvalidGrid(_))
+        ));
+        assert!(matches!(
+            Grid::<u8>::from_rows([vec![], vec![]]),
+            Err(AocError::InvalidGrid(_))
+        ));
+    }
+
+    #[test]
+    fn from_lines() {
+        let digit = |c: char| c.to_digit(10).ok_or("not a digit");
+        let grid = Grid::from_lines("012  \n345\n".lines(), digit).unwrap();
+        assert_eq!(grid, numbered().map(|&cell| cell as u32));
+
+        let error = Grid::from_lines("012\n3x5".lines(), digit).unwrap_err();
+        assert_eq!(error.to_string(), "Line 2, column 2: not a digit");
+    }
+
+    #[test]
+    fn corners_and_edges() {
+        let grid = numbered();
+        assert_eq!(grid[(0, 0)], 0);
+        assert_eq!(grid[(0, 2)], 2);
+        assert_eq!(grid[(1, 0)], 3);
+        assert_eq!(grid[(1, 2)], 5);
+
+        assert!(grid.contains((1, 2)));
+        assert!(!grid.contains((2, 0)));
+        assert!(!grid.contains((0, 3)));
+        assert!(!grid.contains((usize::MAX, usize::MAX)));
+
+        // a column past the edge does not wrap around into the next row
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((1, 2)), Some(&5));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of a grid of 3 columns")]
+    fn index_past_the_last_column() {
+        let _ = numbered()[(0, 3)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut grid = numbered();
+        assert_eq!(grid.row(1), [3, 4, 5]);
+        assert_eq!(grid.iter_rows().collect::<Vec<_>>(), [[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(grid.iter_rows().next_back(), Some(&[3, 4, 5][..]));
+        assert_eq!(grid.col(2).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.col(0).rev().copied().collect::<Vec<_>>(), [3, 0]);
+        assert_eq!(grid.col(1).len(), 2);
+
+        grid.row_mut(0)[1] = 9;
+        grid[(1, 1)] = 8;
+        *grid.get_mut((1, 0)).unwrap() = 7;
+        assert_eq!(grid.get_mut((0, 3)), None);
+        assert_eq!(grid.iter().copied().collect::<Vec<_>>(), [0, 9, 2, 7, 8, 5]);
+    }
+
+    #[test]
+    fn positions() {
+        let grid = numbered();
+        let indexed = grid
+            .indexed_iter()
+            .map(|(position, &cell)| (position, cell))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            indexed,
+            [
+                ((0, 0), 0),
+                ((0, 1), 1),
+                ((0, 2), 2),
+                ((1, 0), 3),
+                ((1, 1), 4),
+                ((1, 2), 5)
+            ]
+        );
+        assert_eq!(grid.position(|&cell| cell > 2), Some((1, 0)));
+        assert_eq!(grid.position(|&cell| cell > 5), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(numbered().to_string(), "012\n345\n");
+    }
+
+    #[test]
+    fn neighbours4_order_and_edges() {
+        let bounds = (3, 4);
+        assert_eq!(
+            neighbours4((1, 1), bounds).collect::<Vec<_>>(),
+            [(0, 1), (2, 1), (1, 2), (1, 0)]
+        );
+        assert_eq!(
+            neighbours4((0, 0), bounds).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            neighbours4((2, 3), bounds).collect::<Vec<_>>(),
+            [(1, 3), (2, 2)]
+        );
+        assert_eq!(
+            neighbours4((0, 2), bounds).collect::<Vec<_>>(),
+            [(1, 2), (0, 3), (0, 1)]
+        );
+        assert_eq!(neighbours4((0, 0), (1, 1)).count(), 0);
+        assert_eq!(
+            neighbours4((0, 1), (1, 3)).collect::<Vec<_>>(),
+            [(0, 2), (0, 0)]
+        );
+
+        let directed = directed_neighbours4((0, 0), bounds).collect::<Vec<_>>();
+        assert_eq!(
+            directed,
+            [((1, 0), Direction::South), ((0, 1), Direction::East)]
+        );
+    }
+
+    #[test]
+    fn neighbours8_order_and_edges() {
+        let bounds = (3, 4);
+        assert_eq!(
+            neighbours8((1, 1), bounds).collect::<Vec<_>>(),
+            [
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        assert_eq!(
+            neighbours8((0, 0), bounds).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(
+            neighbours8((2, 3), bounds).collect::<Vec<_>>(),
+            [(1, 2), (1, 3), (2, 2)]
+        );
+        assert_eq!(neighbours8((0, 0), (1, 1)).count(), 0);
+        assert_eq!(
+            neighbours8((0, 1), (1, 3)).collect::<Vec<_>>(),
+            [(0, 0), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn neighbours_of_every_cell() {
+        let grid = numbered();
+        for ((row, col), _) in grid.indexed_iter() {
+            // every cell of the grid at most one step away along each axis
+            let around = grid
+                .indexed_iter()
+                .map(|(position, _)| position)
+                .filter(|&(other_row, other_col)| {
+                    (other_row, other_col) != (row, col)
+                        && row.abs_diff(other_row) <= 1
+                        && col.abs_diff(other_col) <= 1
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(grid.neighbours8((row, col)).collect::<Vec<_>>(), around);
+
+            let mut sides = around
+                .iter()
+                .copied()
+                .filter(|&(other_row, other_col)| row == other_row || col == other_col)
+                .collect::<Vec<_>>();
+            let mut neighbours = grid.neighbours4((row, col)).collect::<Vec<_>>();
+            sides.sort_unstable();
+            neighbours.sort_unstable();
+            assert_eq!(neighbours, sides);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Parsing the command line of the day binaries, reading the puzzle inputs from a file or stdin, locating those of the calendar (see [`config`](crate::config)), and downloading the missing ones (with the `fetch` feature).
+use crate::{config, error::AocError, solution::Part, trace};
+use clap::{ArgAction, Parser};
+#[cfg(feature = "fetch")]
+use std::error::Error;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+/// The name of the input files, see [`input_path`].
+pub const DEFAULT_INPUT: &str = "input";
+
+/// Where the binary of a day reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    File(PathBuf),
+    /// `-`: the input is piped into the binary
+    Stdin,
+}
+
+/// The `input` file of the current directory.
+impl Default for InputSource {
+    #[inline]
+    fn default() -> Self {
+        Self::File(DEFAULT_INPUT.into())
+    }
+}
+
+impl From<String> for InputSource {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "-" => Self::Stdin,
+            _ => Self::File(value.into()),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "the standard input"),
+        }
+    }
+}
+
+impl InputSource {
+    /// The input the binary of `day` reads when not given `--input PATH`, see [`input_path`].
+    #[inline]
+    pub fn of_day(day: u8) -> Self {
+        Self::File(input_path(day, None))
+    }
+
+    /// Reads the whole input.
+    pub fn read(&self) -> Result<String, AocError> {
+        let input = match self {
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => io::read_to_string(io::stdin()),
+        };
+
+        input.map_err(|err| self.error(err))
+    }
+
+    /// Opens the input to read it line by line.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, AocError> {
+        match self {
+            Self::File(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(err) => Err(self.error(err)),
+            },
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    fn error(&self, error: io::Error) -> AocError {
+        match error.kind() {
+            io::ErrorKind::NotFound => AocError::MissingInput(self.to_string()),
+            _ => AocError::Io {
+                what: self.to_string(),
+                error,
+            },
+        }
+    }
+}
+
+/// The options of every day binary: the days having options of their own flatten them into their command line
+/// (`#[command(flatten)]`), the others parse them alone with [`parse_args`].
+///
+/// `DAY` is the day of the binary, whose input is read when not given `--input`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, clap::Args)]
+#[command(about = None, long_about = None)]
+pub struct Args<const DAY: u8> {
+    /// The puzzle input to read, `-` for stdin
+    #[arg(long, value_name = "PATH", default_value_t = InputSource::of_day(DAY))]
+    pub input: InputSource,
+    /// The parts to solve: 1, 2 or both
+    #[arg(long, value_name = "PART", default_value_t)]
+    pub part: Part,
+    /// Show the debug traces of the solution, and its finest ones when given twice (-vv)
+    #[arg(short = 'v', action = ArgAction::Count)]
+    pub verbosity: u8,
+}
+
+impl<const DAY: u8> Default for Args<DAY> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            input: InputSource::of_day(DAY),
+            part: Part::default(),
+            verbosity: 0,
+        }
+    }
+}
+
+impl<const DAY: u8> Args<DAY> {
+    /// Shows the traces asked for with `-v`/`-vv`, see [`trace::init`].
+    #[inline]
+    pub fn init_tracing(&self) {
+        trace::init(self.verbosity);
+    }
+}
+
+/// The command line of a day binary having no option of its own.
+#[derive(Debug, Parser)]
+#[command(about = None, long_about = None)]
+struct DayCommand<const DAY: u8> {
+    #[command(flatten)]
+    args: Args<DAY>,
+}
+
+/// The command line of a binary solving a single part of a day.
+#[derive(Debug, Parser)]
+#[command(about = None, long_about = None)]
+struct PartCommand<const DAY: u8> {
+    /// The puzzle input to read, `-` for stdin
+    #[arg(long, value_name = "PATH", default_value_t = InputSource::of_day(DAY))]
+    input: InputSource,
+    /// Show the debug traces of the solution, and its finest ones when given twice (-vv)
+    #[arg(short = 'v', action = ArgAction::Count)]
+    verbosity: u8,
+}
+
+/// Parses the command line of the binary of `DAY` having no option of its own but `--input PATH`,
+/// `--part 1|2|both` and `-v`/`-vv`, showing the traces asked for.
+pub fn parse_args<const DAY: u8>() -> Args<DAY> {
+    let DayCommand { args } = DayCommand::<DAY>::parse();
+    args.init_tracing();
+    args
+}
+
+/// Parses the command line of the binaries solving a single part of `DAY`: only `--input PATH` and `-v`/`-vv`
+/// are accepted.
+pub fn parse_input_arg<const DAY: u8>() -> InputSource {
+    let PartCommand { input, verbosity } = PartCommand::<DAY>::parse();
+    trace::init(verbosity);
+    input
+}
+
+/// The directory holding the crates of every day.
+pub fn calendar_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc-utils lives next to the days")
+}
+
+/// `name`, or `name.<profile>` for the files of another account than the default one.
+fn with_profile(name: &str, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("{}.{}", name, profile),
+        None => name.to_owned(),
+    }
+}
+
+/// Where the input of `day` is stored: the `dayNN/input` file of the [input directory](config::input_dir)
+/// (the crate of the day by default), or `dayNN/input.<profile>` for the input of another account.
+#[inline]
+pub fn input_path(day: u8, profile: Option<&str>) -> PathBuf {
+    config::input_dir()
+        .join(format!("day{:02}", day))
+        .join(with_profile(DEFAULT_INPUT, profile))
+}
+
+/// The file holding the value of the `session` cookie of adventofcode.com, needed to download the inputs
+/// (they differ from one account to the other): `.session`, or `.session.<profile>` for another account.
+/// `AOC_SESSION` takes precedence over `.session`.
+#[inline]
+pub fn session_path(profile: Option<&str>) -> PathBuf {
+    calendar_dir().join(with_profile(".session", profile))
+}
+
+/// Reads the input of `day` for `profile`, downloading it with the session of that profile and storing it
+/// under `dayNN/input` (`dayNN/input.<profile>`) first if the file is missing.
+#[cfg(feature = "fetch")]
+pub fn fetch(day: u8, profile: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let path = input_path(day, profile);
+    match InputSource::File(path.clone()).read() {
+        Ok(input) => return Ok(input),
+        Err(AocError::MissingInput(_)) => (),
+        Err(err) => return Err(err.into()),
+    }
+
+    let input = download(day, profile)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+    }
+    fs::write(&path, &input)
+        .map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+    Ok(input)
+}
+
+#[cfg(feature = "fetch")]
+fn download(day: u8, profile: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let session = match (profile, config::session()) {
+        (None, Some(session)) => session,
+        _ => {
+            let session_path = session_path(profile);
+            fs::read_to_string(&session_path).map_err(|err| {
+                format!(
+                    "No input for day {} and no session cookie in {}{} to download it: {}",
+                    day,
+                    session_path.display(),
+                    if profile.is_none() {
+                        " or AOC_SESSION"
+                    } else {
+                        ""
+                    },
+                    err
+                )
+            })?
+        }
+    };
+
+    let url = format!(
+        "https://adventofcode.com/{}/day/{}/input",
+        config::year()?,
+        day
+    );
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session.trim()))
+        .set("User-Agent", "github.com/guy-732/aoc-2023")
+        .call()
+        .map_err(|err| format!("Could not download the input of day {}: {}", day, err))?;
+
+    Ok(response.into_string()?)
+}
@@ -0,0 +1,160 @@
+//! Half-open integer intervals, `start..end`, and the ways puzzles carve them up.
+use std::ops::{Range, Sub};
+
+/// The values `start..end`; any interval with `start >= end` is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord + Default + Sub<Output = T>> Interval<T> {
+    #[inline]
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of values in the interval (zero when it is empty).
+    #[inline]
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Splits into the values strictly below `point` and the ones at or above it.
+    ///
+    /// The two halves always partition `self`: `point` is clamped into the interval
+    /// so neither half reaches outside of it.
+    #[inline]
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        if self.is_empty() {
+            return (*self, *self);
+        }
+
+        let point = point.clamp(self.start, self.end);
+        (Self::new(self.start, point), Self::new(point, self.end))
+    }
+
+    /// Splits into the values before `other`, the ones inside it and the ones after it.
+    #[inline]
+    pub fn split_around(&self, other: &Self) -> (Self, Self, Self) {
+        let (before, rest) = self.split_at(other.start);
+        let (inside, after) = rest.split_at(other.end.max(other.start));
+        (before, inside, after)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    #[inline]
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    #[inline]
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::RangeInclusive;
+
+    /// Every interval, empty ones included, with bounds in `-1..=5`.
+    fn intervals() -> impl Iterator<Item = Interval<i32>> {
+        (-1..=5).flat_map(|start| (-1..=5).map(move |end| Interval::new(start, end)))
+    }
+
+    /// The values looked up in the pieces, a bit past the bounds of [`intervals`] on both sides.
+    const VALUES: RangeInclusive<i32> = -3..=7;
+
+    #[test]
+    fn split_at_partitions() {
+        for interval in intervals() {
+            for point in VALUES {
+                let (below, above) = interval.split_at(point);
+                for value in VALUES {
+                    let pieces = [below, above]
+                        .iter()
+                        .filter(|piece| piece.contains(value))
+                        .count();
+                    assert_eq!(
+                        pieces,
+                        interval.contains(value) as usize,
+                        "{:?} at {}",
+                        interval,
+                        point
+                    );
+                    assert!(!below.contains(value) || value < point);
+                    assert!(!above.contains(value) || value >= point);
+                }
+
+                assert_eq!(below.len() + above.len(), interval.len());
+                if !interval.is_empty() {
+                    assert_eq!(below.end, above.start);
+                }
+                if point <= interval.start {
+                    assert!(below.is_empty(), "{:?} at {}", interval, point);
+                }
+                if point >= interval.end {
+                    assert!(above.is_empty(), "{:?} at {}", interval, point);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_around_partitions() {
+        for interval in intervals() {
+            for other in intervals() {
+                let (before, inside, after) = interval.split_around(&other);
+                for value in VALUES {
+                    let pieces = [before, inside, after]
+                        .iter()
+                        .filter(|piece| piece.contains(value))
+                        .count();
+                    assert_eq!(
+                        pieces,
+                        interval.contains(value) as usize,
+                        "{:?} around {:?}",
+                        interval,
+                        other
+                    );
+                    assert!(!before.contains(value) || value < other.start);
+                    assert!(!inside.contains(value) || other.contains(value));
+                    assert!(!after.contains(value) || (value >= other.end && value >= other.start));
+                }
+
+                assert_eq!(before.len() + inside.len() + after.len(), interval.len());
+                if other.start <= interval.start {
+                    assert!(before.is_empty(), "{:?} around {:?}", interval, other);
+                }
+                if other.end.max(other.start) >= interval.end {
+                    assert!(after.is_empty(), "{:?} around {:?}", interval, other);
+                }
+                if other.is_empty() || other.end <= interval.start || other.start >= interval.end {
+                    assert!(inside.is_empty(), "{:?} around {:?}", interval, other);
+                }
+            }
+        }
+    }
+}
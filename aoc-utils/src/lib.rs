@@ -0,0 +1,15 @@
+//! Helpers shared between the days of the calendar.
+pub mod config;
+pub mod cycles;
+pub mod dot;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod progress;
+pub mod registry;
+pub mod solution;
+pub mod svg;
+pub mod trace;
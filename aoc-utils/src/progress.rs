@@ -0,0 +1,47 @@
+//! Progress bars for the searches that can run for minutes, drawn on stderr with the `progress` feature and
+//! doing nothing without it.
+#[cfg(feature = "progress")]
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Counts the items of a search done so far out of a known total, showing an ETA. Cloning it gives another
+/// handle on the same bar, which can be moved to the threads sharing the work.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    #[cfg(feature = "progress")]
+    bar: ProgressBar,
+}
+
+impl Progress {
+    /// Starts a bar labelled `message` for `total` items.
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    pub fn new(message: &str, total: u64) -> Self {
+        #[cfg(feature = "progress")]
+        {
+            let bar = ProgressBar::new(total).with_message(message.to_owned());
+            bar.set_style(
+                ProgressStyle::with_template(
+                    "{msg} [{elapsed_precise}] {wide_bar} {pos}/{len} (ETA {eta})",
+                )
+                .expect("the template is valid"),
+            );
+            Self { bar }
+        }
+
+        #[cfg(not(feature = "progress"))]
+        Self {}
+    }
+
+    /// One more item is done.
+    #[inline]
+    pub fn inc(&self) {
+        #[cfg(feature = "progress")]
+        self.bar.inc(1);
+    }
+
+    /// Removes the bar, once every item is done.
+    #[inline]
+    pub fn finish(&self) {
+        #[cfg(feature = "progress")]
+        self.bar.finish_and_clear();
+    }
+}
@@ -0,0 +1,130 @@
+//! The days of the calendar, found by the `aoc` runner when it starts rather than listed by hand: the crate of
+//! every day registers its [`Solution`] with [`register_day!`](crate::register_day), the runner only has to
+//! link the crates in.
+use crate::solution::{Answer, Solution};
+use std::error::Error;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// The puzzle input of a day once parsed, whatever the type solving it.
+pub trait ParsedDay {
+    fn part1(&self) -> Result<Answer, Box<dyn Error>>;
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>>;
+}
+
+impl<S: Solution> ParsedDay for S {
+    #[inline]
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Solution::part1(self)
+    }
+
+    #[inline]
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Solution::part2(self)
+    }
+}
+
+/// Parses the puzzle input of a day with its [`Solution`].
+pub type Parser = fn(&str) -> Result<Box<dyn ParsedDay>, Box<dyn Error>>;
+
+/// An example given by the puzzle, with the answers it gives for the parts it is an example of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    #[inline]
+    pub const fn both(input: &'static str, part1: i128, part2: i128) -> Self {
+        Self {
+            input,
+            part1: Some(Answer::Number(part1)),
+            part2: Some(Answer::Number(part2)),
+        }
+    }
+
+    #[inline]
+    pub const fn part1_only(input: &'static str, part1: i128) -> Self {
+        Self {
+            input,
+            part1: Some(Answer::Number(part1)),
+            part2: None,
+        }
+    }
+
+    #[inline]
+    pub const fn part2_only(input: &'static str, part2: i128) -> Self {
+        Self {
+            input,
+            part1: None,
+            part2: Some(Answer::Number(part2)),
+        }
+    }
+}
+
+/// A day, as registered by its crate.
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredDay {
+    pub day: u8,
+    pub parse: Parser,
+    /// parses one of the `examples` (see [`Solution::parse_example`])
+    pub parse_example: Parser,
+    pub examples: &'static [Example],
+}
+
+impl RegisteredDay {
+    #[inline]
+    pub const fn new<S: Solution + 'static>(day: u8, examples: &'static [Example]) -> Self {
+        Self {
+            day,
+            parse: parse_boxed::<S>,
+            parse_example: parse_example_boxed::<S>,
+            examples,
+        }
+    }
+}
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn ParsedDay>, Box<dyn Error>> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn parse_example_boxed<S: Solution + 'static>(
+    input: &str,
+) -> Result<Box<dyn ParsedDay>, Box<dyn Error>> {
+    Ok(Box::new(S::parse_example(input)?))
+}
+
+inventory::collect!(RegisteredDay);
+
+/// Registers the [`Solution`](crate::solution::Solution) of a day, for the runner to find it:
+/// `register_day!(19, Day19);` next to the type, or with the [`Example`]s of the puzzle:
+/// `register_day!(19, Day19, examples = [Example::both(include_str!("../example"), 19114, 167409079868000)]);`
+#[macro_export]
+macro_rules! register_day {
+    ($day:literal, $solution:ty) => {
+        $crate::register_day!($day, $solution, examples = []);
+    };
+    ($day:literal, $solution:ty, examples = [$($example:expr),* $(,)?]) => {
+        const _: () = assert!($day >= 1 && $day <= 25, "The days of the calendar go from 1 to 25");
+
+        $crate::registry::inventory::submit! {
+            $crate::registry::RegisteredDay::new::<$solution>($day, &[$($example),*])
+        }
+    };
+}
+
+/// The solution registered for `day` by the crates linked in: an error if none of them registered one, or
+/// several did.
+pub fn find(day: u8) -> Result<&'static RegisteredDay, String> {
+    let mut registered =
+        inventory::iter::<RegisteredDay>().filter(|registered| registered.day == day);
+    match (registered.next(), registered.next()) {
+        (Some(registered), None) => Ok(registered),
+        (None, _) => Err(format!("No solution is registered for day {}", day)),
+        (Some(_), Some(_)) => Err(format!("Several solutions are registered for day {}", day)),
+    }
+}
@@ -0,0 +1,116 @@
+//! The interface every day of the calendar implements, so that the days can be called as libraries
+//! (by the `aoc` runner) and not only through their own binaries.
+use crate::input::{self, Args};
+use std::{error::Error, fmt, str::FromStr};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    /// the second part of day 25, which has no puzzle
+    NoPuzzle,
+}
+
+macro_rules! answer_from {
+    ($($t:ty)*) => ($(
+        impl From<$t> for Answer {
+            #[inline]
+            fn from(value: $t) -> Self {
+                Self::Number(value as i128)
+            }
+        }
+    )*)
+}
+
+answer_from!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize);
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{}", value),
+            Self::NoPuzzle => write!(f, "-"),
+        }
+    }
+}
+
+/// Which parts of a puzzle to solve (`--part 1|2|both`), letting an expensive part be skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Part {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            "both" => Ok(Self::Both),
+            other => Err(format!("Unknown part {:?} (expected 1, 2 or both)", other)),
+        }
+    }
+}
+
+/// The way `--part` takes it.
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl Part {
+    #[inline]
+    pub fn solves_part1(self) -> bool {
+        matches!(self, Self::One | Self::Both)
+    }
+
+    #[inline]
+    pub fn solves_part2(self) -> bool {
+        matches!(self, Self::Two | Self::Both)
+    }
+}
+
+/// A day of the calendar: its input parsed once, then both parts answered from what was parsed.
+pub trait Solution: Sized {
+    /// Parses the puzzle input, given as the content of the `input` file rather than its path.
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>>;
+
+    /// Parses one of the examples of the puzzle, which some days solve with other parameters than the real
+    /// input (fewer steps, a smaller area...).
+    #[inline]
+    fn parse_example(input: &str) -> Result<Self, Box<dyn Error>> {
+        Self::parse(input)
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>>;
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>>;
+}
+
+/// The `main` of the binary of `DAY` when it has no option of its own: parses its command line (see
+/// [`input::parse_args`]), then solves the parts asked for with `S`, printing their answers or the error that
+/// stopped it.
+pub fn main<S: Solution, const DAY: u8>() {
+    if let Err(err) = solve::<S, DAY>(&input::parse_args::<DAY>()) {
+        eprintln!("Error occurred: {}\nDebug: {:#?}", err, err);
+    }
+}
+
+fn solve<S: Solution, const DAY: u8>(args: &Args<DAY>) -> Result<(), Box<dyn Error>> {
+    let solution = S::parse(&args.input.read()?)?;
+    if args.part.solves_part1() {
+        println!("Part 1 answer: {}", solution.part1()?);
+    }
+    if args.part.solves_part2() {
+        println!("Part 2 answer: {}", solution.part2()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Minimal SVG document builder, enough to draw polygons and line segments.
+use std::{
+    fmt::{self, Write as _},
+    fs, io,
+    path::Path,
+};
+
+/// An SVG document in user coordinates, `x` growing to the right and `y` growing downwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Svg {
+    view_box: (i64, i64, i64, i64),
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// Creates an empty document showing the `width` x `height` area starting at (`min_x`, `min_y`).
+    pub fn new(min_x: i64, min_y: i64, width: i64, height: i64) -> Self {
+        Self {
+            view_box: (min_x, min_y, width, height),
+            elements: vec![],
+        }
+    }
+
+    /// Creates an empty document fitting all `points` with `margin` units of space around them.
+    pub fn fitting<'p>(points: impl IntoIterator<Item = &'p (i64, i64)>, margin: i64) -> Self {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (i64::MAX, i64::MAX, i64::MIN, i64::MIN);
+        for &(x, y) in points {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        if min_x > max_x {
+            return Self::new(0, 0, 2 * margin, 2 * margin);
+        }
+
+        Self::new(
+            min_x - margin,
+            min_y - margin,
+            max_x - min_x + 2 * margin,
+            max_y - min_y + 2 * margin,
+        )
+    }
+
+    /// Adds a closed polygon filled with `fill` (any SVG color, e.g. `"#rrggbb"` or `"none"`).
+    pub fn polygon(&mut self, points: &[(i64, i64)], fill: &str) {
+        let mut element = String::from("<polygon points=\"");
+        for (i, (x, y)) in points.iter().enumerate() {
+            if i != 0 {
+                element.push(' ');
+            }
+            write!(element, "{},{}", x, y).expect("Writing to a String cannot fail");
+        }
+        write!(element, "\" fill=\"{}\" />", fill).expect("Writing to a String cannot fail");
+        self.elements.push(element);
+    }
+
+    /// Adds a straight line from `from` to `to`.
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), stroke: &str, stroke_width: f64) {
+        self.elements.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"square\" />",
+            from.0, from.1, to.0, to.1, stroke, stroke_width
+        ));
+    }
+
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min_x, min_y, width, height) = self.view_box;
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+            min_x, min_y, width, height
+        )?;
+        for element in self.elements.iter() {
+            writeln!(f, "  {}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
@@ -0,0 +1,20 @@
+//! The debug traces of the days (range splits, pulses, falling bricks...), written with `tracing` and shown on
+//! stderr when a binary is given `-v` (debug) or `-vv` (trace).
+use std::io::{self, IsTerminal};
+use tracing::Level;
+
+/// Shows the traces up to the level of `verbosity`, the number of `-v` given: none of them without any.
+pub fn init(verbosity: u8) {
+    let level = match verbosity {
+        0 => return,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .init();
+}
@@ -0,0 +1,46 @@
+//! Criterion benchmarks of the days: a `dayNN` group per day (`dayNN.<profile>` for the inputs of a profile),
+//! benchmarking `parse`, `part1` and `part2`.
+use aoc_utils::{registry::RegisteredDay, solution::Part};
+use criterion::{black_box, Criterion};
+use std::{error::Error, time::Duration};
+
+/// Criterion's configuration, with fewer samples than its default as some parts take seconds.
+pub(crate) fn criterion() -> Criterion {
+    Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_secs(1))
+}
+
+/// Benchmarks parsing `input` (that of `profile`) and answering the chosen parts of the `registered` day.
+/// The parts are answered once before being benchmarked, so that a failing day is reported instead.
+pub(crate) fn bench(
+    criterion: &mut Criterion,
+    registered: &RegisteredDay,
+    profile: Option<&str>,
+    input: &str,
+    part: Part,
+) -> Result<(), Box<dyn Error>> {
+    let (day, parse) = (registered.day, registered.parse);
+    let solution = parse(input)?;
+    if part.solves_part1() {
+        solution.part1()?;
+    }
+    if part.solves_part2() {
+        solution.part2()?;
+    }
+
+    let mut group = match profile {
+        Some(profile) => criterion.benchmark_group(format!("day{:02}.{}", day, profile)),
+        None => criterion.benchmark_group(format!("day{:02}", day)),
+    };
+    group.bench_function("parse", |b| b.iter(|| parse(black_box(input))));
+    if part.solves_part1() {
+        group.bench_function("part1", |b| b.iter(|| solution.part1()));
+    }
+    if part.solves_part2() {
+        group.bench_function("part2", |b| b.iter(|| solution.part2()));
+    }
+    group.finish();
+
+    Ok(())
+}
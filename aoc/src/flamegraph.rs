@@ -0,0 +1,24 @@
+//! `aoc run --flamegraph PATH` (with the `flamegraph` feature): the stacks of every thread sampled with pprof
+//! while a day is solved, written as an SVG flame graph to find where its parts spend their time.
+use std::{error::Error, fs::File, path::Path};
+
+/// Samples per second, enough for the parts taking a few milliseconds to show up.
+const FREQUENCY: i32 = 1000;
+
+/// Calls `f`, writing the flame graph of the stacks sampled meanwhile to `path`.
+pub(crate) fn sample<T>(path: &Path, f: impl FnOnce() -> T) -> Result<T, Box<dyn Error>> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    let result = f();
+    let report = guard.report().build()?;
+
+    let file = File::create(path)
+        .map_err(|err| format!("Could not create {}: {}", path.display(), err))?;
+    report
+        .flamegraph(file)
+        .map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+
+    Ok(result)
+}
@@ -0,0 +1,627 @@
+mod bench;
+#[cfg(feature = "flamegraph")]
+mod flamegraph;
+mod report;
+mod table;
+mod timings;
+#[cfg(feature = "tui")]
+mod tui;
+mod verify;
+
+// The days register their solution with `register_day!`: their crates only have to be linked in for the
+// runner to find them.
+extern crate day01;
+extern crate day02;
+extern crate day03;
+extern crate day04;
+extern crate day05;
+extern crate day06;
+extern crate day07;
+extern crate day08;
+extern crate day09;
+extern crate day10;
+extern crate day11;
+extern crate day12;
+extern crate day13;
+extern crate day14;
+extern crate day15;
+extern crate day16;
+extern crate day17;
+extern crate day18;
+extern crate day19;
+extern crate day20;
+extern crate day21;
+extern crate day22;
+extern crate day23;
+extern crate day24;
+extern crate day25;
+
+use aoc_utils::{
+    input::{self, InputSource},
+    registry::{self, Parser},
+    solution::{Answer, Part},
+    trace,
+};
+use clap::{ArgAction, Parser as _};
+use rayon::prelude::*;
+use std::{
+    error::Error,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process::{self, ExitCode},
+    time::{Duration, Instant},
+};
+use verify::ExampleCheck;
+
+/// Days of the calendar, every one of them solved by the `dayNN` crate next to this one.
+const DAYS: RangeInclusive<u8> = 1..=25;
+
+/// Exit status when some of the days failed, returning an error or panicking (`aoc` exits with 1 when it
+/// could not do what it was asked at all).
+const DAYS_FAILED: u8 = 2;
+/// Exit status of `aoc verify` when the days were all solved but some of their answers were not those expected.
+const WRONG_ANSWERS: u8 = 3;
+
+/// Solves the days of the calendar, checking, benchmarking and reporting on their answers.
+#[derive(Debug, Clone, clap::Parser)]
+#[command(
+    name = "aoc",
+    after_help = "Exits with 2 when some of the days failed or panicked, and with 3 when verify got wrong answers."
+)]
+struct Cli {
+    #[command(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, clap::Subcommand)]
+enum Subcommand {
+    /// Solve the given days, in order
+    Run(Selection),
+    /// Benchmark the given days with criterion, in order
+    Bench(Selection),
+    /// Solve the given days and compare their answers to those stored in `answers.toml` (`answers.<profile>.toml`)
+    Verify(Selection),
+    /// Show the board of every day, solving the given days first (with the `tui` feature)
+    Tui(Selection),
+    /// Solve the given days (every day by default) and write a Markdown report of the run
+    Report(Selection),
+}
+
+/// A day given on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayArg {
+    Day(u8),
+    /// `-`: read the input of the only day from stdin
+    Stdin,
+}
+
+/// The days a subcommand works on.
+#[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
+#[command(about = None, long_about = None)]
+struct Selection {
+    /// The days to work on, `-` after the only one reading its input from stdin
+    #[arg(value_name = "DAY", value_parser = parse_day)]
+    day_args: Vec<DayArg>,
+    /// Work on every day
+    #[arg(long)]
+    all: bool,
+    /// the days of `day_args` and `--all`, in order, see [`Selection::resolve_days`]
+    #[arg(skip)]
+    days: Vec<u8>,
+    /// whether `-` was given
+    #[arg(skip)]
+    stdin: bool,
+    /// The parts to solve: 1, 2 or both
+    #[arg(long, value_name = "PART", default_value_t)]
+    part: Part,
+    /// The account whose inputs (`dayNN/input.NAME`) and answers are used instead of the default one
+    #[arg(long, value_name = "NAME", value_parser = parse_profile)]
+    profile: Option<String>,
+    /// Show the debug traces of the days, and their finest ones when given twice (-vv)
+    #[arg(short = 'v', action = ArgAction::Count)]
+    verbosity: u8,
+    /// Append the timings of the days solved to this CSV file
+    #[arg(long, value_name = "PATH")]
+    timings_csv: Option<PathBuf>,
+    /// Solve several days one after the other rather than all at once, so that their timings are not disturbed
+    /// by each other
+    #[arg(long)]
+    sequential: bool,
+    /// Verify the answers of the examples bundled with the days instead of the inputs
+    #[arg(long)]
+    example: bool,
+    /// Write the flame graph of the only day solved to this SVG file (with the `flamegraph` feature)
+    #[arg(long, value_name = "PATH")]
+    flamegraph: Option<PathBuf>,
+}
+
+impl Selection {
+    #[inline]
+    fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Fills [`Selection::days`] and [`Selection::stdin`] from the days given and `--all`.
+    fn resolve_days(mut self) -> Self {
+        for &day_arg in &self.day_args {
+            match day_arg {
+                DayArg::Day(day) => self.days.push(day),
+                DayArg::Stdin => self.stdin = true,
+            }
+        }
+        if self.all {
+            self.days.extend(DAYS);
+        }
+
+        self
+    }
+}
+
+fn parse_options() -> Result<Subcommand, Box<dyn Error>> {
+    let cli = Cli::try_parse().unwrap_or_else(|err| {
+        // clap exits with 2 on a usage error, which already means that some of the days failed
+        let _ = err.print();
+        process::exit(if err.use_stderr() { 1 } else { 0 })
+    });
+
+    match cli.subcommand {
+        Subcommand::Run(selection) => {
+            let selection = parse_selection(selection)?;
+            if selection.example {
+                return Err("Only verify solves the examples (--example)".into());
+            }
+            if selection.flamegraph.is_some() && selection.days.len() > 1 {
+                return Err("The flame graph (--flamegraph) is that of a single day".into());
+            }
+
+            Ok(Subcommand::Run(selection))
+        }
+        Subcommand::Bench(selection) => {
+            let selection = parse_selection(selection)?;
+            if selection.example {
+                return Err("Only verify solves the examples (--example)".into());
+            }
+            if selection.timings_csv.is_some() {
+                return Err("The benchmarks do not write their timings to --timings-csv".into());
+            }
+            if selection.flamegraph.is_some() {
+                return Err("Only run samples a day into a flame graph (--flamegraph)".into());
+            }
+
+            Ok(Subcommand::Bench(selection))
+        }
+        Subcommand::Verify(selection) => {
+            let selection = parse_selection(selection)?;
+            if selection.example && selection.stdin {
+                return Err("The examples are bundled with the days, not read from stdin".into());
+            }
+            if selection.example && selection.timings_csv.is_some() {
+                return Err("The timings of the examples are not written to --timings-csv".into());
+            }
+            if selection.flamegraph.is_some() {
+                return Err("Only run samples a day into a flame graph (--flamegraph)".into());
+            }
+
+            Ok(Subcommand::Verify(selection))
+        }
+        Subcommand::Tui(selection) => {
+            let selection = selection.resolve_days();
+            if selection.stdin {
+                return Err("The board cannot read an input from stdin".into());
+            }
+            if selection.verbosity > 0 {
+                return Err("The board cannot show the traces of the days".into());
+            }
+            if selection.timings_csv.is_some() {
+                return Err("The board does not write its timings to --timings-csv".into());
+            }
+            if selection.flamegraph.is_some() {
+                return Err("Only run samples a day into a flame graph (--flamegraph)".into());
+            }
+            if selection.example {
+                return Err("Only verify solves the examples (--example)".into());
+            }
+
+            Ok(Subcommand::Tui(selection))
+        }
+        Subcommand::Report(selection) => {
+            let mut selection = selection.resolve_days();
+            if selection.stdin {
+                return Err("A report cannot be made from an input read from stdin".into());
+            }
+            if selection.flamegraph.is_some() {
+                return Err("Only run samples a day into a flame graph (--flamegraph)".into());
+            }
+            if selection.example {
+                return Err("Only verify solves the examples (--example)".into());
+            }
+            if selection.days.is_empty() {
+                selection.days.extend(DAYS);
+            }
+
+            Ok(Subcommand::Report(selection))
+        }
+    }
+}
+
+/// Resolves the days to work on, requiring at least one of them.
+fn parse_selection(selection: Selection) -> Result<Selection, Box<dyn Error>> {
+    let selection = selection.resolve_days();
+    if selection.days.is_empty() {
+        return Err("Expected the days to work on or --all".into());
+    }
+
+    if selection.stdin && selection.days.len() > 1 {
+        return Err("Only a single day can read its input from stdin".into());
+    }
+
+    Ok(selection)
+}
+
+/// The name of a profile ends up in file names, only letters, digits, `-` and `_` are allowed.
+fn parse_profile(profile: &str) -> Result<String, String> {
+    if profile.is_empty()
+        || !profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid profile name {:?} (expected letters, digits, '-' or '_')",
+            profile
+        ));
+    }
+
+    Ok(profile.to_owned())
+}
+
+fn parse_day(day: &str) -> Result<DayArg, String> {
+    if day == "-" {
+        return Ok(DayArg::Stdin);
+    }
+
+    match day.parse() {
+        Ok(day) if DAYS.contains(&day) => Ok(DayArg::Day(day)),
+        _ => Err(format!(
+            "Expected a day between 1 and 25 or -, got {:?}",
+            day
+        )),
+    }
+}
+
+/// The answers of a day (for the parts that were solved) and how long each step took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DayRun {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+    parse_time: Duration,
+    part1_time: Duration,
+    part2_time: Duration,
+}
+
+impl DayRun {
+    #[inline]
+    fn total_time(&self) -> Duration {
+        self.parse_time + self.part1_time + self.part2_time
+    }
+}
+
+/// Calls `f`, a step of a day, turning a panic into an error saying in which `step` it happened (the panic
+/// hook printing where), so that the other days are still solved.
+fn catch_panic<T>(
+    step: &str,
+    f: impl FnOnce() -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("no message");
+        Err(format!("panicked while {}: {}", step, message).into())
+    })
+}
+
+/// Parses `input` with `parse` (the parser of a day or of its examples) and answers the chosen parts, timing
+/// every step.
+fn run(parse: Parser, input: &str, part: Part) -> Result<DayRun, Box<dyn Error>> {
+    let start = Instant::now();
+    let solution = catch_panic("parsing", || parse(input))?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = if part.solves_part1() {
+        Some(catch_panic("solving part 1", || solution.part1())?)
+    } else {
+        None
+    };
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = if part.solves_part2() {
+        Some(catch_panic("solving part 2", || solution.part2())?)
+    } else {
+        None
+    };
+    let part2_time = start.elapsed();
+
+    Ok(DayRun {
+        part1,
+        part2,
+        parse_time,
+        part1_time,
+        part2_time,
+    })
+}
+
+#[inline]
+fn read_input(day: u8, selection: &Selection) -> Result<String, Box<dyn Error>> {
+    if selection.stdin {
+        Ok(InputSource::Stdin.read()?)
+    } else {
+        input::fetch(day, selection.profile())
+    }
+}
+
+fn run_day(day: u8, selection: &Selection) -> Result<DayRun, Box<dyn Error>> {
+    let registered = registry::find(day)?;
+    let input = read_input(day, selection)?;
+    run(registered.parse, &input, selection.part)
+}
+
+/// Appends the timings of `runs` to the `--timings-csv` file of `selection`, if it has one.
+fn record_timings(selection: &Selection, runs: &[(u8, DayRun)]) -> Result<(), Box<dyn Error>> {
+    match &selection.timings_csv {
+        Some(path) => timings::append(path, selection, runs),
+        None => Ok(()),
+    }
+}
+
+/// The days of a selection once they were all run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SelectionRun {
+    /// the days solved, in order
+    runs: Vec<(u8, DayRun)>,
+    /// the error of each day that could not be solved
+    failures: Vec<(u8, String)>,
+    wall_clock: Duration,
+}
+
+impl SelectionRun {
+    #[inline]
+    fn total_time(&self) -> Duration {
+        self.runs.iter().map(|(_, run)| run.total_time()).sum()
+    }
+}
+
+/// Solves the days of `selection`, each of them on its own task of the rayon pool unless `--sequential` was
+/// given. The days parallelizing their own work (day 12, 13, 22...) share that pool, rather than starting
+/// more threads than there are cores.
+fn run_days(selection: &Selection) -> SelectionRun {
+    let start = Instant::now();
+    let solve = |&day: &u8| (day, run_day(day, selection).map_err(|err| err.to_string()));
+    let results: Vec<(u8, Result<DayRun, String>)> = if selection.sequential {
+        selection.days.iter().map(solve).collect()
+    } else {
+        selection
+            .days
+            .par_iter()
+            .with_max_len(1)
+            .map(solve)
+            .collect()
+    };
+
+    let mut runs = vec![];
+    let mut failures = vec![];
+    for (day, result) in results {
+        match result {
+            Ok(run) => runs.push((day, run)),
+            Err(err) => failures.push((day, err)),
+        }
+    }
+
+    SelectionRun {
+        runs,
+        failures,
+        wall_clock: start.elapsed(),
+    }
+}
+
+fn print_day(day: u8, run: &DayRun) {
+    println!(
+        "Day {:02}: {:?} (parsing {:?})",
+        day,
+        run.total_time(),
+        run.parse_time
+    );
+    if let Some(answer) = &run.part1 {
+        println!("    Part 1: {} ({:?})", answer, run.part1_time);
+    }
+    if let Some(answer) = &run.part2 {
+        println!("    Part 2: {} ({:?})", answer, run.part2_time);
+    }
+}
+
+/// The exit status once `failures` days failed and `wrong` others got answers other than those expected.
+fn exit_status(failures: usize, wrong: usize) -> ExitCode {
+    if failures > 0 {
+        ExitCode::from(DAYS_FAILED)
+    } else if wrong > 0 {
+        ExitCode::from(WRONG_ANSWERS)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_options().and_then(|subcommand| solve(&subcommand)) {
+        Ok(status) => status,
+        Err(err) => {
+            eprintln!("Error occurred: {}\nDebug: {:#?}", err, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn solve(subcommand: &Subcommand) -> Result<ExitCode, Box<dyn Error>> {
+    match subcommand {
+        Subcommand::Run(selection)
+        | Subcommand::Bench(selection)
+        | Subcommand::Verify(selection)
+        | Subcommand::Tui(selection)
+        | Subcommand::Report(selection) => trace::init(selection.verbosity),
+    }
+
+    match subcommand {
+        Subcommand::Run(selection) if selection.days.len() == 1 => {
+            let day = selection.days[0];
+            let result = match &selection.flamegraph {
+                #[cfg(feature = "flamegraph")]
+                Some(path) => flamegraph::sample(path, || run_day(day, selection))?,
+                #[cfg(not(feature = "flamegraph"))]
+                Some(_) => return Err("aoc was built without the flamegraph feature".into()),
+                None => run_day(day, selection),
+            };
+            let run = match result {
+                Ok(run) => run,
+                Err(err) => {
+                    eprintln!("Day {:02} failed: {}", day, err);
+                    return Ok(exit_status(1, 0));
+                }
+            };
+            print_day(day, &run);
+            record_timings(selection, &[(day, run)])?;
+        }
+        Subcommand::Run(selection) => {
+            let run = run_days(selection);
+            for (day, err) in &run.failures {
+                eprintln!("Day {:02} failed: {}", day, err);
+            }
+
+            table::print(&run.runs);
+            println!(
+                "Total: {:.2?} solving, {:.2?} wall clock ({} of {} days solved)",
+                run.total_time(),
+                run.wall_clock,
+                run.runs.len(),
+                selection.days.len()
+            );
+            record_timings(selection, &run.runs)?;
+            return Ok(exit_status(run.failures.len(), 0));
+        }
+        Subcommand::Bench(selection) => {
+            let mut criterion = bench::criterion();
+            let mut failures = 0;
+            for &day in &selection.days {
+                let result = registry::find(day)
+                    .map_err(Into::into)
+                    .and_then(|registered| {
+                        let input = read_input(day, selection)?;
+                        catch_panic("benchmarking", || {
+                            bench::bench(
+                                &mut criterion,
+                                registered,
+                                selection.profile(),
+                                &input,
+                                selection.part,
+                            )
+                        })
+                    });
+                if let Err(err) = result {
+                    eprintln!("Day {:02} failed: {}", day, err);
+                    failures += 1;
+                }
+            }
+
+            criterion.final_summary();
+            return Ok(exit_status(failures, 0));
+        }
+        Subcommand::Verify(selection) if selection.example => {
+            let days = &selection.days;
+            let (mut failures, mut wrong) = (0, 0);
+            for &day in days {
+                let check = match registry::find(day) {
+                    Ok(solution) => verify::check_examples(solution, selection.part),
+                    Err(err) => {
+                        println!("Day {:02}: FAILED, {}", day, err);
+                        ExampleCheck::Failed
+                    }
+                };
+                match check {
+                    ExampleCheck::Matches => (),
+                    ExampleCheck::Wrong => wrong += 1,
+                    ExampleCheck::Failed => failures += 1,
+                }
+            }
+
+            if failures > 0 {
+                eprintln!(
+                    "{} of {} days failed to solve their examples",
+                    failures,
+                    days.len()
+                );
+            }
+            if wrong > 0 {
+                eprintln!("{} of {} days got their examples wrong", wrong, days.len());
+            }
+            if failures == 0 && wrong == 0 {
+                println!(
+                    "All {} days match the answers of their examples",
+                    days.len()
+                );
+            }
+            return Ok(exit_status(failures, wrong));
+        }
+        Subcommand::Verify(selection) => {
+            let days = &selection.days;
+            let answers = verify::Answers::load(selection.profile())?;
+            let (mut failures, mut regressions) = (0, 0);
+            let mut runs = vec![];
+            for &day in days {
+                match run_day(day, selection) {
+                    Ok(run) => {
+                        if !answers.check(day, &run)? {
+                            regressions += 1;
+                        }
+                        runs.push((day, run));
+                    }
+                    Err(err) => {
+                        println!("Day {:02}: FAILED, {}", day, err);
+                        failures += 1;
+                    }
+                }
+            }
+
+            record_timings(selection, &runs)?;
+
+            if failures > 0 {
+                eprintln!("{} of {} days failed", failures, days.len());
+            }
+            if regressions > 0 {
+                eprintln!("{} of {} days regressed", regressions, days.len());
+            }
+            if failures == 0 && regressions == 0 {
+                println!("All {} days match the stored answers", days.len());
+            }
+            return Ok(exit_status(failures, regressions));
+        }
+        #[cfg(feature = "tui")]
+        Subcommand::Tui(selection) => tui::run(selection)?,
+        #[cfg(not(feature = "tui"))]
+        Subcommand::Tui(_) => return Err("aoc was built without the tui feature".into()),
+        Subcommand::Report(selection) => {
+            let run = run_days(selection);
+            let path = report::write(selection, &run)?;
+            record_timings(selection, &run.runs)?;
+            println!(
+                "Report of {} of {} days written to {}",
+                run.runs.len(),
+                selection.days.len(),
+                path.display()
+            );
+            return Ok(exit_status(run.failures.len(), 0));
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,266 @@
+//! `aoc report`: a Markdown report of a run of the days, with their answers and timings, the machine they ran
+//! on and how they compare to the previous report. The previous run is read from `report.toml`, written next
+//! to `report.md` (`report.<profile>.toml` and `report.<profile>.md` for the inputs of a profile).
+use crate::{table, DayRun, Selection, SelectionRun};
+use aoc_utils::{config, input};
+use std::{
+    env,
+    error::Error,
+    fmt::Write,
+    fs, io,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use toml::{Table, Value};
+
+/// `name.extension`, or `name.<profile>.extension` for the inputs of a profile.
+fn report_path(profile: Option<&str>, extension: &str) -> PathBuf {
+    match profile {
+        Some(profile) => input::calendar_dir().join(format!("report.{}.{}", profile, extension)),
+        None => input::calendar_dir().join(format!("report.{}", extension)),
+    }
+}
+
+/// Writes the report of `run`, the days of `selection`, returning where.
+pub(crate) fn write(selection: &Selection, run: &SelectionRun) -> Result<PathBuf, Box<dyn Error>> {
+    let data_path = report_path(selection.profile(), "toml");
+    let previous = match fs::read_to_string(&data_path) {
+        Ok(text) => Some(
+            text.parse::<Table>()
+                .map_err(|err| format!("{} is not valid TOML: {}", data_path.display(), err))?,
+        ),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(format!("Could not read {}: {}", data_path.display(), err).into()),
+    };
+
+    let report = markdown(selection, run, previous.as_ref())?;
+    let report_path = report_path(selection.profile(), "md");
+    fs::write(&report_path, report)
+        .map_err(|err| format!("Could not write {}: {}", report_path.display(), err))?;
+    fs::write(&data_path, data(&run.runs).to_string())
+        .map_err(|err| format!("Could not write {}: {}", data_path.display(), err))?;
+
+    Ok(report_path)
+}
+
+/// What the next report compares itself to: the answers and total time of every day solved.
+fn data(runs: &[(u8, DayRun)]) -> Table {
+    let mut table = Table::new();
+    for (day, run) in runs {
+        let mut day_table = Table::new();
+        for (part, answer) in [("part1", &run.part1), ("part2", &run.part2)] {
+            if let Some(answer) = answer {
+                day_table.insert(part.to_owned(), Value::String(answer.to_string()));
+            }
+        }
+        day_table.insert(
+            "total_nanos".to_owned(),
+            Value::Integer(run.total_time().as_nanos() as i64),
+        );
+        table.insert(format!("day{:02}", day), Value::Table(day_table));
+    }
+
+    table
+}
+
+/// The total time of `day` in the previous run, if it was solved then.
+fn previous_total(previous: Option<&Table>, day: u8) -> Option<Duration> {
+    match previous?
+        .get(&format!("day{:02}", day))?
+        .get("total_nanos")?
+    {
+        Value::Integer(nanos) => Some(Duration::from_nanos(*nanos as u64)),
+        _ => None,
+    }
+}
+
+/// How much slower (positive) or faster (negative) `now` is than `before`.
+fn change(now: Duration, before: Duration) -> String {
+    format!(
+        "{:+.1}%",
+        (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0
+    )
+}
+
+fn markdown(
+    selection: &Selection,
+    run: &SelectionRun,
+    previous: Option<&Table>,
+) -> Result<String, Box<dyn Error>> {
+    let runs = &run.runs;
+    let mut report = String::new();
+    writeln!(report, "# Advent of Code {} run report", config::year()?)?;
+    writeln!(report)?;
+    let build = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    write!(
+        report,
+        "Generated {}, {} build",
+        utc_date_time(SystemTime::now()),
+        build
+    )?;
+    if let Some(profile) = selection.profile() {
+        write!(report, ", inputs of the `{}` profile", profile)?;
+    }
+    writeln!(report, ".")?;
+    writeln!(report)?;
+
+    writeln!(report, "## Machine")?;
+    writeln!(report)?;
+    writeln!(report, "- OS: {} ({})", env::consts::OS, env::consts::ARCH)?;
+    writeln!(
+        report,
+        "- CPU: {} ({} threads)",
+        cpu_model().unwrap_or_else(|| "unknown".to_owned()),
+        thread::available_parallelism().map_or(1, |threads| threads.get())
+    )?;
+    writeln!(report)?;
+
+    writeln!(report, "## Days")?;
+    writeln!(report)?;
+    let headers: Vec<&str> = table::HEADERS
+        .into_iter()
+        .chain(["Previous total", "Change"])
+        .collect();
+    writeln!(report, "| {} |", headers.join(" | "))?;
+    let alignments: Vec<&str> = table::LEFT_ALIGNED
+        .into_iter()
+        .chain([false, false])
+        .map(|left| if left { ":---" } else { "---:" })
+        .collect();
+    writeln!(report, "| {} |", alignments.join(" | "))?;
+    for (day, day_run) in runs {
+        let mut cells = table::row(*day, day_run).to_vec();
+        match previous_total(previous, *day) {
+            Some(before) => cells.extend([
+                format!("{:.2?}", before),
+                change(day_run.total_time(), before),
+            ]),
+            None => cells.extend([String::new(), String::new()]),
+        }
+        writeln!(report, "| {} |", cells.join(" | "))?;
+    }
+    writeln!(report)?;
+
+    write!(
+        report,
+        "**Total:** {:.2?} solving, {:.2?} wall clock ({} of {} days solved)",
+        run.total_time(),
+        run.wall_clock,
+        runs.len(),
+        selection.days.len()
+    )?;
+    // only the days solved both times are compared
+    let compared: Vec<(Duration, Duration)> = runs
+        .iter()
+        .filter_map(|(day, day_run)| Some((day_run.total_time(), previous_total(previous, *day)?)))
+        .collect();
+    if !compared.is_empty() {
+        let (now, before) = compared.iter().fold(
+            (Duration::ZERO, Duration::ZERO),
+            |(now, before), (day_now, day_before)| (now + *day_now, before + *day_before),
+        );
+        write!(
+            report,
+            ", {} on the {} days also solved by the previous run",
+            change(now, before),
+            compared.len()
+        )?;
+    }
+    writeln!(report, ".")?;
+
+    let changed_answers = changed_answers(runs, previous);
+    if !changed_answers.is_empty() {
+        writeln!(report)?;
+        writeln!(report, "## Answers changed since the previous run")?;
+        writeln!(report)?;
+        for line in changed_answers {
+            writeln!(report, "- {}", line)?;
+        }
+    }
+
+    if !run.failures.is_empty() {
+        writeln!(report)?;
+        writeln!(report, "## Failures")?;
+        writeln!(report)?;
+        for (day, err) in &run.failures {
+            writeln!(report, "- Day {:02}: {}", day, err)?;
+        }
+    }
+
+    Ok(report)
+}
+
+fn changed_answers(runs: &[(u8, DayRun)], previous: Option<&Table>) -> Vec<String> {
+    let Some(previous) = previous else {
+        return vec![];
+    };
+
+    let mut changed = vec![];
+    for (day, run) in runs {
+        for (part, answer) in [(1, &run.part1), (2, &run.part2)] {
+            let before = previous
+                .get(&format!("day{:02}", day))
+                .and_then(|answers| answers.get(format!("part{}", part)))
+                .and_then(Value::as_str);
+            if let (Some(answer), Some(before)) = (answer, before) {
+                if answer.to_string() != before {
+                    changed.push(format!(
+                        "Day {:02} part {}: {} (was {})",
+                        day, part, answer, before
+                    ));
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+/// The model name of the first CPU, where `/proc/cpuinfo` tells it.
+fn cpu_model() -> Option<String> {
+    let cpu_info = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpu_info
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_owned())
+}
+
+/// `time` as `YYYY-MM-DD hh:mm:ss UTC`, the date being found from the days since the epoch with Howard
+/// Hinnant's `civil_from_days` (the years starting on the 1st of March, so that leap days end them).
+fn utc_date_time(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (days, seconds) = ((seconds / 86_400) as i64, seconds % 86_400);
+
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
@@ -0,0 +1,78 @@
+//! The summary table printed after running several days, its rows also making the board of `aoc tui`.
+use crate::DayRun;
+use aoc_utils::solution::Answer;
+use std::time::Duration;
+
+pub(crate) const HEADERS: [&str; 7] = [
+    "Day",
+    "Part 1",
+    "Part 2",
+    "Parse",
+    "Part 1 time",
+    "Part 2 time",
+    "Total",
+];
+
+/// The answers are left-aligned, the day and timings right-aligned.
+pub(crate) const LEFT_ALIGNED: [bool; 7] = [false, true, true, false, false, false, false];
+
+/// Prints a row per solved day, every column as wide as its widest cell.
+pub(crate) fn print(runs: &[(u8, DayRun)]) {
+    let header = HEADERS.map(String::from);
+    let rows: Vec<[String; 7]> = runs.iter().map(|(day, run)| row(*day, run)).collect();
+
+    let mut widths = [0; 7];
+    for row in [&header].into_iter().chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    print_row(&header, &widths);
+    println!("{}", widths.map(|width| "-".repeat(width)).join("-+-"));
+    for row in &rows {
+        print_row(row, &widths);
+    }
+}
+
+pub(crate) fn row(day: u8, run: &DayRun) -> [String; 7] {
+    [
+        format!("{:02}", day),
+        answer_cell(&run.part1),
+        answer_cell(&run.part2),
+        format!("{:.2?}", run.parse_time),
+        time_cell(&run.part1, run.part1_time),
+        time_cell(&run.part2, run.part2_time),
+        format!("{:.2?}", run.total_time()),
+    ]
+}
+
+/// Skipped parts (see `--part`) are left blank.
+#[inline]
+fn answer_cell(answer: &Option<Answer>) -> String {
+    answer.as_ref().map(Answer::to_string).unwrap_or_default()
+}
+
+#[inline]
+fn time_cell(answer: &Option<Answer>, time: Duration) -> String {
+    answer
+        .as_ref()
+        .map(|_| format!("{:.2?}", time))
+        .unwrap_or_default()
+}
+
+fn print_row(cells: &[String; 7], widths: &[usize; 7]) {
+    let cells: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .zip(LEFT_ALIGNED)
+        .map(|((cell, &width), left)| {
+            if left {
+                format!("{:<width$}", cell)
+            } else {
+                format!("{:>width$}", cell)
+            }
+        })
+        .collect();
+    println!("{}", cells.join(" | "));
+}
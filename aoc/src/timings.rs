@@ -0,0 +1,67 @@
+//! `--timings-csv PATH`: the history of the timings, one row per part solved appended to a CSV file after
+//! every run so that it can be charted across optimizations. Every row of a run shares its timestamp (in seconds
+//! since the Unix epoch); the profile names, answers and build names never hold a comma, no field is quoted.
+use crate::{DayRun, Selection};
+use std::{
+    error::Error,
+    fmt::Write as _,
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const HEADER: &str = "timestamp,day,part,profile,build,answer,parse_nanos,part_nanos";
+
+/// Appends a row for each part solved by `runs`, starting the file with the header if it is new or empty.
+pub(crate) fn append(
+    path: &Path,
+    selection: &Selection,
+    runs: &[(u8, DayRun)],
+) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| format!("Could not open {}: {}", path.display(), err))?;
+
+    let mut rows = String::new();
+    if file.metadata()?.len() == 0 {
+        writeln!(rows, "{}", HEADER)?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let build = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    for (day, run) in runs {
+        for (part, answer, time) in [
+            (1, &run.part1, run.part1_time),
+            (2, &run.part2, run.part2_time),
+        ] {
+            let Some(answer) = answer else {
+                continue;
+            };
+
+            writeln!(
+                rows,
+                "{},{},{},{},{},{},{},{}",
+                timestamp,
+                day,
+                part,
+                selection.profile().unwrap_or_default(),
+                build,
+                answer,
+                run.parse_time.as_nanos(),
+                time.as_nanos()
+            )?;
+        }
+    }
+
+    file.write_all(rows.as_bytes())
+        .map_err(|err| format!("Could not write {}: {}", path.display(), err).into())
+}
@@ -0,0 +1,203 @@
+//! `aoc tui`: a board of the 25 days showing their answers and timings, any of them being solved (again) with a
+//! keypress.
+use crate::{run_day, table, DayRun, Selection, DAYS};
+use aoc_utils::config;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Cell, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use std::{error::Error, panic, time::Duration};
+
+const HELP: &str =
+    "Up/Down: select, r/Enter: solve the selected day, a: solve every day, q/Esc: quit";
+
+/// What the board knows of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+enum DayState {
+    #[default]
+    NotRun,
+    Running,
+    Solved(DayRun),
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct Board<'s> {
+    selection: &'s Selection,
+    /// day `N` at index `N - 1`
+    days: [DayState; 25],
+    /// index of the selected day
+    selected: usize,
+    year: u16,
+}
+
+/// Shows the board until the user quits, the days of `selection` being solved first.
+pub(crate) fn run(selection: &Selection) -> Result<(), Box<dyn Error>> {
+    let board = Board::new(selection, config::year()?);
+    let mut terminal = ratatui::init();
+    let result = board.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl<'s> Board<'s> {
+    fn new(selection: &'s Selection, year: u16) -> Self {
+        Self {
+            selection,
+            days: Default::default(),
+            selected: 0,
+            year,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        for &day in &self.selection.days {
+            self.solve(terminal, day)?;
+        }
+
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(self.days.len() - 1)
+                }
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    self.solve(terminal, self.selected as u8 + 1)?
+                }
+                KeyCode::Char('a') => {
+                    for day in DAYS {
+                        self.solve(terminal, day)?;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Solves `day`, showing it as running until it is done.
+    fn solve(&mut self, terminal: &mut DefaultTerminal, day: u8) -> Result<(), Box<dyn Error>> {
+        let index = day as usize - 1;
+        self.days[index] = DayState::Running;
+        terminal.draw(|frame| self.draw(frame))?;
+
+        // the panics of the days are shown as failures: neither printed over the board nor restoring the
+        // terminal, as the panic hook of ratatui does
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| ()));
+        let result = run_day(day, self.selection);
+        panic::set_hook(hook);
+
+        self.days[index] = match result {
+            Ok(run) => DayState::Solved(run),
+            Err(err) => DayState::Failed(err.to_string()),
+        };
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [title, board, status, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(Line::from(self.title()).bold(), title);
+
+        let header = Row::new(
+            ["Day", "Status"]
+                .into_iter()
+                .chain(table::HEADERS[1..].iter().copied()),
+        )
+        .bold();
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(11),
+        ];
+        let table = Table::new(DAYS.map(|day| self.row(day)), widths)
+            .header(header)
+            .row_highlight_style(Style::new().reversed());
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, board, &mut state);
+
+        frame.render_widget(Line::from(self.status()), status);
+        frame.render_widget(Line::from(HELP).dim(), help);
+    }
+
+    fn title(&self) -> String {
+        let runs: Vec<&DayRun> = self
+            .days
+            .iter()
+            .filter_map(|state| match state {
+                DayState::Solved(run) => Some(run),
+                _ => None,
+            })
+            .collect();
+
+        let mut title = format!(
+            "Advent of Code {}: {} of 25 days solved in {:.2?} (parts: {:?})",
+            self.year,
+            runs.len(),
+            runs.iter().map(|run| run.total_time()).sum::<Duration>(),
+            self.selection.part
+        );
+        if let Some(profile) = self.selection.profile() {
+            title.push_str(&format!(", profile {}", profile));
+        }
+
+        title
+    }
+
+    fn row(&self, day: u8) -> Row<'static> {
+        let (status, mut cells) = match &self.days[day as usize - 1] {
+            DayState::NotRun => (Span::raw(""), <[String; 7]>::default()),
+            DayState::Running => ("running".yellow(), Default::default()),
+            DayState::Solved(run) => ("solved".green(), table::row(day, run)),
+            DayState::Failed(_) => ("failed".red(), Default::default()),
+        };
+        cells[0] = format!("{:02}", day);
+
+        let mut cells: Vec<Cell> = cells
+            .into_iter()
+            .zip(table::LEFT_ALIGNED)
+            .map(|(cell, left)| {
+                let text = Text::from(cell);
+                Cell::from(if left { text } else { text.right_aligned() })
+            })
+            .collect();
+        cells.insert(1, Cell::from(status));
+
+        Row::new(cells)
+    }
+
+    /// What happened to the selected day.
+    fn status(&self) -> String {
+        let day = self.selected + 1;
+        match &self.days[self.selected] {
+            DayState::NotRun => format!("Day {:02} has not been solved yet", day),
+            DayState::Running => format!("Solving day {:02}...", day),
+            DayState::Solved(run) => format!("Day {:02} solved in {:.2?}", day, run.total_time()),
+            DayState::Failed(err) => format!("Day {:02} failed: {}", day, err),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Checking the answers of the days against those known to be right, stored in `answers.toml` at the root of
+//! the calendar, one table per day:
+//!
+//! ```toml
+//! [day05]
+//! part1 = 35
+//! part2 = 46
+//! ```
+//!
+//! The answers too big for a TOML integer are written as strings, and the missing part 2 of day 25 as `"-"`.
+//! The answers to the inputs of a profile are stored in `answers.<profile>.toml` instead.
+//!
+//! `--example` checks the examples bundled with the days against the answers the puzzles give for them instead.
+use crate::{run, DayRun};
+use aoc_utils::{
+    input,
+    registry::RegisteredDay,
+    solution::{Answer, Part},
+};
+use std::{error::Error, fs, path::PathBuf};
+use toml::{Table, Value};
+
+/// Where the answers known to be right for the inputs of `profile` are stored.
+pub(crate) fn answers_path(profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => input::calendar_dir().join(format!("answers.{}.toml", profile)),
+        None => input::calendar_dir().join("answers.toml"),
+    }
+}
+
+/// The answers known to be right, by day.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Answers {
+    table: Table,
+}
+
+impl Answers {
+    pub(crate) fn load(profile: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let path = answers_path(profile);
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        let table = text
+            .parse()
+            .map_err(|err| format!("{} is not valid TOML: {}", path.display(), err))?;
+        Ok(Self { table })
+    }
+
+    /// The stored answer to `part` (1 or 2) of `day`, if any.
+    fn expected(&self, day: u8, part: u8) -> Result<Option<Answer>, Box<dyn Error>> {
+        let Some(value) = self
+            .table
+            .get(&format!("day{:02}", day))
+            .and_then(|answers| answers.get(format!("part{}", part)))
+        else {
+            return Ok(None);
+        };
+
+        match value {
+            Value::Integer(value) => Ok(Some(Answer::Number(*value as i128))),
+            Value::String(value) if value == "-" => Ok(Some(Answer::NoPuzzle)),
+            Value::String(value) => Ok(Some(Answer::Number(value.parse().map_err(|err| {
+                format!(
+                    "The answer to day {} part {} is invalid: {:?} ({})",
+                    day, part, value, err
+                )
+            })?))),
+            other => Err(format!(
+                "The answer to day {} part {} should be a number, was {}",
+                day, part, other
+            )
+            .into()),
+        }
+    }
+
+    /// Prints how the parts solved by `run` compare to the stored answers, returning whether none of them
+    /// differs (the parts without a stored answer are only reported).
+    pub(crate) fn check(&self, day: u8, run: &DayRun) -> Result<bool, Box<dyn Error>> {
+        let mut matches = true;
+        for (part, answer) in [(1, &run.part1), (2, &run.part2)] {
+            let Some(answer) = answer else {
+                continue;
+            };
+
+            match self.expected(day, part)? {
+                Some(expected) if expected == *answer => {
+                    println!("Day {:02} part {}: ok ({})", day, part, answer)
+                }
+                Some(expected) => {
+                    println!(
+                        "Day {:02} part {}: REGRESSION, got {} but expected {}",
+                        day, part, answer, expected
+                    );
+                    matches = false;
+                }
+                None => println!(
+                    "Day {:02} part {}: no stored answer (got {})",
+                    day, part, answer
+                ),
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+/// How the examples of a day went, from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ExampleCheck {
+    Matches,
+    Wrong,
+    /// an example could not be solved, returning an error or panicking
+    Failed,
+}
+
+/// Solves the examples of the `registered` day, printing how their answers compare to those of the puzzle and
+/// returning the worst of them. Only the parts of `part` an example has an answer for are solved.
+pub(crate) fn check_examples(registered: &RegisteredDay, part: Part) -> ExampleCheck {
+    let day = registered.day;
+    if registered.examples.is_empty() {
+        println!("Day {:02}: no example", day);
+        return ExampleCheck::Matches;
+    }
+
+    let mut check = ExampleCheck::Matches;
+    let mut solved = 0;
+    for (index, example) in registered.examples.iter().enumerate() {
+        let example_part = match (
+            part.solves_part1() && example.part1.is_some(),
+            part.solves_part2() && example.part2.is_some(),
+        ) {
+            (true, true) => Part::Both,
+            (true, false) => Part::One,
+            (false, true) => Part::Two,
+            (false, false) => continue,
+        };
+
+        solved += 1;
+        let run = match run(registered.parse_example, example.input, example_part) {
+            Ok(run) => run,
+            Err(err) => {
+                println!("Day {:02} example {}: FAILED, {}", day, index + 1, err);
+                check = ExampleCheck::Failed;
+                continue;
+            }
+        };
+
+        for (part, answer, expected) in [
+            (1, &run.part1, &example.part1),
+            (2, &run.part2, &example.part2),
+        ] {
+            let (Some(answer), Some(expected)) = (answer, expected) else {
+                continue;
+            };
+
+            if answer == expected {
+                println!(
+                    "Day {:02} example {} part {}: ok ({})",
+                    day,
+                    index + 1,
+                    part,
+                    answer
+                );
+            } else {
+                println!(
+                    "Day {:02} example {} part {}: WRONG, got {} but expected {}",
+                    day,
+                    index + 1,
+                    part,
+                    answer,
+                    expected
+                );
+                check = check.max(ExampleCheck::Wrong);
+            }
+        }
+    }
+
+    if solved == 0 {
+        println!("Day {:02}: no example of part {:?}", day, part);
+    }
+
+    check
+}
@@ -1,26 +1,17 @@
-use std::{error::Error, fs};
-
-const INPUT_FILE: &str = "input";
+use aoc_utils::{
+    input::{self, InputSource},
+    solution::{Answer, Solution},
+};
+use day01::Day01;
+use std::error::Error;
 
 fn main() {
-    match solve() {
-        Ok(answer) => println!("Answer: {answer}"),
-        Err(err) => eprintln!("Error occurred: {err}"),
+    match solve(&input::parse_input_arg::<1>()) {
+        Ok(answer) => println!("Answer: {}", answer),
+        Err(err) => eprintln!("Error occurred: {}\nDebug: {:#?}", err, err),
     }
 }
 
-fn solve() -> Result<u32, Box<dyn Error>> {
-    Ok(fs::read_to_string(INPUT_FILE)?
-        .lines()
-        .inspect(|line| eprint!("{:?} => ", line))
-        .map(|line| get_number_from_line(line))
-        .inspect(|res| eprintln!("{:?}", res))
-        .sum())
-}
-
-fn get_number_from_line(line: &str) -> u32 {
-    let chars = line.chars();
-    let val_1 = chars.clone().find_map(|c| c.to_digit(10)).unwrap_or(0) * 10;
-    let val_2 = chars.rev().find_map(|c| c.to_digit(10)).unwrap_or(0);
-    val_1 + val_2
+fn solve(input: &InputSource) -> Result<Answer, Box<dyn Error>> {
+    Day01::parse(&input.read()?)?.part1()
 }
@@ -0,0 +1,40 @@
+//! Day 1: Trebuchet?!
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The calibration document, [`part1`] reading the digits of each line and [`part2`] the spelled out ones too.
+#[derive(Debug, Clone)]
+pub struct Day01 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    1,
+    Day01,
+    examples = [
+        Example::part1_only(include_str!("../example1"), 142),
+        Example::part2_only(include_str!("../example2"), 281),
+    ]
+);
+
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_utils::solution::main::<day01::Day01, 1>();
+}
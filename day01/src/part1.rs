@@ -0,0 +1,20 @@
+use std::error::Error;
+use tracing::trace;
+
+pub fn solve(input: &str) -> Result<u32, Box<dyn Error>> {
+    Ok(input
+        .lines()
+        .map(|line| {
+            let value = get_number_from_line(line);
+            trace!(line, value, "calibration value");
+            value
+        })
+        .sum())
+}
+
+fn get_number_from_line(line: &str) -> u32 {
+    let chars = line.chars();
+    let val_1 = chars.clone().find_map(|c| c.to_digit(10)).unwrap_or(0) * 10;
+    let val_2 = chars.rev().find_map(|c| c.to_digit(10)).unwrap_or(0);
+    val_1 + val_2
+}
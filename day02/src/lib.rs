@@ -0,0 +1,37 @@
+//! Day 2: Cube Conundrum
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The record of the games, [`part1`] checking each one against the bag and [`part2`] finding its fewest cubes.
+#[derive(Debug, Clone)]
+pub struct Day02 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    2,
+    Day02,
+    examples = [Example::both(include_str!("../example"), 8, 2286)]
+);
+
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
@@ -0,0 +1,37 @@
+//! Day 3: Gear Ratios
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The engine schematic, searched for part numbers by [`part1`] and for gears by [`part2`].
+#[derive(Debug, Clone)]
+pub struct Day03 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    3,
+    Day03,
+    examples = [Example::both(include_str!("../example"), 4361, 467835)]
+);
+
+impl Solution for Day03 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
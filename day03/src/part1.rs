@@ -0,0 +1,151 @@
+use aoc_utils::grid::neighbours8;
+use itertools::Itertools;
+use std::{error::Error, fmt, iter::Sum, ops::Deref};
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EngineCell {
+    Digit(u8),
+    Symbol(char),
+    Nothing,
+}
+
+impl From<char> for EngineCell {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Self::Nothing,
+            c @ '0'..='9' => Self::Digit(c.to_digit(10).unwrap() as u8),
+            symbol => Self::Symbol(symbol),
+        }
+    }
+}
+
+impl fmt::Display for EngineCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nothing => write!(f, "."),
+            Self::Digit(d) => write!(f, "{}", d),
+            Self::Symbol(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PartNumber {
+    number: u64,
+    line_no: usize,
+    column_no: usize,
+    length: usize,
+}
+
+impl PartNumber {
+    fn from_vec(vec: &[Vec<EngineCell>]) -> Vec<Self> {
+        let mut result = vec![];
+        for (line_no, inner) in vec.iter().enumerate() {
+            let mut current_number = 0;
+            let mut starting_column = 0;
+            let mut was_last_digit = false;
+            let mut last_col = 0;
+            for (column_no, &cell) in inner.iter().enumerate() {
+                last_col = column_no;
+                match cell {
+                    EngineCell::Digit(digit) => {
+                        if was_last_digit {
+                            current_number *= 10;
+                            current_number += digit as u64;
+                        } else {
+                            was_last_digit = true;
+                            starting_column = column_no;
+                            current_number = digit as u64;
+                        }
+                    }
+
+                    _ => {
+                        if was_last_digit {
+                            was_last_digit = false;
+                            result.push(PartNumber {
+                                number: current_number,
+                                line_no,
+                                column_no: starting_column,
+                                length: column_no - starting_column,
+                            });
+                        }
+                    }
+                }
+            }
+
+            if was_last_digit {
+                result.push(PartNumber {
+                    number: current_number,
+                    line_no,
+                    column_no: starting_column,
+                    length: last_col - starting_column + 1,
+                });
+            }
+        }
+
+        result
+    }
+
+    fn is_adjacent_to_symbol(&self, vec: &[Vec<EngineCell>]) -> bool {
+        let bounds = (vec.len(), vec[0].len());
+        let symbol = (self.column_no..self.column_no + self.length)
+            .flat_map(|col| neighbours8((self.line_no, col), bounds))
+            .find_map(|(row, col)| match vec[row][col] {
+                EngineCell::Symbol(symbol) => Some(symbol),
+                _ => None,
+            });
+        if let Some(symbol) = symbol {
+            trace!(part = ?self, ?symbol, "counting a part next to a symbol");
+            return true;
+        }
+
+        trace!(part = ?self, "not counting a part");
+        false
+    }
+}
+
+impl Sum<PartNumber> for u64 {
+    fn sum<I: Iterator<Item = PartNumber>>(iter: I) -> Self {
+        iter.map(|part| part.number).sum()
+    }
+}
+
+pub fn solve(input: &str) -> Result<u64, Box<dyn Error>> {
+    let engine = input
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() {
+                None
+            } else {
+                Some(line.chars().map_into::<EngineCell>().collect_vec())
+            }
+        })
+        .collect_vec();
+
+    debug!("engine:\n{}", render_engine(&engine));
+    let parts = PartNumber::from_vec(&engine);
+    trace!(?parts, "part numbers");
+
+    Ok(parts
+        .into_iter()
+        .filter(|part| part.is_adjacent_to_symbol(&engine))
+        .sum())
+}
+
+/// The engine schematic drawn one row per line
+fn render_engine<I, I2, C>(iter: I) -> String
+where
+    I: IntoIterator<Item = I2>,
+    I2: IntoIterator<Item = C>,
+    C: Deref<Target = EngineCell>,
+{
+    iter.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| cell.to_string())
+                .collect::<String>()
+        })
+        .join("\n")
+}
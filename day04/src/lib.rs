@@ -0,0 +1,37 @@
+//! Day 4: Scratchcards
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The scratchcards, scored by [`part1`] and won copies of by [`part2`].
+#[derive(Debug, Clone)]
+pub struct Day04 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    4,
+    Day04,
+    examples = [Example::both(include_str!("../example"), 13, 30)]
+);
+
+impl Solution for Day04 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
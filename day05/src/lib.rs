@@ -0,0 +1,341 @@
+use aoc_utils::{
+    error::{parse_field, AocError},
+    interval::Interval,
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use itertools::Itertools;
+use std::{error::Error, ops, str::FromStr, vec};
+use tracing::trace;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MapEntry {
+    destination_start: u64,
+    source_start: u64,
+    range_length: u64,
+}
+
+impl MapEntry {
+    #[inline]
+    pub(crate) const fn destination_start(&self) -> u64 {
+        self.destination_start
+    }
+
+    #[inline]
+    pub(crate) const fn source_start(&self) -> u64 {
+        self.source_start
+    }
+
+    #[inline]
+    pub(crate) const fn range_length(&self) -> u64 {
+        self.range_length
+    }
+
+    #[inline]
+    pub(crate) const fn source_one_after_last(&self) -> u64 {
+        self.source_start() + self.range_length()
+    }
+
+    #[inline]
+    pub(crate) const fn source_range(&self) -> ops::Range<u64> {
+        self.source_start()..self.source_one_after_last()
+    }
+
+    #[inline]
+    pub(crate) const fn sort_key(&self) -> u64 {
+        self.source_start()
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, value: u64) -> bool {
+        self.source_range().contains(&value)
+    }
+
+    #[inline]
+    pub(crate) fn map(&self, value: u64) -> Option<u64> {
+        if self.contains(value) {
+            Some(self.map_impl(value))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn map_impl(&self, value: u64) -> u64 {
+        self.destination_start() + value - self.source_start()
+    }
+
+    /// The 3 ranges returned corresponds to the following:
+    /// - 1st range are values contained before the map entry
+    /// - 2nd range are values this map entry supports
+    /// - 3rd range are values beyond this map entry
+    ///
+    /// Empty ranges means that none of the values meets the condition above for that range
+    #[inline]
+    pub(crate) fn map_range(
+        &self,
+        range: ops::Range<u64>,
+    ) -> (ops::Range<u64>, ops::Range<u64>, ops::Range<u64>) {
+        let (before, matching, after) =
+            Interval::from(range.clone()).split_around(&self.source_range().into());
+
+        let matching = if matching.is_empty() {
+            0..0
+        } else {
+            self.map_impl(matching.start)..self.map_impl(matching.end)
+        };
+
+        let res = (before.into(), matching, after.into());
+        trace!(entry = ?self, ?range, split = ?res, "range split");
+        res
+    }
+}
+
+impl FromStr for MapEntry {
+    type Err = AocError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((first_num, rest)) = s.split_once(' ') else {
+            return Err(AocError::parse(
+                s,
+                format!("Could not split {:?} into 3 number fields", s),
+            ));
+        };
+
+        let Some((second_num, third_num)) = rest.split_once(' ') else {
+            return Err(AocError::parse(
+                s,
+                format!("Could not split {:?} into 3 number fields", s),
+            ));
+        };
+
+        Ok(Self {
+            destination_start: parse_field(first_num)?,
+            source_start: parse_field(second_num)?,
+            range_length: parse_field(third_num)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Map {
+    entries: Box<[MapEntry]>,
+}
+
+impl Map {
+    #[inline]
+    pub(crate) fn new(mut entries: Box<[MapEntry]>) -> Self {
+        entries.sort_unstable_by_key(MapEntry::sort_key);
+        Self { entries }
+    }
+
+    #[inline]
+    pub(crate) fn map(&self, value: u64) -> u64 {
+        let res = self
+            .entries
+            .iter()
+            .find_map(|map| map.map(value))
+            .unwrap_or(value);
+        trace!(value, mapped = res);
+        res
+    }
+
+    #[inline]
+    pub(crate) fn map_range(&self, mut range: ops::Range<u64>) -> Vec<ops::Range<u64>> {
+        let mut res = vec![];
+        for map in self.entries.iter() {
+            if range.is_empty() {
+                break;
+            }
+
+            let (before, mapped, after) = map.map_range(range);
+
+            // before doesn't have any mapping
+            if !before.is_empty() {
+                res.push(before);
+            }
+
+            // mapped just got mapped
+            if !mapped.is_empty() {
+                res.push(mapped);
+            }
+
+            // after are values beyond this mapping, so check the next one (they are in sorted order)
+            range = after;
+        }
+
+        if !range.is_empty() {
+            res.push(range);
+        }
+
+        res
+    }
+
+    #[inline]
+    pub(crate) fn map_ranges(&self, ranges: Vec<ops::Range<u64>>) -> Vec<ops::Range<u64>> {
+        ranges
+            .into_iter()
+            .flat_map(|range| self.map_range(range))
+            .collect_vec()
+        // let mut result = vec![];
+        // for range in ranges {
+        //     result.extend(self.map_range(range));
+        // }
+
+        // result
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AllMaps {
+    maps: [Map; 7],
+}
+
+impl AllMaps {
+    #[inline]
+    pub(crate) fn map(&self, mut value: u64) -> u64 {
+        for map in self.maps.iter() {
+            value = map.map(value);
+        }
+
+        value
+    }
+
+    #[inline]
+    pub(crate) fn map_range(&self, range: SeedRange) -> Vec<ops::Range<u64>> {
+        let mut result = vec![range.seed_range()];
+        for map in self.maps.iter() {
+            result = map.map_ranges(result);
+        }
+
+        result
+    }
+}
+
+impl AllMaps {
+    /// Parses the 7 maps, each one a header line followed by its entries, from the lines of the input
+    /// (numbered from 0) following the seeds.
+    pub(crate) fn parse<'s>(
+        mut lines: impl Iterator<Item = (usize, &'s str)>,
+    ) -> Result<Self, AocError> {
+        let mut maps = vec![];
+        for _ in 0..7 {
+            let entries: Box<[MapEntry]> = lines
+                .by_ref()
+                .skip_while(|(_, line)| line.trim().is_empty())
+                .skip(1)
+                .take_while(|(_, line)| !line.trim().is_empty())
+                .map(|(index, line)| {
+                    line.trim()
+                        .parse()
+                        .map_err(|err: AocError| err.in_line(index, line))
+                })
+                .try_collect()?;
+            maps.push(Map::new(entries));
+        }
+
+        Ok(Self {
+            maps: maps.try_into().expect("Vec did not have 7 elements"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SeedRange {
+    seed_start: u64,
+    seed_length: u64,
+}
+
+impl SeedRange {
+    #[inline]
+    pub(crate) const fn seed_start(&self) -> u64 {
+        self.seed_start
+    }
+
+    #[inline]
+    pub(crate) const fn seed_length(&self) -> u64 {
+        self.seed_length
+    }
+
+    #[inline]
+    pub(crate) const fn seed_range(&self) -> ops::Range<u64> {
+        self.seed_start()..(self.seed_start() + self.seed_length())
+    }
+
+    #[inline]
+    pub(crate) const fn new(seed_start: u64, seed_length: u64) -> Self {
+        Self {
+            seed_start,
+            seed_length,
+        }
+    }
+}
+
+/// The seeds to plant and the maps from seeds to locations.
+#[derive(Debug, Clone)]
+pub struct Day05 {
+    seeds: Box<[u64]>,
+    maps: AllMaps,
+}
+
+impl FromStr for Day05 {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines().enumerate();
+        let (index, first_line) = lines
+            .next()
+            .ok_or_else(|| AocError::parse(input, "Empty input"))?;
+        let seeds: Box<[u64]> = first_line
+            .strip_prefix("seeds:")
+            .ok_or_else(|| AocError::parse(first_line, r#"First line did not start with "seeds:""#))
+            .and_then(|seeds| seeds.split_whitespace().map(parse_field).try_collect())
+            .map_err(|err| err.in_line(index, first_line))?;
+
+        let maps = AllMaps::parse(lines)?;
+
+        Ok(Self { seeds, maps })
+    }
+}
+
+aoc_utils::register_day!(
+    5,
+    Day05,
+    examples = [Example::both(include_str!("../example"), 35, 46)]
+);
+
+impl Solution for Day05 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self
+            .seeds
+            .iter()
+            .map(|&seed| self.maps.map(seed))
+            .min()
+            .ok_or("No seeds")?
+            .into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_2(&self.seeds, &self.maps).into())
+    }
+}
+
+#[inline]
+fn part_2(seeds: &[u64], maps: &AllMaps) -> u64 {
+    let seeds = seeds
+        .chunks_exact(2)
+        .map(|data| SeedRange::new(data[0], data[1]))
+        .collect_vec();
+
+    seeds
+        .into_iter()
+        .flat_map(|range| maps.map_range(range))
+        .map(|range| range.start) // range start is smallest value obviously
+        .min()
+        .expect("No seeds")
+}
@@ -0,0 +1,85 @@
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::{error::Error, num::ParseIntError, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RecordData {
+    time: u64,
+    distance: u64,
+}
+
+impl RecordData {
+    fn new(time: u64, distance: u64) -> Self {
+        Self { time, distance }
+    }
+
+    fn count_ways_to_beat(&self) -> u64 {
+        (1..self.time)
+            .filter(|&time_held| (self.time - time_held) * time_held > self.distance)
+            .count() as u64
+    }
+}
+
+/// One race per column of the sheet of paper.
+#[derive(Debug, Clone)]
+pub struct Day06 {
+    records: Vec<RecordData>,
+}
+
+aoc_utils::register_day!(
+    6,
+    Day06,
+    examples = [Example::both(include_str!("../example"), 288, 71503)]
+);
+
+impl Solution for Day06 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = input.lines();
+        let times = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Time:"))
+            .ok_or(r#"The first line did not start with "Time:""#)?
+            .split_whitespace()
+            .map(u64::from_str);
+        let distances = lines
+            .next()
+            .and_then(|line| line.strip_prefix("Distance:"))
+            .ok_or(r#"The second line did not start with "Distance:""#)?
+            .split_whitespace()
+            .map(u64::from_str);
+        let records = times
+            .zip(distances)
+            .map(|(time, distance)| Ok::<_, ParseIntError>(RecordData::new(time?, distance?)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { records })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self
+            .records
+            .iter()
+            .map(|record| record.count_ways_to_beat())
+            .product::<u64>()
+            .into())
+    }
+
+    /// There is a single race after all, the spaces between the digits being bad kerning.
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        let (time, distance) = self.records.iter().fold(
+            (String::new(), String::new()),
+            |(time, distance), record| {
+                (
+                    time + &record.time.to_string(),
+                    distance + &record.distance.to_string(),
+                )
+            },
+        );
+
+        Ok(RecordData::new(time.parse()?, distance.parse()?)
+            .count_ways_to_beat()
+            .into())
+    }
+}
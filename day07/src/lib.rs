@@ -0,0 +1,37 @@
+//! Day 7: Camel Cards
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The hands of Camel Cards and their bids, [`part2`] reading the `J` cards as jokers.
+#[derive(Debug, Clone)]
+pub struct Day07 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    7,
+    Day07,
+    examples = [Example::both(include_str!("../example"), 6440, 5905)]
+);
+
+impl Solution for Day07 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
@@ -0,0 +1,18 @@
+use aoc_utils::{
+    dot::ToDot,
+    input::{self, InputSource},
+};
+use day08::network::Network;
+use std::{error::Error, io};
+
+fn main() {
+    if let Err(err) = write_network(&input::parse_input_arg::<8>()) {
+        eprintln!("Error occurred: {}\nDebug: {:#?}", err, err);
+    }
+}
+
+/// Writes the map of the input to stdout in the DOT format.
+fn write_network(input: &InputSource) -> Result<(), Box<dyn Error>> {
+    Network::parse(&input.read()?)?.write_dot(&mut io::stdout().lock())?;
+    Ok(())
+}
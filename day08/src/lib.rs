@@ -0,0 +1,42 @@
+//! Day 8: Haunted Wasteland
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod network;
+pub mod part1;
+pub mod part2;
+
+/// The left/right instructions and the network of nodes they are followed through.
+#[derive(Debug, Clone)]
+pub struct Day08 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    8,
+    Day08,
+    examples = [
+        Example::part1_only(include_str!("../example1"), 2),
+        Example::part1_only(include_str!("../example2"), 6),
+        Example::part2_only(include_str!("../example3"), 6),
+    ]
+);
+
+impl Solution for Day08 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
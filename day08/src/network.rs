@@ -0,0 +1,68 @@
+use aoc_utils::dot::{Attributes, ToDot};
+use std::error::Error;
+
+/// The nodes of the map with the nodes their left and right instructions lead to, in the order of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network<'s> {
+    nodes: Vec<(&'s str, [&'s str; 2])>,
+}
+
+impl<'s> Network<'s> {
+    /// Parses the nodes of the map, skipping the instructions on its first line.
+    pub fn parse(input: &'s str) -> Result<Self, Box<dyn Error>> {
+        let nodes = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .skip(1)
+            .map(|line| {
+                let (node, destinations) =
+                    line.split_once('=').ok_or("Line did not have char '='")?;
+                let (left, right) = destinations
+                    .trim()
+                    .trim_start_matches('(')
+                    .trim_end_matches(')')
+                    .split_once(',')
+                    .ok_or("Not a comma separated list of values")?;
+                Ok::<_, &'static str>((node.trim(), [left.trim(), right.trim()]))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { nodes })
+    }
+}
+
+/// Every node pointing at where its left and right instructions lead, the starting nodes of both parts drawn
+/// as double circles and their ending nodes as boxes.
+impl<'s> ToDot for Network<'s> {
+    type Node = &'s str;
+    /// the instruction following the edge, `'L'` or `'R'`
+    type Edge = char;
+
+    fn nodes(&self) -> impl Iterator<Item = &'s str> {
+        self.nodes.iter().map(|&(node, _)| node)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&'s str, &'s str, char)> {
+        self.nodes
+            .iter()
+            .flat_map(|&(node, [left, right])| [(node, left, 'L'), (node, right, 'R')])
+    }
+
+    fn node_id(&self, node: &&'s str) -> String {
+        node.to_string()
+    }
+
+    fn node_attributes(&self, node: &&'s str) -> Attributes {
+        if node.ends_with('A') {
+            vec![("shape", "doublecircle".to_owned())]
+        } else if node.ends_with('Z') {
+            vec![("shape", "box".to_owned())]
+        } else {
+            vec![]
+        }
+    }
+
+    fn edge_label(&self, _from: &&'s str, _to: &&'s str, instruction: &char) -> Option<String> {
+        Some(instruction.to_string())
+    }
+}
@@ -1,56 +1,17 @@
-#![feature(iter_map_windows)]
-
-use std::{error::Error, fs, num::ParseIntError, str::FromStr};
-
-const INPUT: &str = "input";
+use aoc_utils::{
+    input::{self, InputSource},
+    solution::{Answer, Solution},
+};
+use day09::Day09;
+use std::error::Error;
 
 fn main() {
-    match solve() {
+    match solve(&input::parse_input_arg::<9>()) {
         Ok(answer) => println!("Answer: {}", answer),
-        Err(err) => eprintln!("Error occurred: {:#?}", err),
+        Err(err) => eprintln!("Error occurred: {}\nDebug: {:#?}", err, err),
     }
 }
 
-fn solve() -> Result<i64, Box<dyn Error>> {
-    let input = fs::read_to_string(INPUT)?;
-    Ok(input
-        .lines()
-        .filter_map(|line| {
-            if line.trim().is_empty() {
-                None
-            } else {
-                Some(
-                    line.split_whitespace()
-                        .map(i64::from_str)
-                        .collect::<Result<Vec<_>, _>>(),
-                )
-            }
-        })
-        .map::<Result<_, ParseIntError>, _>(|vec| Ok(get_next_number_of_sequence(vec?)))
-        .sum::<Result<i64, ParseIntError>>()?)
-}
-
-fn get_next_number_of_sequence(seq: Vec<i64>) -> i64 {
-    let mut vec_stack = vec![seq];
-    while vec_stack
-        .last()
-        .expect("Non-empty Vec doesn't have a last element")
-        .iter()
-        .any(|&val| val != 0)
-    {
-        vec_stack.push(
-            vec_stack
-                .last()
-                .expect("Non-empty Vec doesn't have a last element")
-                .iter()
-                .map_windows(|&[a, b]| b - a)
-                .collect(),
-        );
-    }
-
-    vec_stack
-        .into_iter()
-        .rev()
-        .map(|vec| vec[0])
-        .fold(0, |acc, val| val - acc)
+fn solve(input: &InputSource) -> Result<Answer, Box<dyn Error>> {
+    Day09::parse(&input.read()?)?.part2()
 }
@@ -0,0 +1,37 @@
+//! Day 9: Mirage Maintenance
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The history of every value of the report, extrapolated forwards by [`part1`] and backwards by [`part2`].
+#[derive(Debug, Clone)]
+pub struct Day09 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    9,
+    Day09,
+    examples = [Example::both(include_str!("../example"), 114, 2)]
+);
+
+impl Solution for Day09 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
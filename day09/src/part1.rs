@@ -0,0 +1,43 @@
+use std::{error::Error, num::ParseIntError, str::FromStr};
+
+pub fn solve(input: &str) -> Result<i64, Box<dyn Error>> {
+    Ok(input
+        .lines()
+        .filter_map(|line| {
+            if line.trim().is_empty() {
+                None
+            } else {
+                Some(
+                    line.split_whitespace()
+                        .map(i64::from_str)
+                        .collect::<Result<Vec<_>, _>>(),
+                )
+            }
+        })
+        .map::<Result<_, ParseIntError>, _>(|vec| Ok(get_next_number_of_sequence(vec?)))
+        .sum::<Result<i64, ParseIntError>>()?)
+}
+
+fn get_next_number_of_sequence(seq: Vec<i64>) -> i64 {
+    let mut vec_stack = vec![seq];
+    while vec_stack
+        .last()
+        .expect("Non-empty Vec doesn't have a last element")
+        .iter()
+        .any(|&val| val != 0)
+    {
+        vec_stack.push(
+            vec_stack
+                .last()
+                .expect("Non-empty Vec doesn't have a last element")
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .collect(),
+        );
+    }
+
+    vec_stack
+        .into_iter()
+        .map(|vec| *vec.last().unwrap_or(&0))
+        .sum()
+}
@@ -0,0 +1,43 @@
+//! Day 10: Pipe Maze
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The map of the pipes, [`part1`] following the loop from `S` and [`part2`] counting the tiles it encloses.
+#[derive(Debug, Clone)]
+pub struct Day10 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    10,
+    Day10,
+    examples = [
+        Example::part1_only(include_str!("../example1"), 4),
+        Example::part1_only(include_str!("../example2"), 8),
+        Example::part2_only(include_str!("../example3"), 4),
+        Example::part2_only(include_str!("../example4"), 8),
+        Example::part2_only(include_str!("../example5"), 10),
+    ]
+);
+
+impl Solution for Day10 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
@@ -0,0 +1,395 @@
+use aoc_utils::{geometry::Direction, grid::Grid};
+use std::{
+    error::Error,
+    fmt,
+    ops::{Index, IndexMut},
+};
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ConnectionVariant {
+    Vertical,
+    Horizontal,
+    CornerNE,
+    CornerNW,
+    CornerSW,
+    CornerSE,
+    StartingPoint,
+    Ground,
+}
+
+impl ConnectionVariant {
+    //! It is safe to do for ANY of those
+    //! > variant.connected_to.unwrap()
+    const CONNECTED_VARIANTS: [ConnectionVariant; 6] = [
+        Self::Vertical,
+        Self::Horizontal,
+        Self::CornerNE,
+        Self::CornerNW,
+        Self::CornerSE,
+        Self::CornerSW,
+    ];
+
+    fn connected_to(&self) -> Option<(Direction, Direction)> {
+        use Direction::*;
+        Some(match self {
+            Self::Vertical => (North, South),
+            Self::Horizontal => (West, East),
+            Self::CornerNE => (North, East),
+            Self::CornerNW => (North, West),
+            Self::CornerSW => (South, West),
+            Self::CornerSE => (South, East),
+            Self::Ground | Self::StartingPoint => None?,
+        })
+    }
+
+    fn connects_to_direction(&self, direction: Direction) -> bool {
+        use Direction::*;
+        match self {
+            Self::Ground | Self::StartingPoint => false,
+            Self::Vertical => direction == North || direction == South,
+            Self::Horizontal => direction == East || direction == West,
+            Self::CornerNE => direction == North || direction == East,
+            Self::CornerNW => direction == North || direction == West,
+            Self::CornerSW => direction == South || direction == West,
+            Self::CornerSE => direction == South || direction == East,
+        }
+    }
+}
+
+impl TryFrom<char> for ConnectionVariant {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use ConnectionVariant::*;
+        Ok(match value {
+            '|' => Vertical,
+            '-' => Horizontal,
+            'L' => CornerNE,
+            'J' => CornerNW,
+            '7' => CornerSW,
+            'F' => CornerSE,
+            '.' => Ground,
+            'S' => StartingPoint,
+            other => Err(format!("Unrecognized character for pipe grid: {other:?}"))?,
+        })
+    }
+}
+
+impl fmt::Display for ConnectionVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ConnectionVariant::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                Vertical => '║',
+                Horizontal => '═',
+                CornerNE => '╚',
+                CornerNW => '╝',
+                CornerSW => '╗',
+                CornerSE => '╔',
+                Ground => '.',
+                StartingPoint => 'S',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Connection {
+    grid_position: (usize, usize),
+    variant: ConnectionVariant,
+}
+
+impl From<(ConnectionVariant, usize, usize)> for Connection {
+    fn from(value: (ConnectionVariant, usize, usize)) -> Self {
+        let (variant, row_num, col_num) = value;
+        Self {
+            grid_position: (row_num, col_num),
+            variant,
+        }
+    }
+}
+
+impl Connection {
+    fn connected_to(&self) -> Option<(Direction, Direction)> {
+        self.variant.connected_to()
+    }
+
+    fn is_other_connected(&self, grid: &PipeMaze, direction: Direction) -> bool {
+        let (row, col) = self.grid_position;
+        if let Some((row, col)) = direction.translate_coordinates(row, col) {
+            if let Some((direct_1, direct_2)) = grid
+                .pipes
+                .get((row, col))
+                .and_then(|connection| connection.connected_to())
+            {
+                direct_1.opposite() == direction || direct_2.opposite() == direction
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn equivalent_connection(&self, grid: &PipeMaze) -> Result<ConnectionVariant, &'static str> {
+        match self.variant {
+            ConnectionVariant::StartingPoint => ConnectionVariant::CONNECTED_VARIANTS
+                .into_iter()
+                .find(|variant| {
+                    let (direct_1, direct_2) = variant
+                        .connected_to()
+                        .expect("(CONNECTED_VARIANT member).connected_to() returned None");
+
+                    self.is_other_connected(grid, direct_1)
+                        && self.is_other_connected(grid, direct_2)
+                })
+                .ok_or("Could not find a corresponding connection variant for StartingPoint"),
+            others => Ok(others),
+        }
+    }
+}
+
+impl fmt::Display for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.variant)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PipeMaze {
+    pipes: Grid<Connection>,
+    start_row: usize,
+    start_col: usize,
+    start_replaced_by_equivalent: bool,
+}
+
+impl PipeMaze {
+    fn check_grid_integrity(&self) -> bool {
+        let mut status = true;
+        for (position, val) in self.pipes.indexed_iter() {
+            if val.grid_position != position {
+                eprintln!(
+                    "Expected val.grid_position to be {:?}: was {:?}",
+                    position, val.grid_position
+                );
+                status = false; // don't return, check the rest for logging
+            }
+        }
+
+        let start_variant = self[(self.start_row, self.start_col)].variant;
+        if !self.start_replaced_by_equivalent && start_variant != ConnectionVariant::StartingPoint {
+            eprintln!(
+                "Expected a starting point at {:?}: found {} ({:?})",
+                (self.start_row, self.start_col),
+                start_variant,
+                start_variant
+            );
+            status = false;
+        }
+
+        status
+    }
+
+    fn make_start_into_equivalent(
+        &mut self,
+    ) -> Result<(Connection, ConnectionVariant), &'static str> {
+        let connection = self[(self.start_row, self.start_col)];
+        let equivalent = connection.equivalent_connection(self)?;
+        let index = (self.start_row, self.start_col);
+        self[index] = Connection {
+            variant: equivalent,
+            ..connection
+        };
+        self.start_replaced_by_equivalent = true;
+        Ok((connection, equivalent))
+    }
+
+    fn get(&self, coord: (usize, usize)) -> Option<&Connection> {
+        self.pipes.get(coord)
+    }
+
+    fn copy_with_loop_only(&self) -> Self {
+        let mut copy = Self {
+            pipes: self.pipes.map(|conn| Connection {
+                variant: ConnectionVariant::Ground,
+                ..*conn
+            }),
+            ..*self
+        };
+
+        for connection in LoopIterator::new(self) {
+            copy[connection.grid_position] = connection;
+        }
+
+        copy
+    }
+}
+
+impl fmt::Display for PipeMaze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pipes)
+    }
+}
+
+impl Index<(usize, usize)> for PipeMaze {
+    type Output = Connection;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.pipes[index]
+    }
+}
+
+impl IndexMut<(usize, usize)> for PipeMaze {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.pipes[index]
+    }
+}
+
+impl TryFrom<Grid<ConnectionVariant>> for PipeMaze {
+    type Error = Box<dyn Error>;
+
+    fn try_from(variants: Grid<ConnectionVariant>) -> Result<Self, Self::Error> {
+        let mut starts = variants
+            .indexed_iter()
+            .filter(|&(_, &variant)| variant == ConnectionVariant::StartingPoint);
+        let (start_row, start_col) = match (starts.next(), starts.next()) {
+            (Some((start, _)), None) => start,
+            (None, _) => return Err("No starting points found".into()),
+            (Some(_), Some(_)) => return Err("Multiple starting points".into()),
+        };
+
+        let pipes = Grid::from_rows(variants.iter_rows().enumerate().map(|(row_index, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(col_index, &variant)| Connection::from((variant, row_index, col_index)))
+        }))?;
+
+        Ok(Self {
+            pipes,
+            start_row,
+            start_col,
+            start_replaced_by_equivalent: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct LoopIterator<'g> {
+    grid: &'g PipeMaze,
+    current_position: (usize, usize),
+    from: Direction,
+    left_start: bool,
+}
+
+impl<'g> Iterator for LoopIterator<'g> {
+    type Item = Connection;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left_start && self.current_position == (self.grid.start_row, self.grid.start_col) {
+            None
+        } else {
+            let (direct_1, direct_2) = self.grid[self.current_position]
+                .variant
+                .connected_to()
+                .expect("LoopIterator: Encountered ground, not a closed loop");
+            if !(self.move_towards(direct_1) || self.move_towards(direct_2)) {
+                panic!("LoopIterator: Cannot move from current position")
+            }
+
+            Some(self.grid[self.current_position])
+        }
+    }
+}
+
+impl<'g> LoopIterator<'g> {
+    fn new(grid: &'g PipeMaze) -> Self {
+        Self {
+            grid,
+            current_position: (grid.start_row, grid.start_col),
+            from: Direction::North, // doesn't matter anyways
+            left_start: false,
+        }
+    }
+
+    fn move_towards(&mut self, direction: Direction) -> bool {
+        if self.from == direction {
+            false
+        } else if let Some(translated) =
+            direction.translate_coordinates(self.current_position.0, self.current_position.1)
+        {
+            if self.grid.get(translated).is_some()
+                && self.grid[self.current_position].is_other_connected(self.grid, direction)
+            {
+                self.current_position = translated;
+                self.from = direction.opposite();
+                self.left_start = true;
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+}
+
+pub fn solve(input: &str) -> Result<u64, Box<dyn Error>> {
+    let variants = Grid::from_lines(
+        input.lines().map(str::trim).filter(|line| !line.is_empty()),
+        ConnectionVariant::try_from,
+    )?;
+
+    let mut grid = PipeMaze::try_from(variants)?;
+    let integrity = grid.check_grid_integrity();
+    debug!(integrity, "grid integrity check");
+    if !integrity {
+        return Err("grid.check_grid_integrity() failed".into());
+    }
+
+    let (conn, new_variant) = grid.make_start_into_equivalent()?;
+    trace!("grid:\n{}", grid);
+    debug!(?conn, %new_variant, "starting point replaced");
+
+    let grid = grid.copy_with_loop_only();
+    trace!("loop only:\n{}", grid);
+    let integrity = grid.check_grid_integrity();
+    debug!(integrity, "loop integrity check");
+    if !integrity {
+        return Err("grid.check_grid_integrity() failed".into());
+    }
+
+    let mut count = 0;
+    let mut is_inside = false; // we're not... not initially
+                               // the loop with the enclosed tiles as '.', the others left blank
+    let mut enclosed = String::new();
+
+    for row in grid.pipes.iter_rows() {
+        for connection in row {
+            match connection.variant {
+                ConnectionVariant::Ground => {
+                    if is_inside {
+                        count += 1;
+                        enclosed.push('.');
+                    } else {
+                        enclosed.push(' ');
+                    }
+                }
+                pipe => {
+                    if pipe.connects_to_direction(Direction::South) {
+                        is_inside = !is_inside;
+                    }
+
+                    enclosed.push_str(&pipe.to_string());
+                }
+            }
+        }
+
+        enclosed.push('\n');
+    }
+
+    debug!("enclosed tiles:\n{}", enclosed);
+    Ok(count)
+}
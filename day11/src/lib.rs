@@ -0,0 +1,37 @@
+//! Day 11: Cosmic Expansion
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
+
+/// The image of the galaxies, [`part2`] expanding its empty rows and columns a million times over.
+#[derive(Debug, Clone)]
+pub struct Day11 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    11,
+    Day11,
+    examples = [Example::both(include_str!("../example"), 374, 82000210)]
+);
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::solve(&self.input)?.into())
+    }
+}
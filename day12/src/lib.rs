@@ -1,5 +1,42 @@
+//! Day 12: Hot Springs
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use std::error::Error;
+
+pub mod part1;
+pub mod part2;
 pub mod util {
-    pub mod integer;
     pub mod hash;
+    pub mod integer;
     pub mod parse;
 }
+
+/// The condition records of the springs, unfolded five times over by [`part2`].
+#[derive(Debug, Clone)]
+pub struct Day12 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    12,
+    Day12,
+    examples = [Example::both(include_str!("../example"), 21, 525152)]
+);
+
+impl Solution for Day12 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part1::solve(&self.input)?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part2::part2(&part2::parse(&self.input)).into())
+    }
+}
@@ -139,4 +139,4 @@ impl Hasher for FxHasher {
     fn finish(&self) -> u64 {
         self.hash
     }
-}
\ No newline at end of file
+}
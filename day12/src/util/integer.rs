@@ -43,4 +43,4 @@ macro_rules! empty_trait {
 
 integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128);
 empty_trait!(Unsigned for u8 u16 u32 u64 u128 usize);
-empty_trait!(Signed for i16 i32 i64 i128);
\ No newline at end of file
+empty_trait!(Signed for i16 i32 i64 i128);
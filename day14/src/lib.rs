@@ -0,0 +1,207 @@
+use aoc_utils::{
+    cycles,
+    grid::Grid,
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use core::fmt;
+use std::error::Error;
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum PlatformCell {
+    RollingRock,
+    StationaryRock,
+    Empty,
+}
+
+impl TryFrom<char> for PlatformCell {
+    type Error = String;
+
+    #[inline]
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::StationaryRock),
+            'O' => Ok(Self::RollingRock),
+            other => Err(format!(
+                "char was not any of '.', '#' or 'O', was {:?}",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PlatformCell {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "."),
+            Self::StationaryRock => write!(f, "#"),
+            Self::RollingRock => write!(f, "O"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Platform {
+    grid: Grid<PlatformCell>,
+}
+
+impl Platform {
+    #[inline]
+    pub(crate) fn spin_cycle(&mut self) {
+        self.slide_rolling_to_north();
+        trace!("north:\n{}", self);
+        self.slide_rolling_to_west();
+        trace!("west:\n{}", self);
+        self.slide_rolling_to_south();
+        trace!("south:\n{}", self);
+        self.slide_rolling_to_east();
+        trace!("east:\n{}", self);
+    }
+
+    #[inline]
+    fn slide_rolling_to_south(&mut self) {
+        for i in (0..(self.grid.rows() - 1)).rev() {
+            for j in 0..self.grid.cols() {
+                if matches!(self.grid[(i, j)], PlatformCell::RollingRock) {
+                    if let Some(k) = ((i + 1)..self.grid.rows())
+                        .take_while(|&k| matches!(self.grid[(k, j)], PlatformCell::Empty))
+                        .last()
+                    {
+                        self.grid[(k, j)] = PlatformCell::RollingRock;
+                        self.grid[(i, j)] = PlatformCell::Empty;
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn slide_rolling_to_west(&mut self) {
+        for j in 1..self.grid.cols() {
+            for i in 0..self.grid.rows() {
+                if matches!(self.grid[(i, j)], PlatformCell::RollingRock) {
+                    if let Some(k) = (0..j)
+                        .rev()
+                        .take_while(|&k| matches!(self.grid[(i, k)], PlatformCell::Empty))
+                        .last()
+                    {
+                        self.grid[(i, k)] = PlatformCell::RollingRock;
+                        self.grid[(i, j)] = PlatformCell::Empty;
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn slide_rolling_to_east(&mut self) {
+        for j in (0..(self.grid.cols() - 1)).rev() {
+            for i in 0..self.grid.rows() {
+                if matches!(self.grid[(i, j)], PlatformCell::RollingRock) {
+                    if let Some(k) = ((j + 1)..self.grid.cols())
+                        .take_while(|&k| matches!(self.grid[(i, k)], PlatformCell::Empty))
+                        .last()
+                    {
+                        self.grid[(i, k)] = PlatformCell::RollingRock;
+                        self.grid[(i, j)] = PlatformCell::Empty;
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn slide_rolling_to_north(&mut self) {
+        for i in 1..self.grid.rows() {
+            for j in 0..self.grid.cols() {
+                if matches!(self.grid[(i, j)], PlatformCell::RollingRock) {
+                    if let Some(k) = (0..i)
+                        .rev()
+                        .take_while(|&k| matches!(self.grid[(k, j)], PlatformCell::Empty))
+                        .last()
+                    {
+                        self.grid[(k, j)] = PlatformCell::RollingRock;
+                        self.grid[(i, j)] = PlatformCell::Empty;
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn load_on_north_beam(&self) -> u64 {
+        self.grid
+            .iter_rows()
+            .rev()
+            .zip(1..)
+            .map(|(row, weight)| {
+                weight
+                    * (row
+                        .iter()
+                        .filter(|&cell| matches!(cell, PlatformCell::RollingRock))
+                        .count() as u64)
+            })
+            .sum()
+    }
+
+    /// The load after [`PART_2_SPIN_COUNT`] spin cycles, only spinning until the platform repeats and then the
+    /// spins left over after the last whole cycle.
+    #[inline]
+    pub(crate) fn solve_part_2(mut self) -> u64 {
+        let cycle = cycles::detect(&self, Platform::spin_cycle);
+        let spins = cycles::equivalent_steps(cycle, PART_2_SPIN_COUNT);
+        debug!(cycle_start = cycle.0, cycle_length = cycle.1, spins);
+        for _ in 0..spins {
+            self.spin_cycle();
+        }
+
+        self.load_on_north_beam()
+    }
+}
+
+const PART_2_SPIN_COUNT: u64 = 1_000_000_000;
+
+impl fmt::Display for Platform {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+/// The platform with its rocks before any tilting.
+#[derive(Debug, Clone)]
+pub struct Day14 {
+    platform: Platform,
+}
+
+aoc_utils::register_day!(
+    14,
+    Day14,
+    examples = [Example::both(include_str!("../example"), 136, 64)]
+);
+
+impl Solution for Day14 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let grid = Grid::from_lines(
+            input.lines().take_while(|&line| !line.trim().is_empty()),
+            PlatformCell::try_from,
+        )?;
+
+        Ok(Self {
+            platform: Platform { grid },
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        let mut platform = self.platform.clone();
+        platform.slide_rolling_to_north();
+        Ok(platform.load_on_north_beam().into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self.platform.clone().solve_part_2().into())
+    }
+}
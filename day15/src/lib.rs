@@ -0,0 +1,294 @@
+use aoc_utils::{
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use core::fmt;
+use std::{
+    error::Error,
+    ops::{Index, IndexMut},
+};
+use tracing::trace;
+
+use lazy_static::lazy_static;
+use regex::{Regex, RegexBuilder};
+
+lazy_static! {
+    static ref LABEL_REGEX: Regex = RegexBuilder::new(r#"^([a-zA-Z]+)([-=])(\d+)?$"#)
+        .build()
+        .expect("Regex failed to build");
+}
+
+/// The HASH algorithm: for every character, add its ASCII code, multiply by 17 and keep the remainder of 256.
+#[inline]
+pub fn hash_str(s: &str) -> u8 {
+    let mut res: u32 = 0;
+    for c in s.chars() {
+        res += c as u32;
+        res *= 17;
+        res &= 0xFF;
+    }
+
+    res as u8
+}
+
+/// Splits the initialization sequence on commas, skipping empty steps (e.g. the trailing newline).
+pub fn initialization_steps(input: &str) -> impl Iterator<Item = &str> {
+    input.split(',').filter_map(|part| {
+        let part = part.trim();
+        if part.is_empty() {
+            None
+        } else {
+            Some(part)
+        }
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapEntry<'s> {
+    label: &'s str,
+    focal: u64,
+}
+
+impl<'s> MapEntry<'s> {
+    pub fn new(label: &'s str, focal: u64) -> Self {
+        Self { label, focal }
+    }
+
+    pub fn label(&self) -> &'s str {
+        self.label
+    }
+
+    pub fn focal(&self) -> u64 {
+        self.focal
+    }
+
+    pub fn calculate_power(&self, in_box: u64, slot: u64) -> u64 {
+        (in_box + 1) * (slot + 1) * self.focal
+    }
+}
+
+impl fmt::Debug for MapEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.label, self.focal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map<'s> {
+    boxes: [Vec<MapEntry<'s>>; 256],
+}
+
+impl Default for Map<'_> {
+    fn default() -> Self {
+        let mut vecs = Vec::with_capacity(256);
+        for _ in 0..256 {
+            vecs.push(vec![]);
+        }
+
+        Self {
+            boxes: vecs.try_into().expect("Vec was not 256 elements long"),
+        }
+    }
+}
+
+impl<'s> Index<u8> for Map<'s> {
+    type Output = [MapEntry<'s>];
+
+    fn index(&self, index: u8) -> &Self::Output {
+        &self.boxes[index as usize]
+    }
+}
+
+impl<'s> IndexMut<u8> for Map<'s> {
+    fn index_mut(&mut self, index: u8) -> &mut Self::Output {
+        &mut self.boxes[index as usize]
+    }
+}
+
+impl<'s> Map<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, string: &'s str) {
+        let m = match LABEL_REGEX.captures(string) {
+            Some(ok) => ok,
+            None => panic!(
+                "The string {:?} did not match the regex /{}/",
+                string,
+                LABEL_REGEX.as_str()
+            ),
+        };
+
+        let label = m.get(1).expect("Capture group 1 did not exist").as_str();
+        let sign = m[2]
+            .chars()
+            .next()
+            .expect("Capture group 2 captured nothing");
+        if sign == '=' {
+            let number = m[3].parse::<u64>().expect(r#"Could not parse a \d+ match"#);
+            self.insert_equals_impl(label, number);
+        } else {
+            // sign == '-'
+            self.insert_dash_impl(label);
+        }
+    }
+
+    fn insert_equals_impl(&mut self, label: &'s str, number: u64) {
+        let hash = hash_str(label);
+        if let Some(s) = self[hash].iter_mut().find(|entry| entry.label == label) {
+            s.focal = number;
+        } else {
+            self.boxes[hash as usize].push(MapEntry::new(label, number));
+        }
+    }
+
+    fn insert_dash_impl(&mut self, label: &str) {
+        self.boxes[hash_str(label) as usize].retain(|entry| entry.label != label);
+    }
+
+    pub fn print_box(&self, box_to_print: u8) {
+        println!("Box {}: {:?}", box_to_print, &self[box_to_print]);
+    }
+
+    /// The boxes holding at least a lens, with their number.
+    pub fn non_empty_boxes(&self) -> impl Iterator<Item = (u8, &[MapEntry<'s>])> {
+        self.boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| !b.is_empty())
+            .map(|(i, b)| (i as u8, b.as_slice()))
+    }
+
+    pub fn print_non_empty_boxes(&self) {
+        for (i, _) in self.non_empty_boxes() {
+            self.print_box(i);
+        }
+    }
+
+    pub fn calculate_power(&self) -> u64 {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(box_index, b)| {
+                b.iter().enumerate().map(move |(lens_slot, lens)| {
+                    let res = lens.calculate_power(box_index as u64, lens_slot as u64);
+                    trace!(
+                        "Box {}: Slot {}: Power of {:?}: {}",
+                        box_index,
+                        lens_slot,
+                        lens,
+                        res
+                    );
+                    res
+                })
+            })
+            .sum()
+    }
+}
+
+/// The initialization sequence, whose steps borrow from the input.
+#[derive(Debug, Clone)]
+pub struct Day15 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    15,
+    Day15,
+    examples = [Example::both(include_str!("../example"), 1320, 145)]
+);
+
+impl Solution for Day15 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(initialization_steps(&self.input)
+            .map(|step| hash_str(step) as u64)
+            .sum::<u64>()
+            .into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        let mut hash_map = Map::new();
+        for s in initialization_steps(&self.input) {
+            hash_map.insert(s);
+            trace!("Inserted {s:?}");
+        }
+
+        Ok(hash_map.calculate_power().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    /// The non empty boxes with the label and focal length of their lenses.
+    type Boxes<'a> = &'a [(u8, &'a [(&'a str, u64)])];
+
+    #[test]
+    fn hash() {
+        assert_eq!(hash_str("HASH"), 52);
+        assert_eq!(hash_str("rn=1"), 30);
+        assert_eq!(hash_str("rn"), 0);
+        assert_eq!(hash_str("qp"), 1);
+        assert_eq!(hash_str("pc"), 3);
+    }
+
+    #[test]
+    fn example_steps() {
+        let expected: [Boxes<'_>; 11] = [
+            &[(0, &[("rn", 1)])],
+            &[(0, &[("rn", 1)])],
+            &[(0, &[("rn", 1)]), (1, &[("qp", 3)])],
+            &[(0, &[("rn", 1), ("cm", 2)]), (1, &[("qp", 3)])],
+            &[(0, &[("rn", 1), ("cm", 2)])],
+            &[(0, &[("rn", 1), ("cm", 2)]), (3, &[("pc", 4)])],
+            &[(0, &[("rn", 1), ("cm", 2)]), (3, &[("pc", 4), ("ot", 9)])],
+            &[
+                (0, &[("rn", 1), ("cm", 2)]),
+                (3, &[("pc", 4), ("ot", 9), ("ab", 5)]),
+            ],
+            &[(0, &[("rn", 1), ("cm", 2)]), (3, &[("ot", 9), ("ab", 5)])],
+            &[
+                (0, &[("rn", 1), ("cm", 2)]),
+                (3, &[("ot", 9), ("ab", 5), ("pc", 6)]),
+            ],
+            &[
+                (0, &[("rn", 1), ("cm", 2)]),
+                (3, &[("ot", 7), ("ab", 5), ("pc", 6)]),
+            ],
+        ];
+
+        let mut map = Map::new();
+        for (step, expected) in initialization_steps(EXAMPLE).zip(expected) {
+            map.insert(step);
+            let boxes = map
+                .non_empty_boxes()
+                .map(|(i, lenses)| {
+                    (
+                        i,
+                        lenses
+                            .iter()
+                            .map(|lens| (lens.label(), lens.focal()))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let expected = expected
+                .iter()
+                .map(|&(i, lenses)| (i, lenses.to_vec()))
+                .collect::<Vec<_>>();
+            assert_eq!(boxes, expected, "after {}", step);
+        }
+
+        assert_eq!(map.calculate_power(), 145);
+    }
+}
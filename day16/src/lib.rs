@@ -0,0 +1,774 @@
+use aoc_utils::{
+    error::AocError,
+    geometry::Direction,
+    grid::Grid,
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::{Parser, ValueEnum};
+use core::fmt;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{collections::HashMap, error::Error, str::FromStr};
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SplitterVariant {
+    Vertical,
+    Horizontal,
+}
+
+impl SplitterVariant {
+    #[inline]
+    pub(crate) const fn need_to_split(
+        &self,
+        beam_from: Direction,
+    ) -> Option<(Direction, Direction)> {
+        match self {
+            Self::Vertical => {
+                if matches!(beam_from, Direction::East | Direction::West) {
+                    Some((Direction::North, Direction::South))
+                } else {
+                    None
+                }
+            }
+            Self::Horizontal => {
+                if matches!(beam_from, Direction::North | Direction::South) {
+                    Some((Direction::East, Direction::West))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for SplitterVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Horizontal => '-',
+                Self::Vertical => '|',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MirrorVariant {
+    ForwardSlash,
+    Backslash,
+}
+
+impl MirrorVariant {
+    #[inline]
+    pub(crate) const fn reflect(&self, beam_from: Direction) -> Direction {
+        use MirrorVariant::*;
+        match beam_from {
+            Direction::North => match self {
+                ForwardSlash => Direction::West,
+                Backslash => Direction::East,
+            },
+            Direction::South => match self {
+                ForwardSlash => Direction::East,
+                Backslash => Direction::West,
+            },
+            Direction::East => match self {
+                ForwardSlash => Direction::South,
+                Backslash => Direction::North,
+            },
+            Direction::West => match self {
+                ForwardSlash => Direction::North,
+                Backslash => Direction::South,
+            },
+        }
+    }
+}
+
+impl fmt::Display for MirrorVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::ForwardSlash => '/',
+                Self::Backslash => '\\',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tile {
+    Empty,
+    Splitter(SplitterVariant),
+    Mirror(MirrorVariant),
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "."),
+            Self::Mirror(mirror) => write!(f, "{}", mirror),
+            Self::Splitter(splitter) => write!(f, "{}", splitter),
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use MirrorVariant::*;
+        use SplitterVariant::*;
+        match value {
+            '.' => Ok(Self::Empty),
+            '-' => Ok(Self::Splitter(Horizontal)),
+            '|' => Ok(Self::Splitter(Vertical)),
+            '/' => Ok(Self::Mirror(ForwardSlash)),
+            '\\' => Ok(Self::Mirror(Backslash)),
+            other => Err(format!("Unrecognized tile char {:?}", other)),
+        }
+    }
+}
+
+/// One bit per direction, for the directions the beams already came from to fit in a byte.
+#[inline]
+const fn direction_bit(direction: Direction) -> u8 {
+    1 << direction.index()
+}
+
+/// Set of tile positions, one bit per tile in row-major order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TileSet {
+    cols: usize,
+    words: Box<[u64]>,
+}
+
+impl TileSet {
+    pub(crate) fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            cols,
+            words: vec![0; (rows * cols).div_ceil(64)].into_boxed_slice(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn insert(&mut self, row_index: usize, col_index: usize) {
+        let bit = row_index * self.cols + col_index;
+        self.words[bit / 64] |= 1 << (bit % 64);
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, row_index: usize, col_index: usize) -> bool {
+        let bit = row_index * self.cols + col_index;
+        self.words[bit / 64] & (1 << (bit % 64)) != 0
+    }
+
+    pub(crate) fn union_with(&mut self, other: &Self) {
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(word, other)| *word |= other);
+    }
+
+    pub(crate) fn len(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Contraption {
+    tiles: Grid<Tile>,
+    /// beams leaving the grid re-enter on the opposite edge instead of dying
+    wrap: bool,
+}
+
+impl Contraption {
+    pub(crate) fn with_wrapping(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// The tile next to the given one when `going` in a direction, or `None` if that leaves
+    /// the grid (never the case when wrapping, where the beam re-enters from the opposite edge).
+    #[inline]
+    pub(crate) fn step(
+        &self,
+        row_index: usize,
+        col_index: usize,
+        going: Direction,
+    ) -> Option<(usize, usize)> {
+        let (rows, cols) = (self.tiles.rows(), self.tiles.cols());
+        if self.wrap {
+            return Some(going.wrapping_translate_coordinates(row_index, col_index, rows, cols));
+        }
+
+        going
+            .translate_coordinates(row_index, col_index)
+            .filter(|&next| self.tiles.contains(next))
+    }
+
+    /// The grid with the energized tiles as `#` and the others as `.`
+    pub(crate) fn render_energized(&self, energized: &TileSet) -> String {
+        self.tiles
+            .iter_rows()
+            .enumerate()
+            .map(|(row_index, row)| {
+                (0..row.len())
+                    .map(|col_index| {
+                        if energized.contains(row_index, col_index) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    /// Every tile on the edge of the grid, paired with the side the beam enters from
+    /// (corners appear once per side they touch).
+    pub(crate) fn border_starts(&self) -> impl Iterator<Item = (usize, usize, Direction)> {
+        let rows = self.tiles.rows();
+        let cols = self.tiles.cols();
+        (0..rows)
+            .flat_map(move |row| [(row, 0, Direction::West), (row, cols - 1, Direction::East)])
+            .chain((0..cols).flat_map(move |col| {
+                [
+                    (0, col, Direction::North),
+                    (rows - 1, col, Direction::South),
+                ]
+            }))
+    }
+
+    /// Follows a single beam from `initial` until it either leaves the grid or reaches a splitter
+    /// that splits it, returning the tiles it crossed and the position of that splitter.
+    ///
+    /// Without wrapping, a beam entering from the border can never loop without splitting
+    /// (the path is reversible and leads back outside), so this always terminates.
+    pub(crate) fn beam_segment(
+        &self,
+        initial: (usize, usize, Direction),
+    ) -> (TileSet, Option<(usize, usize)>) {
+        let rows = self.tiles.rows();
+        let cols = self.tiles.cols();
+        let mut tiles = TileSet::new(rows, cols);
+        // only needed when wrapping, where a beam can circle forever without splitting
+        let mut beams_from = vec![0u8; if self.wrap { rows * cols } else { 0 }];
+        let (mut row_index, mut col_index, mut beam_from) = initial;
+        loop {
+            if self.wrap {
+                let seen = &mut beams_from[row_index * cols + col_index];
+                if *seen & direction_bit(beam_from) != 0 {
+                    return (tiles, None);
+                }
+
+                *seen |= direction_bit(beam_from);
+            }
+
+            tiles.insert(row_index, col_index);
+            let going = match self.tiles[(row_index, col_index)] {
+                Tile::Empty => beam_from.opposite(),
+                Tile::Mirror(variant) => variant.reflect(beam_from),
+                Tile::Splitter(variant) => {
+                    if variant.need_to_split(beam_from).is_some() {
+                        return (tiles, Some((row_index, col_index)));
+                    }
+
+                    beam_from.opposite()
+                }
+            };
+
+            match self.step(row_index, col_index, going) {
+                Some((next_row, next_col)) => {
+                    row_index = next_row;
+                    col_index = next_col;
+                    beam_from = going.opposite();
+                }
+                None => return (tiles, None),
+            }
+        }
+    }
+
+    /// Every tile energized once the splitter at `position` splits a beam;
+    /// that set does not depend on which side the beam hit it from.
+    pub(crate) fn splitter_closure(&self, position: (usize, usize)) -> TileSet {
+        let (row_index, col_index) = position;
+        let beam_from = match self.tiles[(row_index, col_index)] {
+            Tile::Splitter(SplitterVariant::Vertical) => Direction::East,
+            Tile::Splitter(SplitterVariant::Horizontal) => Direction::North,
+            other => panic!("Tile {} at {:?} is not a splitter", other, position),
+        };
+
+        self.energize((row_index, col_index, beam_from))
+    }
+
+    /// The (at most two) beams leaving the tile a beam entered at `beam`, as seen from their next tile.
+    pub(crate) fn next_beams(
+        &self,
+        beam: (usize, usize, Direction),
+    ) -> [Option<(usize, usize, Direction)>; 2] {
+        let (row_index, col_index, beam_from) = beam;
+        let (first, second) = match self.tiles[(row_index, col_index)] {
+            Tile::Empty => (beam_from.opposite(), None),
+            Tile::Mirror(variant) => (variant.reflect(beam_from), None),
+            Tile::Splitter(variant) => match variant.need_to_split(beam_from) {
+                Some((direct1, direct2)) => (direct1, Some(direct2)),
+                None => (beam_from.opposite(), None),
+            },
+        };
+
+        let step = |going: Direction| {
+            let (next_row, next_col) = self.step(row_index, col_index, going)?;
+            Some((next_row, next_col, going.opposite()))
+        };
+
+        [step(first), second.and_then(step)]
+    }
+
+    /// Energizes the grid from `initial` and returns every tile the beams went through,
+    /// leaving the grid itself untouched.
+    pub(crate) fn energize(&self, initial: (usize, usize, Direction)) -> TileSet {
+        let rows = self.tiles.rows();
+        let cols = self.tiles.cols();
+        // per tile, one bit for each direction a beam already came from (see [`direction_bit`])
+        let mut beams_from = vec![0u8; rows * cols];
+        let mut energized = TileSet::new(rows, cols);
+        let mut directions = vec![initial];
+        while let Some(beam) = directions.pop() {
+            let (row_index, col_index, beam_from) = beam;
+            let seen = &mut beams_from[row_index * cols + col_index];
+            if *seen & direction_bit(beam_from) != 0 {
+                continue;
+            }
+
+            *seen |= direction_bit(beam_from);
+            energized.insert(row_index, col_index);
+            directions.extend(self.next_beams(beam).into_iter().flatten());
+        }
+
+        energized
+    }
+}
+
+impl fmt::Display for Contraption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+impl FromStr for Contraption {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::from_lines(
+            s.lines().map(str::trim).filter(|line| !line.is_empty()),
+            Tile::try_from,
+        )?;
+        Ok(Self { tiles, wrap: false })
+    }
+}
+
+/// Each (tile, beam direction) is a node of a directed graph; the graph is condensed into
+/// its strongly connected components so every component's energized tiles are computed only once.
+///
+/// Only the components reachable from the border are visited, and a component's tiles are dropped
+/// as soon as every edge into it and every start on it have been consumed.
+struct BeamGraph<'g> {
+    grid: &'g Contraption,
+    cols: usize,
+    /// Tarjan's discovery index for each node
+    index: Vec<usize>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    /// component of each node (`usize::MAX` while not yet assigned)
+    component: Vec<usize>,
+    /// tiles energized by a beam in each component, including everything downstream
+    /// (`None` once nothing is left to read them)
+    closures: Vec<Option<TileSet>>,
+    /// edges into each node plus the border starts on it, left to consume its component's tiles
+    readers: Vec<usize>,
+    /// readers left for each component, once completed
+    component_readers: Vec<usize>,
+    next_index: usize,
+}
+
+impl<'g> BeamGraph<'g> {
+    pub(crate) fn new(grid: &'g Contraption) -> Self {
+        let nodes = grid.tiles.rows() * grid.tiles.cols() * Direction::ALL.len();
+        let mut graph = Self {
+            grid,
+            cols: grid.tiles.cols(),
+            index: vec![usize::MAX; nodes],
+            lowlink: vec![0; nodes],
+            on_stack: vec![false; nodes],
+            stack: vec![],
+            component: vec![usize::MAX; nodes],
+            closures: vec![],
+            readers: vec![0; nodes],
+            component_readers: vec![],
+            next_index: 0,
+        };
+
+        for node in 0..nodes {
+            for successor in graph.successors(node).into_iter().flatten() {
+                graph.readers[successor] += 1;
+            }
+        }
+
+        for initial in grid.border_starts() {
+            let node = graph.node(initial);
+            graph.readers[node] += 1;
+        }
+
+        graph
+    }
+
+    /// Number of components found so far.
+    pub(crate) fn components(&self) -> usize {
+        self.closures.len()
+    }
+
+    #[inline]
+    fn node(&self, beam: (usize, usize, Direction)) -> usize {
+        (beam.0 * self.cols + beam.1) * Direction::ALL.len() + beam.2.index()
+    }
+
+    #[inline]
+    fn beam(&self, node: usize) -> (usize, usize, Direction) {
+        let tile = node / Direction::ALL.len();
+        (
+            tile / self.cols,
+            tile % self.cols,
+            Direction::ALL[node % Direction::ALL.len()],
+        )
+    }
+
+    fn successors(&self, node: usize) -> [Option<usize>; 2] {
+        self.grid
+            .next_beams(self.beam(node))
+            .map(|next| next.map(|beam| self.node(beam)))
+    }
+
+    /// Number of tiles energized by a beam starting as `beam`, which must be one of the border starts.
+    pub(crate) fn count_energized(&mut self, beam: (usize, usize, Direction)) -> u64 {
+        let node = self.node(beam);
+        if self.index[node] == usize::MAX {
+            self.strong_connect(node);
+        }
+
+        let component = self.component[node];
+        let count = self.closure(component).len();
+        self.consume(component);
+        count
+    }
+
+    fn closure(&self, component: usize) -> &TileSet {
+        self.closures[component]
+            .as_ref()
+            .expect("component tiles read after every reader consumed them")
+    }
+
+    /// One reader of the component is done with its tiles, which are dropped after the last one.
+    fn consume(&mut self, component: usize) {
+        self.component_readers[component] -= 1;
+        if self.component_readers[component] == 0 {
+            self.closures[component] = None;
+        }
+    }
+
+    /// Iterative Tarjan: components are completed in reverse topological order,
+    /// so the closures of every component reachable from a new one are already known.
+    fn strong_connect(&mut self, root: usize) {
+        let mut call_stack = vec![(root, self.successors(root), 0)];
+        self.visit(root);
+
+        while let Some((node, successors, next)) = call_stack.last_mut() {
+            let node = *node;
+            if let Some(successor) = successors.get(*next).copied() {
+                *next += 1;
+                let Some(successor) = successor else {
+                    continue;
+                };
+
+                if self.index[successor] == usize::MAX {
+                    self.visit(successor);
+                    call_stack.push((successor, self.successors(successor), 0));
+                } else if self.on_stack[successor] {
+                    self.lowlink[node] = self.lowlink[node].min(self.index[successor]);
+                }
+
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some((parent, _, _)) = call_stack.last() {
+                self.lowlink[*parent] = self.lowlink[*parent].min(self.lowlink[node]);
+            }
+
+            if self.lowlink[node] == self.index[node] {
+                self.complete_component(node);
+            }
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.index[node] = self.next_index;
+        self.lowlink[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+
+    fn complete_component(&mut self, root: usize) {
+        let component = self.closures.len();
+        let mut members = vec![];
+        loop {
+            let member = self
+                .stack
+                .pop()
+                .expect("Tarjan stack ran out before the root");
+            self.on_stack[member] = false;
+            self.component[member] = component;
+            members.push(member);
+            if member == root {
+                break;
+            }
+        }
+
+        // edges between members never read the tiles, as they are not stored yet
+        let mut readers = members
+            .iter()
+            .map(|&member| self.readers[member])
+            .sum::<usize>();
+        let mut closure = TileSet::new(self.grid.tiles.rows(), self.cols);
+        let mut consumed = vec![];
+        for &member in members.iter() {
+            let (row_index, col_index, _) = self.beam(member);
+            closure.insert(row_index, col_index);
+            for successor in self.successors(member).into_iter().flatten() {
+                let successor_component = self.component[successor];
+                if successor_component == component {
+                    readers -= 1;
+                } else {
+                    closure.union_with(self.closure(successor_component));
+                    consumed.push(successor_component);
+                }
+            }
+        }
+
+        self.closures.push(Some(closure));
+        self.component_readers.push(readers);
+        for successor_component in consumed {
+            self.consume(successor_component);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+enum Algorithm {
+    /// follow each start up to its first splitter, then reuse cached splitter closures
+    #[default]
+    Splitters,
+    /// condense the (tile, direction) graph into strongly connected components
+    Graph,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<16>,
+    /// How part 2 finds the tiles energized from every start
+    #[arg(long = "algo", value_enum, default_value_t)]
+    algorithm: Algorithm,
+    /// Let the beams leaving the contraption come back in from the opposite edge
+    #[arg(long)]
+    wrap: bool,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let input = input.read()?;
+    let grid = input.parse::<Contraption>()?.with_wrapping(options.wrap);
+    trace!("tiles:\n{}", grid);
+
+    // part 2 starts from part 1's answer, so it is always computed
+    let energized = grid.energize((0, 0, Direction::West));
+    let mut answer = energized.len();
+
+    if options.args.part.solves_part1() {
+        println!("Part 1 answer: {}", answer);
+    }
+
+    debug!("energized tiles:\n{}", grid.render_energized(&energized));
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    let results = part_2_results(&grid, options.algorithm);
+    for &((row, col, beam_from), count) in &results {
+        debug!(
+            "row {}, col {}, beam from {:?}: {} tiles energized",
+            row, col, beam_from, count
+        );
+    }
+
+    if let Some(&((row, col, beam_from), count)) = results.iter().max_by_key(|(_, count)| *count) {
+        println!(
+            "Best of {} part 2 starts: row {}, col {}, beam from {:?} ({} tiles energized)",
+            results.len(),
+            row,
+            col,
+            beam_from,
+            count
+        );
+        answer = answer.max(count);
+    }
+
+    Ok(Some(answer))
+}
+
+/// How many tiles every start from the border energizes.
+fn part_2_results(
+    grid: &Contraption,
+    algorithm: Algorithm,
+) -> Vec<((usize, usize, Direction), u64)> {
+    match algorithm {
+        Algorithm::Splitters => part_2_splitters(grid),
+        Algorithm::Graph => {
+            let mut graph = BeamGraph::new(grid);
+            let results = grid
+                .border_starts()
+                .map(|initial| (initial, graph.count_energized(initial)))
+                .collect_vec();
+            println!(
+                "Strongly connected components for part 2: {}",
+                graph.components()
+            );
+            results
+        }
+    }
+}
+
+fn part_2_splitters(grid: &Contraption) -> Vec<((usize, usize, Direction), u64)> {
+    let segments = grid
+        .border_starts()
+        .collect_vec()
+        .into_par_iter()
+        .map(|initial| (initial, grid.beam_segment(initial)))
+        .collect::<Vec<_>>();
+
+    let closures: HashMap<(usize, usize), TileSet> = segments
+        .iter()
+        .filter_map(|(_, (_, splitter))| *splitter)
+        .unique()
+        .collect_vec()
+        .into_par_iter()
+        .map(|splitter| (splitter, grid.splitter_closure(splitter)))
+        .collect();
+    debug!(
+        closures = closures.len(),
+        "splitter closures computed for part 2"
+    );
+
+    segments
+        .into_iter()
+        .map(|(initial, (mut tiles, splitter))| {
+            if let Some(splitter) = splitter {
+                tiles.union_with(&closures[&splitter]);
+            }
+
+            (initial, tiles.len())
+        })
+        .collect_vec()
+}
+
+/// The contraption, beams going through its edges without wrapping around.
+#[derive(Debug, Clone)]
+pub struct Day16 {
+    grid: Contraption,
+}
+
+aoc_utils::register_day!(
+    16,
+    Day16,
+    examples = [Example::both(include_str!("../example"), 46, 51)]
+);
+
+impl Solution for Day16 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            grid: input.parse()?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self.grid.energize((0, 0, Direction::West)).len().into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_2_results(&self.grid, Algorithm::default())
+            .into_iter()
+            .map(|(_, count)| count)
+            .max()
+            .ok_or("The contraption has no border")?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn border_starts() {
+        let grid: Contraption = "...\n...".parse().unwrap();
+        let starts = grid.border_starts().sorted().collect_vec();
+        let expected = [
+            (0, 0, West),
+            (1, 0, West),
+            (0, 2, East),
+            (1, 2, East),
+            (0, 0, North),
+            (0, 1, North),
+            (0, 2, North),
+            (1, 0, South),
+            (1, 1, South),
+            (1, 2, South),
+        ]
+        .into_iter()
+        .sorted()
+        .collect_vec();
+        assert_eq!(starts, expected);
+
+        // each corner once per side: the top-left one entered from the west and from the north
+        for corner in [(0, 0), (0, 2), (1, 0), (1, 2)] {
+            let sides = starts
+                .iter()
+                .filter(|&&(row, col, _)| (row, col) == corner)
+                .count();
+            assert_eq!(sides, 2, "corner {:?}", corner);
+        }
+        assert_eq!(
+            starts
+                .iter()
+                .filter(|&&(row, col, _)| (row, col) == (0, 1))
+                .count(),
+            1
+        );
+        assert_eq!(starts.len(), 2 * (2 + 3));
+    }
+}
@@ -0,0 +1,407 @@
+use aoc_utils::{
+    error::AocError,
+    geometry::Direction,
+    graph,
+    grid::Grid,
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Part, Solution},
+};
+use clap::{Parser, ValueEnum};
+use std::{
+    error::Error,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CityBlock {
+    weight: u8,
+}
+
+impl TryFrom<char> for CityBlock {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let weight = value
+            .to_digit(10)
+            .ok_or_else(|| format!("char was not a digit ({:?})", value))?;
+        Ok(CityBlock {
+            weight: weight as u8,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct City {
+    blocks: Grid<CityBlock>,
+}
+
+impl City {
+    /// Least heat loss from the top-left to the bottom-right block for a crucible that must move
+    /// at least `min_run` and at most `max_run` blocks in a straight line before turning.
+    ///
+    /// Since the crucible can neither reverse nor keep going straight after a full run,
+    /// every move is a whole run along the axis perpendicular to the previous one:
+    /// nodes are only (row, col, axis of the last run), no straight-step counter needed.
+    ///
+    /// `None` when no such runs lead to the bottom-right block.
+    pub(crate) fn min_heat_loss(&self, min_run: u8, max_run: u8) -> Option<u64> {
+        let (rows, cols) = (self.blocks.rows(), self.blocks.cols());
+        let goal = (rows - 1, cols - 1);
+        graph::dense_dijkstra(
+            rows * cols * 2,
+            |&(row, col, axis)| (row * cols + col) * 2 + axis as usize,
+            [Axis::Horizontal, Axis::Vertical].map(|axis| (0, 0, axis)),
+            |&(row, col, axis)| self.runs_from(row, col, axis.perpendicular(), min_run, max_run),
+            |&(row, col, _)| (row, col) == goal,
+        )
+    }
+
+    /// Same as [`City::min_heat_loss`], searching from both the start and the goal at once.
+    pub(crate) fn min_heat_loss_bidirectional(&self, min_run: u8, max_run: u8) -> Option<u64> {
+        let (goal_row, goal_col) = (self.blocks.rows() - 1, self.blocks.cols() - 1);
+        graph::bidirectional_dijkstra(
+            [Axis::Horizontal, Axis::Vertical].map(|axis| (0, 0, axis)),
+            [Axis::Horizontal, Axis::Vertical].map(|axis| (goal_row, goal_col, axis)),
+            |&(row, col, axis)| self.runs_from(row, col, axis.perpendicular(), min_run, max_run),
+            |&(row, col, axis)| self.runs_into(row, col, axis, min_run, max_run),
+        )
+    }
+
+    /// Every block a run of `min_run..=max_run` blocks along `axis` can stop on, with the heat lost on the way.
+    fn runs_from(
+        &self,
+        row: usize,
+        col: usize,
+        axis: Axis,
+        min_run: u8,
+        max_run: u8,
+    ) -> Vec<((usize, usize, Axis), u64)> {
+        let mut runs = vec![];
+        for direction in axis.directions() {
+            let (mut new_row, mut new_col, mut heat_loss) = (row, col, 0);
+            for steps in 1..=max_run {
+                match direction.translate_coordinates(new_row, new_col) {
+                    Some(next) if self.blocks.contains(next) => (new_row, new_col) = next,
+                    _ => break,
+                }
+
+                heat_loss += self[(new_row, new_col)].weight as u64;
+                if steps >= min_run {
+                    runs.push(((new_row, new_col, axis), heat_loss));
+                }
+            }
+        }
+
+        runs
+    }
+
+    /// Reverse of [`City::runs_from`]: every block from which a run of `min_run..=max_run` blocks
+    /// along `axis` ends on this one, with the heat lost during that run.
+    fn runs_into(
+        &self,
+        row: usize,
+        col: usize,
+        axis: Axis,
+        min_run: u8,
+        max_run: u8,
+    ) -> Vec<((usize, usize, Axis), u64)> {
+        let mut runs = vec![];
+        for direction in axis.directions() {
+            let (mut new_row, mut new_col, mut heat_loss) = (row, col, 0);
+            for steps in 1..=max_run {
+                heat_loss += self[(new_row, new_col)].weight as u64;
+                match direction.translate_coordinates(new_row, new_col) {
+                    Some(next) if self.blocks.contains(next) => (new_row, new_col) = next,
+                    _ => break,
+                }
+
+                if steps >= min_run {
+                    runs.push(((new_row, new_col, axis.perpendicular()), heat_loss));
+                }
+            }
+        }
+
+        runs
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    #[inline]
+    pub(crate) const fn perpendicular(&self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn directions(&self) -> [Direction; 2] {
+        match self {
+            Self::Horizontal => [Direction::East, Direction::West],
+            Self::Vertical => [Direction::North, Direction::South],
+        }
+    }
+}
+
+impl Index<(usize, usize)> for City {
+    type Output = CityBlock;
+
+    #[inline]
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.blocks[index]
+    }
+}
+
+impl IndexMut<(usize, usize)> for City {
+    #[inline]
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.blocks[index]
+    }
+}
+
+impl FromStr for City {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let blocks = Grid::from_lines(
+            s.lines().map(str::trim).filter(|line| !line.is_empty()),
+            CityBlock::try_from,
+        )?;
+        Ok(Self { blocks })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+pub enum Algorithm {
+    #[default]
+    Dijkstra,
+    /// experimental: search from both ends at once
+    Bidirectional,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<17>,
+    /// Solve a single crucible moving at least that many blocks in a straight line (1 unless given) instead
+    /// of both parts
+    #[arg(long, value_name = "BLOCKS")]
+    min_run: Option<u8>,
+    /// Solve a single crucible moving at most that many blocks in a straight line (3 unless given) instead of
+    /// both parts
+    #[arg(long, value_name = "BLOCKS")]
+    max_run: Option<u8>,
+    /// the straight runs of the single crucible of `--min-run` and `--max-run`, if either was given
+    #[arg(skip)]
+    pub runs: Option<(u8, u8)>,
+    /// The search finding the least heat loss
+    #[arg(long = "algo", value_enum, default_value_t)]
+    pub algorithm: Algorithm,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let mut options = Options::parse();
+    if options.min_run.is_some() || options.max_run.is_some() {
+        let (min_run, max_run) = (options.min_run.unwrap_or(1), options.max_run.unwrap_or(3));
+        if min_run == 0 || min_run > max_run {
+            return Err(format!("Invalid straight runs: {} to {} blocks", min_run, max_run).into());
+        }
+
+        options.runs = Some((min_run, max_run));
+    }
+
+    options.args.init_tracing();
+    Ok(options)
+}
+
+fn min_heat_loss(
+    grid: &City,
+    min_run: u8,
+    max_run: u8,
+    algorithm: Algorithm,
+) -> Result<u64, Box<dyn Error>> {
+    let heat_loss = match algorithm {
+        Algorithm::Dijkstra => grid.min_heat_loss(min_run, max_run),
+        Algorithm::Bidirectional => grid.min_heat_loss_bidirectional(min_run, max_run),
+    };
+
+    heat_loss.ok_or_else(|| {
+        format!(
+            "No straight runs of {} to {} blocks lead to the bottom-right block",
+            min_run, max_run
+        )
+        .into()
+    })
+}
+
+pub fn solve_runs(
+    input: &InputSource,
+    min_run: u8,
+    max_run: u8,
+    algorithm: Algorithm,
+) -> Result<u64, Box<dyn Error>> {
+    let input = input.read()?;
+    let grid: City = input.parse()?;
+
+    min_heat_loss(&grid, min_run, max_run, algorithm)
+}
+
+pub fn solve(
+    input: &InputSource,
+    part: Part,
+    algorithm: Algorithm,
+) -> Result<Option<u64>, Box<dyn Error>> {
+    let input = input.read()?;
+    let grid: City = input.parse()?;
+
+    if part.solves_part1() {
+        println!("Part 1 answer: {}", min_heat_loss(&grid, 1, 3, algorithm)?);
+    }
+
+    if !part.solves_part2() {
+        return Ok(None);
+    }
+
+    Ok(Some(min_heat_loss(&grid, 4, 10, algorithm)?))
+}
+
+/// The city blocks and the heat each of them loses.
+#[derive(Debug, Clone)]
+pub struct Day17 {
+    grid: City,
+}
+
+aoc_utils::register_day!(
+    17,
+    Day17,
+    examples = [
+        Example::both(include_str!("../example1"), 102, 94),
+        Example::part2_only(include_str!("../example2"), 71),
+    ]
+);
+
+impl Solution for Day17 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            grid: input.parse()?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(min_heat_loss(&self.grid, 1, 3, Algorithm::default())?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(min_heat_loss(&self.grid, 4, 10, Algorithm::default())?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    /// The search [`City::min_heat_loss`] replaced, with its own queue and cost table.
+    fn bespoke_min_heat_loss(city: &City, min_run: u8, max_run: u8) -> u64 {
+        let (rows, cols) = (city.blocks.rows(), city.blocks.cols());
+        let state = |row, col, axis: Axis| (row * cols + col) * 2 + axis as usize;
+        let mut queue = BinaryHeap::new();
+        let mut best = vec![u64::MAX; rows * cols * 2];
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            best[state(0, 0, axis)] = 0;
+            queue.push((Reverse(0), 0, 0, axis));
+        }
+
+        while let Some((Reverse(prio), row, col, axis)) = queue.pop() {
+            if (row, col) == (rows - 1, cols - 1) {
+                return prio;
+            }
+
+            if prio > best[state(row, col, axis)] {
+                continue;
+            }
+
+            let new_axis = axis.perpendicular();
+            for direction in new_axis.directions() {
+                let (mut new_row, mut new_col, mut new_prio) = (row, col, prio);
+                for steps in 1..=max_run {
+                    match direction.translate_coordinates(new_row, new_col) {
+                        Some((r, c)) if r < rows && c < cols => (new_row, new_col) = (r, c),
+                        _ => break,
+                    }
+
+                    new_prio += city[(new_row, new_col)].weight as u64;
+                    if steps < min_run {
+                        continue;
+                    }
+
+                    let new_state = state(new_row, new_col, new_axis);
+                    if new_prio < best[new_state] {
+                        best[new_state] = new_prio;
+                        queue.push((Reverse(new_prio), new_row, new_col, new_axis));
+                    }
+                }
+            }
+        }
+
+        panic!("Unreachable");
+    }
+
+    #[test]
+    fn dense_dijkstra_matches_the_bespoke_search() {
+        let cases = [
+            (include_str!("../example1"), 1, 3, 102),
+            (include_str!("../example1"), 4, 10, 94),
+            (include_str!("../example2"), 4, 10, 71),
+        ];
+        for (input, min_run, max_run, expected) in cases {
+            let city: City = input.parse().unwrap();
+            assert_eq!(bespoke_min_heat_loss(&city, min_run, max_run), expected);
+            assert_eq!(city.min_heat_loss(min_run, max_run), Some(expected));
+        }
+    }
+
+    #[test]
+    fn min_heat_loss_of_the_examples() {
+        let example1: City = include_str!("../example1").parse().unwrap();
+        let example2: City = include_str!("../example2").parse().unwrap();
+        for algorithm in [Algorithm::Dijkstra, Algorithm::Bidirectional] {
+            assert_eq!(
+                min_heat_loss(&example1, 1, 3, algorithm).unwrap(),
+                102,
+                "{:?}",
+                algorithm
+            );
+            assert_eq!(
+                min_heat_loss(&example1, 4, 10, algorithm).unwrap(),
+                94,
+                "{:?}",
+                algorithm
+            );
+            assert_eq!(
+                min_heat_loss(&example2, 4, 10, algorithm).unwrap(),
+                71,
+                "{:?}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn unreachable_bottom_right() {
+        // example 1 is 13 blocks wide and high
+        let example1: City = include_str!("../example1").parse().unwrap();
+        assert_eq!(example1.min_heat_loss(13, 20), None);
+        assert_eq!(example1.min_heat_loss_bidirectional(13, 20), None);
+        assert!(min_heat_loss(&example1, 50, 60, Algorithm::Dijkstra).is_err());
+        assert!(min_heat_loss(&example1, 50, 60, Algorithm::Bidirectional).is_err());
+    }
+}
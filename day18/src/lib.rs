@@ -0,0 +1,783 @@
+use aoc_utils::{
+    geometry::{Direction, Point, Segment},
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+    svg::Svg,
+};
+use clap::{Parser, ValueEnum};
+use core::fmt;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{
+    collections::{BTreeSet, HashSet},
+    error::Error,
+    num::ParseIntError,
+    path::PathBuf,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Rect([i64; 4]);
+
+type RectsGrid = Vec<Vec<Rect>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PlanError {
+    /// the instruction at this index moves the trench out of the 64-bit coordinate range
+    CoordinateOverflow { instruction: usize },
+    /// the trench ends at this point instead of where it started
+    NotClosed { end: Point },
+    /// the instruction at this index does not move the trench along a single axis
+    NotAxisAligned { instruction: usize },
+    /// the trench dug by these two instructions crosses or touches itself
+    SelfIntersecting { first: usize, second: usize },
+    /// the lagoon holds more cubes than fit in 64 bits
+    AreaOverflow,
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CoordinateOverflow { instruction } => write!(
+                f,
+                "Instruction #{} moves the trench out of the 64-bit coordinate range",
+                instruction
+            ),
+            Self::NotClosed { end } => write!(
+                f,
+                "The polygon does not end where it started (ends at {:?})",
+                end
+            ),
+            Self::NotAxisAligned { instruction } => write!(
+                f,
+                "Instruction #{} does not move along exactly one axis",
+                instruction
+            ),
+            Self::SelfIntersecting { first, second } => write!(
+                f,
+                "The trench of instructions #{} and #{} intersect",
+                first, second
+            ),
+            Self::AreaOverflow => write!(f, "The lagoon's area does not fit in 64 bits"),
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RGBValue(u32);
+
+impl From<u32> for RGBValue {
+    fn from(value: u32) -> Self {
+        RGBValue(value)
+    }
+}
+
+impl fmt::Display for RGBValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:06x}", self.0)
+    }
+}
+
+impl FromStr for RGBValue {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_start_matches(['(', '#']);
+        let s = s.trim_end_matches(')');
+        Ok(u32::from_str_radix(s, 16)?.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct DigInstruction {
+    direction: Direction,
+    distance: u32,
+    rgb: RGBValue,
+}
+
+impl DigInstruction {
+    #[inline]
+    pub(crate) const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    #[inline]
+    pub(crate) const fn distance(&self) -> u32 {
+        self.distance
+    }
+
+    /// How far the digger moves in (rows, columns).
+    #[inline]
+    pub(crate) const fn movement(&self) -> (i128, i128) {
+        let distance = self.distance() as i128;
+        let (dr, dc) = self.direction().delta();
+        (dr as i128 * distance, dc as i128 * distance)
+    }
+
+    #[inline]
+    pub(crate) const fn rgb(&self) -> RGBValue {
+        self.rgb
+    }
+}
+
+impl FromStr for DigInstruction {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: [&str; 3] =
+            s.split_whitespace()
+                .collect_vec()
+                .try_into()
+                .map_err(|vec: Vec<&str>| {
+                    format!(
+                        "Could not split string {:?} into 3 parts (was split into {} parts)",
+                        s,
+                        vec.len()
+                    )
+                })?;
+
+        Ok(Self {
+            direction: parts[0].chars().next().unwrap().try_into()?,
+            distance: parts[1].parse()?,
+            rgb: parts[2].parse()?,
+        })
+    }
+}
+
+impl TryFrom<RGBValue> for DigInstruction {
+    type Error = String;
+
+    fn try_from(value: RGBValue) -> Result<Self, Self::Error> {
+        let distance: u32 = value.0 >> 4;
+        let direction = match value.0 & 0xF {
+            0 => Direction::East,
+            1 => Direction::South,
+            2 => Direction::West,
+            3 => Direction::North,
+            other => {
+                return Err(format!(
+                    "Last digit of #{:06x} wasn't any of [0, 1, 2, 3] ({:x})",
+                    value.0, other
+                ))
+            }
+        };
+
+        Ok(Self {
+            distance,
+            direction,
+            rgb: value,
+        })
+    }
+}
+
+/// Turns one (non-empty) line of a dig plan into an instruction.
+trait InstructionDecoder {
+    fn decode(&self, line: &str) -> Result<DigInstruction, Box<dyn Error>>;
+
+    fn decode_plan(&self, plan: &str) -> Result<Vec<DigInstruction>, Box<dyn Error>> {
+        plan.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| self.decode(line))
+            .try_collect()
+    }
+}
+
+/// `R 6 (#70c710)`: direction and distance as written (part 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct DirectFields;
+
+impl InstructionDecoder for DirectFields {
+    fn decode(&self, line: &str) -> Result<DigInstruction, Box<dyn Error>> {
+        line.parse()
+    }
+}
+
+/// `R 6 (#70c710)`: distance and direction hidden in the color (part 2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct HexColor;
+
+impl InstructionDecoder for HexColor {
+    fn decode(&self, line: &str) -> Result<DigInstruction, Box<dyn Error>> {
+        Ok(DigInstruction::try_from(
+            line.parse::<DigInstruction>()?.rgb(),
+        )?)
+    }
+}
+
+/// `R,6` or `R,6,#70c710`: comma separated direction, distance and optional color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Csv;
+
+impl InstructionDecoder for Csv {
+    fn decode(&self, line: &str) -> Result<DigInstruction, Box<dyn Error>> {
+        let fields = line.split(',').map(str::trim).collect_vec();
+        let (direction, distance, rgb) = match fields[..] {
+            [direction, distance] => (direction, distance, RGBValue(0)),
+            [direction, distance, rgb] => (direction, distance, rgb.parse()?),
+            _ => {
+                return Err(format!(
+                    "Expected 2 or 3 comma separated fields in {:?} (found {})",
+                    line,
+                    fields.len()
+                )
+                .into())
+            }
+        };
+
+        let mut chars = direction.chars();
+        let direction = match (chars.next(), chars.next()) {
+            (Some(c @ ('U' | 'D' | 'L' | 'R')), None) => c.try_into()?,
+            _ => return Err(format!("Invalid direction {:?} in {:?}", direction, line).into()),
+        };
+
+        Ok(DigInstruction {
+            direction,
+            distance: distance.parse()?,
+            rgb,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Decoder {
+    /// direction and distance as written (part 1)
+    Direct,
+    /// distance and direction hidden in the color (part 2)
+    Hex,
+    /// comma separated direction, distance and optional color
+    Csv,
+}
+
+impl Decoder {
+    fn decoder(&self) -> &'static dyn InstructionDecoder {
+        match self {
+            Self::Direct => &DirectFields,
+            Self::Hex => &HexColor,
+            Self::Csv => &Csv,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+pub enum Algorithm {
+    /// shoelace formula + Pick's theorem on the polygon's vertices
+    #[default]
+    Shoelace,
+    /// split the plane into rectangles along the vertices' rows and columns and flood fill the outside
+    Rects,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<18>,
+    /// How the lagoon is measured
+    #[arg(long = "algo", value_enum, default_value_t)]
+    pub algorithm: Algorithm,
+    /// Render the (part 1) dig plan to this SVG file
+    #[arg(long, value_name = "PATH")]
+    pub svg: Option<PathBuf>,
+    /// Decode the plan a single way instead of solving both parts
+    #[arg(long, value_enum)]
+    pub decoder: Option<Decoder>,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+pub fn solve_decoder(
+    input: &InputSource,
+    decoder: Decoder,
+    options: &Options,
+) -> Result<u64, Box<dyn Error>> {
+    let input = input.read()?;
+    let instructions = decoder.decoder().decode_plan(&input)?;
+
+    if let Some(path) = options.svg.as_ref() {
+        render_svg(&instructions)?.write_to_file(path)?;
+        println!("Dig plan written to {}", path.display());
+    }
+
+    dug_out(&instructions, options.algorithm)
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let input = input.read()?;
+    let instructions = DirectFields.decode_plan(&input)?;
+
+    if let Some(path) = options.svg.as_ref() {
+        render_svg(&instructions)?.write_to_file(path)?;
+        println!("Dig plan written to {}", path.display());
+    }
+
+    if options.args.part.solves_part1() {
+        println!(
+            "Part 1 answer: {}",
+            dug_out(&instructions, options.algorithm)?
+        );
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    // Part 2 start
+    let instructions = HexColor.decode_plan(&input)?;
+
+    Ok(Some(dug_out(&instructions, options.algorithm)?))
+}
+
+fn dug_out(instructions: &[DigInstruction], algorithm: Algorithm) -> Result<u64, Box<dyn Error>> {
+    let points = read_ngon(instructions)?;
+    Ok(match algorithm {
+        Algorithm::Shoelace => polygon_area(&points, instructions)?,
+        Algorithm::Rects => {
+            let (rects_grid, segments) = rectangular_parts(&points);
+            let outside = get_outside(&rects_grid, &segments);
+            get_inside_area(&rects_grid, &outside)
+        }
+    })
+}
+
+/// Draws the filled lagoon with every trench segment stroked in the color of its instruction.
+fn render_svg(instructions: &[DigInstruction]) -> Result<Svg, Box<dyn Error>> {
+    let points = read_ngon(instructions)?
+        .into_iter()
+        .map(|point| (point.col, point.row))
+        .collect_vec();
+
+    let mut svg = Svg::fitting(&points, 2);
+    svg.polygon(&points, "#d0d0d0");
+    for ((from, to), instr) in points
+        .iter()
+        .copied()
+        .circular_tuple_windows()
+        .zip(instructions)
+    {
+        svg.line(from, to, &instr.rgb().to_string(), 1.0);
+    }
+
+    Ok(svg)
+}
+
+/// Instructions handled by a single rayon task when computing the polygon's vertices.
+const CHUNK_SIZE: usize = 1 << 14;
+
+fn read_ngon(data: &[DigInstruction]) -> Result<Vec<Point>, PlanError> {
+    // Parallel prefix sum: every chunk's total movement is computed independently (in i128, which
+    // cannot overflow), then each chunk writes its vertices starting from the sum of the previous chunks.
+    let chunk_moves = data
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            chunk.iter().fold((0, 0), |(r, c), instr| {
+                let (dr, dc) = instr.movement();
+                (r + dr, c + dc)
+            })
+        })
+        .collect::<Vec<_>>();
+    let chunk_starts = chunk_moves
+        .iter()
+        .scan((0_i128, 0_i128), |(r, c), &(dr, dc)| {
+            let start = (*r, *c);
+            (*r, *c) = (*r + dr, *c + dc);
+            Some(start)
+        })
+        .collect_vec();
+
+    let to_point =
+        |(r, c): (i128, i128)| Some(Point::new(i64::try_from(r).ok()?, i64::try_from(c).ok()?));
+    let mut pts = vec![Point::ORIGIN; data.len()];
+    let first_overflow = pts
+        .par_chunks_mut(CHUNK_SIZE)
+        .zip(data.par_chunks(CHUNK_SIZE))
+        .zip(chunk_starts.par_iter())
+        .enumerate()
+        .filter_map(|(chunk_index, ((pts, chunk), &(mut r, mut c)))| {
+            for (i, (pt, instr)) in pts.iter_mut().zip(chunk).enumerate() {
+                match to_point((r, c)) {
+                    Some(point) => *pt = point,
+                    // an out of range vertex means the previous instruction overflowed (never the first vertex)
+                    None => return Some(chunk_index * CHUNK_SIZE + i - 1),
+                }
+                let (dr, dc) = instr.movement();
+                (r, c) = (r + dr, c + dc);
+            }
+
+            None
+        })
+        .min();
+    if let Some(instruction) = first_overflow {
+        return Err(PlanError::CoordinateOverflow { instruction });
+    }
+
+    let end = chunk_starts
+        .last()
+        .zip(chunk_moves.last())
+        .map_or(Some(Point::ORIGIN), |((r, c), (dr, dc))| {
+            to_point((r + dr, c + dc))
+        })
+        .ok_or(PlanError::CoordinateOverflow {
+            instruction: data.len() - 1,
+        })?;
+    if end != Point::ORIGIN {
+        return Err(PlanError::NotClosed { end });
+    }
+
+    validate_polygon(&pts)?;
+    Ok(pts)
+}
+
+/// Checks that every segment of the closed polygon `pts` (segment `i` going from `pts[i]` to the next point,
+/// i.e. dug by instruction `i`) is axis-aligned and that no two segments meet except consecutive
+/// ones at their shared corner.
+///
+/// Sweeps over the rows, so it stays `O(n log n)` even for huge plans.
+fn validate_polygon(pts: &[Point]) -> Result<(), PlanError> {
+    let n = pts.len();
+    let adjacent = |i: usize, j: usize| (i + 1) % n == j || (j + 1) % n == i;
+
+    // (fixed coordinate, low end, high end, instruction)
+    let mut horizontals = vec![];
+    let mut verticals = vec![];
+    for (instruction, (&from, &to)) in pts.iter().circular_tuple_windows().enumerate() {
+        let segment = Segment::new(from, to);
+        if !segment.is_axis_aligned() {
+            return Err(PlanError::NotAxisAligned { instruction });
+        } else if segment.is_horizontal() {
+            horizontals.push((
+                from.row,
+                from.col.min(to.col),
+                from.col.max(to.col),
+                instruction,
+            ));
+        } else {
+            verticals.push((
+                from.col,
+                from.row.min(to.row),
+                from.row.max(to.row),
+                instruction,
+            ));
+        }
+    }
+
+    // Collinear segments on the same line may only share the corner between two consecutive instructions.
+    for segments in [&mut horizontals, &mut verticals] {
+        segments.sort_unstable();
+        let mut furthest: Option<(i64, i64, usize)> = None; // (line, high end, instruction)
+        for &(line, low, high, instruction) in segments.iter() {
+            if let Some((furthest_line, furthest_high, other)) = furthest {
+                if furthest_line == line
+                    && (low < furthest_high
+                        || (low == furthest_high && !adjacent(instruction, other)))
+                {
+                    return Err(PlanError::SelfIntersecting {
+                        first: other.min(instruction),
+                        second: other.max(instruction),
+                    });
+                }
+            }
+
+            if furthest.is_none_or(|(furthest_line, furthest_high, _)| {
+                furthest_line != line || high > furthest_high
+            }) {
+                furthest = Some((line, high, instruction));
+            }
+        }
+    }
+
+    // Perpendicular segments may only meet at the corner between two consecutive instructions.
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    enum Event {
+        Open {
+            col: i64,
+            instruction: usize,
+        },
+        Horizontal {
+            low: i64,
+            high: i64,
+            instruction: usize,
+        },
+        Close {
+            col: i64,
+            instruction: usize,
+        },
+    }
+
+    let mut events = verticals
+        .iter()
+        .flat_map(|&(col, low, high, instruction)| {
+            [
+                (low, Event::Open { col, instruction }),
+                (high, Event::Close { col, instruction }),
+            ]
+        })
+        .chain(horizontals.iter().map(|&(row, low, high, instruction)| {
+            (
+                row,
+                Event::Horizontal {
+                    low,
+                    high,
+                    instruction,
+                },
+            )
+        }))
+        .collect_vec();
+    events.sort_unstable();
+
+    let mut open = BTreeSet::new();
+    for (_, event) in events {
+        match event {
+            Event::Open { col, instruction } => {
+                open.insert((col, instruction));
+            }
+            Event::Close { col, instruction } => {
+                open.remove(&(col, instruction));
+            }
+            Event::Horizontal {
+                low,
+                high,
+                instruction,
+            } => {
+                if let Some(&(_, other)) = open
+                    .range((low, 0)..=(high, usize::MAX))
+                    .find(|&&(_, other)| !adjacent(instruction, other))
+                {
+                    return Err(PlanError::SelfIntersecting {
+                        first: other.min(instruction),
+                        second: other.max(instruction),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of cubes dug out by the loop, trench included.
+///
+/// The shoelace formula gives the area of the polygon going through the center of the trench cubes;
+/// by Pick's theorem it holds `area - boundary / 2 + 1` whole cubes, to which the `boundary` cubes
+/// of the trench itself are added.
+fn polygon_area(pts: &[Point], data: &[DigInstruction]) -> Result<u64, PlanError> {
+    let double_area = (0..pts.len())
+        .into_par_iter()
+        .map(|i| Some(pts[i].cross(&pts[(i + 1) % pts.len()])))
+        .try_reduce(|| 0, |a, b| a.checked_add(b))
+        .and_then(|double_area| u64::try_from(double_area.unsigned_abs()).ok())
+        .ok_or(PlanError::AreaOverflow)?;
+    let boundary = data
+        .par_iter()
+        .map(|instr| Some(instr.distance() as u64))
+        .try_reduce(|| 0, |a, b| a.checked_add(b))
+        .ok_or(PlanError::AreaOverflow)?;
+    Ok(double_area
+        .checked_add(boundary)
+        .ok_or(PlanError::AreaOverflow)?
+        / 2
+        + 1)
+}
+
+/// Split the ground into (big) rectangles and cut polygon segments on border accordingly.
+fn rectangular_parts(pts: &[Point]) -> (RectsGrid, HashSet<Segment>) {
+    // Both `rs` and `cs` are reasonably small, leading to a not too big 2D grid.
+    let mut rs = pts
+        .iter()
+        .map(|point| point.row)
+        .sorted()
+        .dedup()
+        .collect_vec();
+    let mut cs = pts
+        .iter()
+        .map(|point| point.col)
+        .sorted()
+        .dedup()
+        .collect_vec();
+    // Add rects on the outside.
+    rs.insert(0, rs[0] - 1);
+    rs.push(*rs.last().expect("Empty data") + 1);
+    cs.insert(0, cs[0] - 1);
+    cs.push(*cs.last().expect("Empty data") + 1);
+    let segments = pts
+        .iter()
+        .copied()
+        .circular_tuple_windows()
+        .flat_map(|(from, to)| {
+            let (mut r0, mut c0, mut r1, mut c1) = (from.row, from.col, to.row, to.col);
+            debug_assert!(Segment::new(from, to).is_axis_aligned(), "Diagonal?!");
+            if r0 == r1 {
+                if c0 > c1 {
+                    (c0, c1) = (c1, c0);
+                }
+                cs.iter()
+                    .copied()
+                    .filter(|&c| c0 <= c && c <= c1)
+                    .tuple_windows()
+                    .map(|(u, v)| Segment::new(Point::new(r0, u), Point::new(r0, v)))
+                    .collect_vec()
+            } else {
+                if r0 > r1 {
+                    (r0, r1) = (r1, r0);
+                }
+                rs.iter()
+                    .copied()
+                    .filter(|&r| r0 <= r && r <= r1)
+                    .tuple_windows()
+                    .map(|(u, v)| Segment::new(Point::new(u, c0), Point::new(v, c0)))
+                    .collect_vec()
+            }
+        })
+        .collect();
+    let rects_grid = rs
+        .iter()
+        .tuple_windows()
+        .map(|(r0, r1)| {
+            cs.iter()
+                .tuple_windows()
+                .map(|(c0, c1)| Rect([*r0, *r1, *c0, *c1]))
+                .collect()
+        })
+        .collect();
+    (rects_grid, segments)
+}
+
+impl Rect {
+    const fn border(&self, dir: Direction) -> Segment {
+        let a = &self.0;
+        match dir {
+            Direction::North => Segment::new(Point::new(a[0], a[2]), Point::new(a[0], a[3])),
+            Direction::South => Segment::new(Point::new(a[1], a[2]), Point::new(a[1], a[3])),
+            Direction::West => Segment::new(Point::new(a[0], a[2]), Point::new(a[1], a[2])),
+            Direction::East => Segment::new(Point::new(a[0], a[3]), Point::new(a[1], a[3])),
+        }
+    }
+
+    fn area(&self) -> u64 {
+        let a = &self.0;
+        u64::try_from(a[1] - a[0]).expect("Positive length")
+            * u64::try_from(a[3] - a[2]).expect("Positive length")
+    }
+}
+
+fn get_outside(rects_grid: &RectsGrid, segments: &HashSet<Segment>) -> HashSet<(usize, usize)> {
+    let nrows = rects_grid.len();
+    let ncols = rects_grid[0].len();
+    // Since I previously added some space around the polygon,
+    // (0, 0) is outside and all the outside is accessible from it.
+    let mut stack = vec![(0usize, 0usize)];
+    let mut outside = HashSet::new();
+    while let Some((r, c)) = stack.pop() {
+        if !outside.insert((r, c)) {
+            continue; // Visited already.
+        }
+        for dir in Direction::ALL {
+            let next_loc = match dir {
+                Direction::North => r.checked_sub(1).map(|i| (i, c)),
+                Direction::South => (r + 1 < nrows).then_some((r + 1, c)),
+                Direction::West => c.checked_sub(1).map(|i| (r, i)),
+                Direction::East => (c + 1 < ncols).then_some((r, c + 1)),
+            };
+            let Some(loc) = next_loc else {
+                continue; // Outside the grid.
+            };
+            if segments.contains(&rects_grid[r][c].border(dir)) {
+                continue; // Inside the digged zone.
+            }
+            stack.push(loc);
+        }
+    }
+    outside
+}
+
+fn get_inside_area(rects_grid: &RectsGrid, outside: &HashSet<(usize, usize)>) -> u64 {
+    let nrows = rects_grid.len();
+    let ncols = rects_grid[0].len();
+    let mut total = 0;
+    for (r, c) in itertools::iproduct!(0..nrows, 0..ncols) {
+        if outside.contains(&(r, c)) {
+            continue;
+        }
+        let rect = &rects_grid[r][c];
+        total += rect.area();
+        let mut south_east_corner: u8 = 0;
+        for (dir, (r0, c0)) in [
+            (Direction::South, (r + 1, c)),
+            (Direction::East, (r, c + 1)),
+        ] {
+            if r0 < nrows && c0 < ncols && outside.contains(&(r0, c0)) {
+                total += rect.border(dir).len();
+                if dir == Direction::East && !outside.contains(&(r - 1, c + 1)) {
+                    total -= 1; // Counted twice.
+                }
+                south_east_corner += 1;
+            }
+        }
+        if south_east_corner == 2 && outside.contains(&(r + 1, c + 1)) {
+            total += 1; // Not counted yet.
+        }
+    }
+    total
+}
+
+/// The dig plan, read the way of each part.
+#[derive(Debug, Clone)]
+pub struct Day18 {
+    direct: Vec<DigInstruction>,
+    hex: Vec<DigInstruction>,
+}
+
+aoc_utils::register_day!(
+    18,
+    Day18,
+    examples = [Example::both(include_str!("../example"), 62, 952408144115)]
+);
+
+impl Solution for Day18 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            direct: DirectFields.decode_plan(input)?,
+            hex: HexColor.decode_plan(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(dug_out(&self.direct, Algorithm::default())?.into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(dug_out(&self.hex, Algorithm::default())?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithms_agree_on_the_example() {
+        let plan = include_str!("../example");
+        for (decoder, expected) in [(Decoder::Direct, 62), (Decoder::Hex, 952408144115)] {
+            let instructions = decoder.decoder().decode_plan(plan).unwrap();
+            let shoelace = dug_out(&instructions, Algorithm::Shoelace).unwrap();
+            let rects = dug_out(&instructions, Algorithm::Rects).unwrap();
+            assert_eq!(shoelace, expected, "shoelace with {:?}", decoder);
+            assert_eq!(rects, shoelace, "rects with {:?}", decoder);
+        }
+    }
+
+    #[test]
+    fn hex_direction_out_of_range() {
+        assert!(HexColor.decode("R 6 (#70c714)").is_err());
+        assert_eq!(
+            HexColor.decode("R 6 (#70c713)").unwrap().direction,
+            Direction::North
+        );
+    }
+}
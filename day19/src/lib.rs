@@ -0,0 +1,1455 @@
+use aoc_utils::{
+    dot::{Attributes, ToDot},
+    error::{parse_field, parse_lines, AocError},
+    input::{Args, InputSource},
+    interval::Interval,
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::Parser;
+use core::fmt;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{
+    array,
+    collections::{HashMap, HashSet},
+    error::Error,
+    io::{self, BufRead},
+    ops::{Index, IndexMut},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::trace;
+
+/// How many rating categories an input may use, one const generic instantiation is compiled for each count
+const MAX_CATEGORIES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Category {
+    index: u8,
+    letter: char,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter)
+    }
+}
+
+fn single_letter(category: &str) -> Result<char, AocError> {
+    let mut chars = category.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => Ok(letter),
+        _ => Err(AocError::parse(
+            category,
+            format!("Category should be 1 character, was {:?}", category),
+        )),
+    }
+}
+
+/// The rating letters used by an input, in the order the first part lists them
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Categories {
+    letters: Vec<char>,
+}
+
+impl Default for Categories {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            letters: vec!['x', 'm', 'a', 's'],
+        }
+    }
+}
+
+impl Categories {
+    /// Reads the category letters from a part line such as `{x=787,m=2655,a=1222,s=2876}`
+    pub(crate) fn from_part_line(line: &str) -> Result<Self, AocError> {
+        let mut letters = vec![];
+        for rating in line
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .split(',')
+        {
+            let (letter_text, _) = rating.split_once('=').ok_or_else(|| {
+                AocError::parse(
+                    rating,
+                    format!("Expected {:?} to be of the form \"c=value\"", rating),
+                )
+            })?;
+            let letter = single_letter(letter_text)?;
+            if letters.contains(&letter) {
+                return Err(AocError::parse(
+                    letter_text,
+                    format!("Category {:?} is listed twice in {:?}", letter, line),
+                ));
+            }
+
+            letters.push(letter);
+        }
+
+        if letters.len() > MAX_CATEGORIES {
+            return Err(AocError::parse(
+                line,
+                format!(
+                    "At most {} categories are supported, found {}",
+                    MAX_CATEGORIES,
+                    letters.len()
+                ),
+            ));
+        }
+
+        Ok(Self { letters })
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.letters.len()
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, letter_text: &str) -> Result<Category, AocError> {
+        let letter = single_letter(letter_text)?;
+        self.letters
+            .iter()
+            .position(|&known| known == letter)
+            .map(|index| Category {
+                index: index as u8,
+                letter,
+            })
+            .ok_or_else(|| {
+                AocError::parse(
+                    letter_text,
+                    format!("Category was not any of {:?} ({:?})", self.letters, letter),
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct WorkflowConditionDetails {
+    category: Category,
+    compare_value: u32,
+}
+
+impl WorkflowConditionDetails {
+    #[inline]
+    /// Splits `part` into the values of `category` strictly below `bound` and the ones at or above it
+    pub(crate) fn split_below<const N: usize>(
+        &self,
+        part: PartRatingsRange<N>,
+        bound: u32,
+    ) -> (PartRatingsRange<N>, PartRatingsRange<N>) {
+        let (below_range, above_range) = part[self.category].split_at(bound);
+        let mut below = part.clone();
+        let mut above = part;
+        below[self.category] = below_range;
+        above[self.category] = above_range;
+        (below, above)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum WorkflowCondition {
+    Greater(WorkflowConditionDetails),
+    GreaterOrEqual(WorkflowConditionDetails),
+    Lesser(WorkflowConditionDetails),
+    LesserOrEqual(WorkflowConditionDetails),
+    NotEqual(WorkflowConditionDetails),
+    AlwaysTrue,
+}
+
+impl WorkflowCondition {
+    pub(crate) fn parse(s: &str, categories: &Categories) -> Result<Self, AocError> {
+        type Constructor = fn(WorkflowConditionDetails) -> WorkflowCondition;
+        // two character operators first so that "<=" is not read as "<" followed by "=..."
+        const OPERATORS: [(&str, Constructor); 5] = [
+            ("<=", WorkflowCondition::LesserOrEqual),
+            (">=", WorkflowCondition::GreaterOrEqual),
+            ("!=", WorkflowCondition::NotEqual),
+            ("<", WorkflowCondition::Lesser),
+            (">", WorkflowCondition::Greater),
+        ];
+
+        if s.is_empty() {
+            return Ok(Self::AlwaysTrue);
+        }
+
+        for (operator, constructor) in OPERATORS {
+            if let Some((category, compare_value)) = s.split_once(operator) {
+                return Ok(constructor(WorkflowConditionDetails {
+                    category: categories.get(category)?,
+                    compare_value: parse_field(compare_value)?,
+                }));
+            }
+        }
+
+        Err(AocError::parse(
+            s,
+            format!("{:?} could not be parsed into a WorkflowCondition", s),
+        ))
+    }
+}
+
+impl fmt::Display for WorkflowCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, details) = match self {
+            Self::Greater(details) => (">", details),
+            Self::GreaterOrEqual(details) => (">=", details),
+            Self::Lesser(details) => ("<", details),
+            Self::LesserOrEqual(details) => ("<=", details),
+            Self::NotEqual(details) => ("!=", details),
+            Self::AlwaysTrue => return write!(f, "else"),
+        };
+
+        write!(
+            f,
+            "{}{}{}",
+            details.category, operator, details.compare_value
+        )
+    }
+}
+
+impl WorkflowCondition {
+    #[inline]
+    pub(crate) fn is_condition_true<const N: usize>(&self, part: &PartRatings<N>) -> bool {
+        match self {
+            Self::Greater(details) => part[details.category] > details.compare_value,
+            Self::GreaterOrEqual(details) => part[details.category] >= details.compare_value,
+            Self::Lesser(details) => part[details.category] < details.compare_value,
+            Self::LesserOrEqual(details) => part[details.category] <= details.compare_value,
+            Self::NotEqual(details) => part[details.category] != details.compare_value,
+            Self::AlwaysTrue => true,
+        }
+    }
+
+    #[inline]
+    /// The first two values are mapped to this condition (`!=` maps both sides of the excluded value,
+    /// every other condition leaves the second one empty), the last one is not
+    pub(crate) fn map_range<const N: usize>(
+        &self,
+        part: PartRatingsRange<N>,
+    ) -> ([PartRatingsRange<N>; 2], PartRatingsRange<N>) {
+        let empty = PartRatingsRange::empty();
+        // no range goes up to u32::MAX, so nothing is above a bound saturating there
+        let after = |details: &WorkflowConditionDetails| details.compare_value.saturating_add(1);
+
+        match self {
+            Self::AlwaysTrue => ([part, empty.clone()], empty),
+            Self::Greater(details) => {
+                let (below, above) = details.split_below(part, after(details));
+                ([above, empty], below)
+            }
+            Self::GreaterOrEqual(details) => {
+                let (below, above) = details.split_below(part, details.compare_value);
+                ([above, empty], below)
+            }
+            Self::Lesser(details) => {
+                let (below, above) = details.split_below(part, details.compare_value);
+                ([below, empty], above)
+            }
+            Self::LesserOrEqual(details) => {
+                let (below, above) = details.split_below(part, after(details));
+                ([below, empty], above)
+            }
+            Self::NotEqual(details) => {
+                let (below, rest) = details.split_below(part, details.compare_value);
+                let (equal, above) = details.split_below(rest, after(details));
+                ([below, above], equal)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct WorkflowPart<'s> {
+    condition: WorkflowCondition,
+    if_true: &'s str,
+}
+
+impl<'s> WorkflowPart<'s> {
+    #[inline]
+    pub(crate) fn is_condition_true<const N: usize>(&self, part: &PartRatings<N>) -> bool {
+        self.condition.is_condition_true(part)
+    }
+
+    #[inline]
+    pub(crate) const fn get_target_flow(&self) -> &'s str {
+        self.if_true
+    }
+
+    #[inline]
+    /// The first values are mapped to this workflow part, the last one is not
+    pub(crate) fn map_range<const N: usize>(
+        &self,
+        part: PartRatingsRange<N>,
+    ) -> ([PartRatingsRange<N>; 2], PartRatingsRange<N>) {
+        self.condition.map_range(part)
+    }
+}
+
+impl<'s> WorkflowPart<'s> {
+    pub(crate) fn parse(s: &'s str, categories: &Categories) -> Result<Self, AocError> {
+        if let Some((condition, if_true)) = s.split_once(':') {
+            Ok(Self {
+                condition: WorkflowCondition::parse(condition, categories)?,
+                if_true,
+            })
+        } else {
+            Ok(Self {
+                condition: WorkflowCondition::AlwaysTrue,
+                if_true: s,
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Workflow<'s> {
+    workflow_name: &'s str,
+    conditions: Box<[WorkflowPart<'s>]>,
+}
+
+impl<'s> Workflow<'s> {
+    #[inline]
+    pub(crate) fn execute_workflow<const N: usize>(&self, part: &PartRatings<N>) -> &'s str {
+        for flow in self.conditions.iter() {
+            if flow.is_condition_true(part) {
+                return flow.get_target_flow();
+            }
+        }
+
+        panic!("Workflow::execute_workflow(): Unreachable");
+    }
+
+    #[inline]
+    pub(crate) fn execute_on_range<const N: usize>(
+        &self,
+        part: PartRatingsRange<N>,
+    ) -> Vec<(&'s str, PartRatingsRange<N>)> {
+        let mut result = vec![];
+        let mut current = part;
+        for flow in self.conditions.iter() {
+            let (mapped, non_mapped) = flow.map_range(current);
+            trace!(rule = %flow.condition, ?mapped, rest = ?non_mapped, "range split");
+            for mapped in mapped {
+                if !mapped.is_empty() {
+                    result.push((flow.get_target_flow(), mapped));
+                }
+            }
+
+            current = non_mapped;
+            if current.is_empty() {
+                break;
+            }
+        }
+
+        if !current.is_empty() {
+            panic!("Unreachable");
+        }
+
+        result
+    }
+}
+
+impl<'s> Workflow<'s> {
+    pub(crate) fn parse(value: &'s str, categories: &Categories) -> Result<Self, AocError> {
+        let value = value.strip_suffix('}').ok_or_else(|| {
+            AocError::parse(value, "Workflow::parse(): value did not end with '}'")
+        })?;
+        let (workflow_name, conditions) = value.split_once('{').ok_or_else(|| {
+            AocError::parse(
+                value,
+                format!("Workflow::parse(): {:?} could nto be splut at '{{'", value),
+            )
+        })?;
+
+        Ok(Self {
+            workflow_name,
+            conditions: conditions
+                .split(',')
+                .map(|part| WorkflowPart::parse(part, categories))
+                .try_collect()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PartRatings<const N: usize> {
+    ratings: [u32; N],
+}
+
+impl<const N: usize> PartRatings<N> {
+    #[inline]
+    pub(crate) fn sum(&self) -> u32 {
+        self.ratings.iter().sum()
+    }
+
+    #[inline]
+    pub(crate) fn is_accepted(&self, workflows: &HashMap<&str, Workflow<'_>>) -> bool {
+        trace!(part = ?self.ratings, "sorting a part");
+        let mut current_flow = "in";
+        loop {
+            trace!(workflow = current_flow);
+            if current_flow == "A" {
+                break true;
+            }
+
+            if current_flow == "R" {
+                break false;
+            }
+
+            // validate_workflows() made sure every target exists
+            let workflow = &workflows[current_flow];
+
+            current_flow = workflow.execute_workflow(self);
+        }
+    }
+
+    /// Parses a part line, which must rate every category of `categories` exactly once (in any order)
+    pub(crate) fn parse(s: &str, categories: &Categories) -> Result<Self, AocError> {
+        let mut ratings = [None; N];
+        for rating in s.trim_start_matches('{').trim_end_matches('}').split(',') {
+            let (letter, value) = rating.split_once('=').ok_or_else(|| {
+                AocError::parse(
+                    rating,
+                    format!("Expected {:?} to be of the form \"c=value\"", rating),
+                )
+            })?;
+            let category = categories.get(letter)?;
+            if ratings[category.index as usize]
+                .replace(parse_field(value)?)
+                .is_some()
+            {
+                return Err(AocError::parse(
+                    letter,
+                    format!("Category {} is rated twice in {:?}", category, s),
+                ));
+            }
+        }
+
+        let mut result = [0; N];
+        for (index, rating) in ratings.into_iter().enumerate() {
+            result[index] = rating.ok_or_else(|| {
+                AocError::parse(
+                    s,
+                    format!(
+                        "Category {:?} is not rated in {:?}",
+                        categories.letters[index], s
+                    ),
+                )
+            })?;
+        }
+
+        Ok(Self { ratings: result })
+    }
+}
+
+impl<const N: usize> Index<Category> for PartRatings<N> {
+    type Output = u32;
+
+    fn index(&self, index: Category) -> &Self::Output {
+        &self.ratings[index.index as usize]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PartRatingsRange<const N: usize> {
+    ranges: [Interval<u32>; N],
+}
+
+impl<const N: usize> Index<Category> for PartRatingsRange<N> {
+    type Output = Interval<u32>;
+
+    fn index(&self, index: Category) -> &Self::Output {
+        &self.ranges[index.index as usize]
+    }
+}
+
+impl<const N: usize> IndexMut<Category> for PartRatingsRange<N> {
+    fn index_mut(&mut self, index: Category) -> &mut Self::Output {
+        &mut self.ranges[index.index as usize]
+    }
+}
+
+impl<const N: usize> Default for PartRatingsRange<N> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            ranges: array::from_fn(|_| Interval::new(1, 4001)),
+        }
+    }
+}
+
+impl<const N: usize> PartRatingsRange<N> {
+    #[inline]
+    pub(crate) fn empty() -> Self {
+        Self {
+            ranges: array::from_fn(|_| Interval::default()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ranges.iter().any(|range| range.is_empty())
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, part: &PartRatings<N>) -> bool {
+        self.ranges
+            .iter()
+            .zip(part.ratings.iter())
+            .all(|(range, &rating)| range.contains(rating))
+    }
+
+    /// Total rating of the lowest rated part in this (non empty) range
+    #[inline]
+    pub(crate) fn min_rating_sum(&self) -> u64 {
+        self.ranges.iter().map(|range| range.start as u64).sum()
+    }
+
+    /// Total rating of the highest rated part in this (non empty) range
+    #[inline]
+    pub(crate) fn max_rating_sum(&self) -> u64 {
+        self.ranges.iter().map(|range| range.end as u64 - 1).sum()
+    }
+
+    #[inline]
+    pub(crate) fn count_values(&self) -> u64 {
+        self.ranges.iter().map(|range| range.len() as u64).product()
+    }
+
+    #[inline]
+    pub(crate) fn pass_through_workflow(
+        self,
+        workflows: &HashMap<&str, Workflow<'_>>,
+    ) -> Vec<PartRatingsRange<N>> {
+        let mut result = vec![];
+        let mut stack = vec![("in", self)];
+        while let Some((workflow, range)) = stack.pop() {
+            if workflow == "A" {
+                result.push(range);
+                continue;
+            }
+
+            if workflow == "R" {
+                continue;
+            }
+
+            // validate_workflows() made sure every target exists
+            let workflow = &workflows[workflow];
+
+            stack.extend(workflow.execute_on_range(range));
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum WorkflowError {
+    /// two workflows share this name
+    Duplicate { workflow: String },
+    /// there is no `in` workflow to start from
+    MissingStart,
+    /// rule number `rule` (counting from 1) of `workflow` sends parts to a workflow that does not exist
+    UnknownTarget {
+        workflow: String,
+        rule: usize,
+        target: String,
+    },
+    /// these workflows send parts to each other in a loop, the last one back to the first
+    Cycle { workflows: Vec<String> },
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate { workflow } => {
+                write!(f, "The workflow {:?} is defined more than once", workflow)
+            }
+            Self::MissingStart => write!(f, "There is no \"in\" workflow"),
+            Self::UnknownTarget {
+                workflow,
+                rule,
+                target,
+            } => write!(
+                f,
+                "Rule #{} of workflow {:?} sends to {:?}, which does not exist",
+                rule, workflow, target
+            ),
+            Self::Cycle { workflows } => write!(
+                f,
+                "The workflows loop back on themselves: {} -> {}",
+                workflows.join(" -> "),
+                workflows[0]
+            ),
+        }
+    }
+}
+
+impl Error for WorkflowError {}
+
+/// Checks that running parts through `workflows` cannot fail: `in` exists,
+/// every rule sends to `A`, `R` or an existing workflow, and no part can loop forever.
+fn validate_workflows(workflows: &HashMap<&str, Workflow<'_>>) -> Result<(), WorkflowError> {
+    if !workflows.contains_key("in") {
+        return Err(WorkflowError::MissingStart);
+    }
+
+    let names = workflows.keys().copied().sorted().collect_vec();
+    for &name in names.iter() {
+        for (index, part) in workflows[name].conditions.iter().enumerate() {
+            let target = part.get_target_flow();
+            if target != "A" && target != "R" && !workflows.contains_key(target) {
+                return Err(WorkflowError::UnknownTarget {
+                    workflow: name.to_owned(),
+                    rule: index + 1,
+                    target: target.to_owned(),
+                });
+            }
+        }
+    }
+
+    // depth first search, a target found on the current path closes a cycle
+    let mut finished = HashSet::new();
+    for &root in names.iter() {
+        if finished.contains(root) {
+            continue;
+        }
+
+        let mut path = vec![root];
+        let mut stack = vec![(root, 0)];
+        while let Some((name, next_rule)) = stack.pop() {
+            let Some(part) = workflows[name].conditions.get(next_rule) else {
+                finished.insert(name);
+                path.pop();
+                continue;
+            };
+
+            stack.push((name, next_rule + 1));
+            let target = part.get_target_flow();
+            if target == "A" || target == "R" || finished.contains(target) {
+                continue;
+            }
+
+            if let Some(position) = path.iter().position(|&on_path| on_path == target) {
+                return Err(WorkflowError::Cycle {
+                    workflows: path[position..]
+                        .iter()
+                        .map(|&name| name.to_owned())
+                        .collect(),
+                });
+            }
+
+            path.push(target);
+            stack.push((target, 0));
+        }
+    }
+
+    Ok(())
+}
+
+/// Simplifies the workflows without changing which parts get accepted:
+/// - rules following an unconditional one are dropped (they can never be reached),
+/// - a workflow whose every rule sends to the same destination is replaced by that destination,
+/// - workflows that cannot be reached from `in` are deleted.
+///
+/// Returns the simplified workflows along with the number of rules eliminated.
+fn optimize_workflows<'s>(
+    workflows: &HashMap<&'s str, Workflow<'s>>,
+) -> (HashMap<&'s str, Workflow<'s>>, usize) {
+    let rule_count = |workflows: &HashMap<&'s str, Workflow<'s>>| -> usize {
+        workflows
+            .values()
+            .map(|workflow| workflow.conditions.len())
+            .sum()
+    };
+
+    let mut optimized: HashMap<&'s str, Workflow<'s>> = workflows
+        .iter()
+        .map(|(&name, workflow)| {
+            let end = workflow
+                .conditions
+                .iter()
+                .position(|part| part.condition == WorkflowCondition::AlwaysTrue)
+                .map_or(workflow.conditions.len(), |index| index + 1);
+            (
+                name,
+                Workflow {
+                    workflow_name: name,
+                    conditions: workflow.conditions[..end].into(),
+                },
+            )
+        })
+        .collect();
+
+    // merging a workflow may make the ones pointing to it uniform as well, so repeat until stable
+    loop {
+        let merged: HashMap<&'s str, &'s str> = optimized
+            .values()
+            .filter(|workflow| workflow.workflow_name != "in")
+            .filter_map(|workflow| {
+                let (first, rest) = workflow.conditions.split_first()?;
+                let target = first.get_target_flow();
+                (target != workflow.workflow_name
+                    && rest.iter().all(|part| part.get_target_flow() == target))
+                .then_some((workflow.workflow_name, target))
+            })
+            .collect();
+
+        // a chain `a -> b -> c` is merged one link per round, so `a` never ends up pointing to a removed workflow
+        let merged: HashMap<&'s str, &'s str> = merged
+            .iter()
+            .filter(|(_, target)| !merged.contains_key(*target))
+            .map(|(&name, &target)| (name, target))
+            .collect();
+
+        if merged.is_empty() {
+            break;
+        }
+
+        optimized.retain(|name, _| !merged.contains_key(name));
+        for workflow in optimized.values_mut() {
+            for part in workflow.conditions.iter_mut() {
+                if let Some(&target) = merged.get(part.if_true) {
+                    part.if_true = target;
+                }
+            }
+        }
+    }
+
+    let mut reachable = HashSet::from(["in"]);
+    let mut stack = vec!["in"];
+    while let Some(name) = stack.pop() {
+        if let Some(workflow) = optimized.get(name) {
+            for part in workflow.conditions.iter() {
+                if reachable.insert(part.get_target_flow()) {
+                    stack.push(part.get_target_flow());
+                }
+            }
+        }
+    }
+
+    optimized.retain(|name, _| reachable.contains(name));
+
+    let eliminated = rule_count(workflows) - rule_count(&optimized);
+    (optimized, eliminated)
+}
+
+/// Sums the ratings of the parts `is_accepted` lets through (part 1)
+fn accepted_ratings<const N: usize>(
+    parts: &[PartRatings<N>],
+    is_accepted: impl Fn(&PartRatings<N>) -> bool + Sync,
+) -> u64 {
+    parts
+        .par_iter()
+        .filter(|part| is_accepted(part))
+        .map(|part| part.sum() as u64)
+        .sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(u32),
+}
+
+/// The workflows with their names interned to indices and all their rules stored back to back,
+/// so going from one workflow to the next is an index instead of hashing its name
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CompiledWorkflows {
+    rules: Vec<(WorkflowCondition, Target)>,
+    /// the rules of workflow `id` are `rules[bounds[id].0..bounds[id].1]`
+    bounds: Vec<(usize, usize)>,
+    start: Target,
+}
+
+impl CompiledWorkflows {
+    /// Expects workflows that passed validate_workflows()
+    pub(crate) fn compile(workflows: &HashMap<&str, Workflow<'_>>) -> Self {
+        let names = workflows.keys().copied().sorted().collect_vec();
+        let ids: HashMap<&str, u32> = names
+            .iter()
+            .enumerate()
+            .map(|(id, &name)| (name, id as u32))
+            .collect();
+        let target = |name: &str| match name {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            name => Target::Workflow(ids[name]),
+        };
+
+        let mut rules = vec![];
+        let mut bounds = Vec::with_capacity(names.len());
+        for name in names {
+            let first = rules.len();
+            rules.extend(
+                workflows[name]
+                    .conditions
+                    .iter()
+                    .map(|part| (part.condition, target(part.get_target_flow()))),
+            );
+            bounds.push((first, rules.len()));
+        }
+
+        Self {
+            rules,
+            bounds,
+            start: target("in"),
+        }
+    }
+
+    #[inline]
+    fn rules(&self, id: u32) -> &[(WorkflowCondition, Target)] {
+        let (first, end) = self.bounds[id as usize];
+        &self.rules[first..end]
+    }
+
+    #[inline]
+    pub(crate) fn is_accepted<const N: usize>(&self, part: &PartRatings<N>) -> bool {
+        let mut current = self.start;
+        loop {
+            match current {
+                Target::Accept => break true,
+                Target::Reject => break false,
+                Target::Workflow(id) => {
+                    current = self
+                        .rules(id)
+                        .iter()
+                        .find(|(condition, _)| condition.is_condition_true(part))
+                        .expect("CompiledWorkflows::is_accepted(): Unreachable")
+                        .1;
+                }
+            }
+        }
+    }
+
+    /// The parts of `range` that get accepted, as disjoint non empty ranges
+    pub(crate) fn accepted_ranges<const N: usize>(
+        &self,
+        range: PartRatingsRange<N>,
+    ) -> Vec<PartRatingsRange<N>> {
+        let mut result = vec![];
+        let mut stack = vec![(self.start, range)];
+        while let Some((target, mut range)) = stack.pop() {
+            let id = match target {
+                Target::Accept => {
+                    result.push(range);
+                    continue;
+                }
+                Target::Reject => continue,
+                Target::Workflow(id) => id,
+            };
+
+            for &(condition, target) in self.rules(id) {
+                let (mapped, non_mapped) = condition.map_range(range);
+                trace!(rule = %condition, ?mapped, rest = ?non_mapped, "range split");
+                stack.extend(
+                    mapped
+                        .into_iter()
+                        .filter(|mapped| !mapped.is_empty())
+                        .map(|mapped| (target, mapped)),
+                );
+
+                range = non_mapped;
+                if range.is_empty() {
+                    break;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Every rating combination the workflows accept, as disjoint non empty ranges,
+/// so questions about accepted parts no longer need to go through the workflows
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AcceptedRegion<const N: usize> {
+    ranges: Vec<PartRatingsRange<N>>,
+}
+
+impl<const N: usize> AcceptedRegion<N> {
+    #[inline]
+    pub(crate) fn from_workflows(workflows: &HashMap<&str, Workflow<'_>>) -> Self {
+        Self {
+            ranges: PartRatingsRange::default().pass_through_workflow(workflows),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_compiled(workflows: &CompiledWorkflows) -> Self {
+        Self {
+            ranges: workflows.accepted_ranges(PartRatingsRange::default()),
+        }
+    }
+
+    /// How many rating combinations get accepted (part 2)
+    #[inline]
+    pub(crate) fn combinations(&self) -> u64 {
+        self.ranges.iter().map(|range| range.count_values()).sum()
+    }
+
+    #[inline]
+    pub(crate) fn is_accepted(&self, part: &PartRatings<N>) -> bool {
+        self.ranges.iter().any(|range| range.contains(part))
+    }
+
+    /// The lowest total rating of any accepted part, `None` if nothing gets accepted
+    #[inline]
+    pub(crate) fn min_rating_sum(&self) -> Option<u64> {
+        self.ranges.iter().map(|range| range.min_rating_sum()).min()
+    }
+
+    /// The highest total rating of any accepted part, `None` if nothing gets accepted
+    #[inline]
+    pub(crate) fn max_rating_sum(&self) -> Option<u64> {
+        self.ranges.iter().map(|range| range.max_rating_sum()).max()
+    }
+}
+
+/// The routing graph: one node per workflow plus the `A` and `R` outcomes, one edge per rule labeled with its
+/// condition (and its position in the workflow).
+struct RoutingGraph<'w, 's>(&'w HashMap<&'s str, Workflow<'s>>);
+
+impl<'w, 's> ToDot for RoutingGraph<'w, 's> {
+    type Node = &'s str;
+    /// the position of the rule in its workflow, and its condition
+    type Edge = (usize, &'w WorkflowCondition);
+
+    fn nodes(&self) -> impl Iterator<Item = &'s str> {
+        ["in", "A", "R"]
+            .into_iter()
+            .chain(self.0.keys().copied().filter(|&name| name != "in").sorted())
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&'s str, &'s str, Self::Edge)> {
+        self.0.keys().sorted().flat_map(|&name| {
+            self.0[name]
+                .conditions
+                .iter()
+                .enumerate()
+                .map(move |(index, part)| {
+                    (name, part.get_target_flow(), (index + 1, &part.condition))
+                })
+        })
+    }
+
+    fn node_id(&self, name: &&'s str) -> String {
+        name.to_string()
+    }
+
+    fn node_attributes(&self, &name: &&'s str) -> Attributes {
+        let attributes: &[(&'static str, &str)] = match name {
+            "in" => &[("shape", "doublecircle")],
+            "A" => &[
+                ("shape", "box"),
+                ("style", "filled"),
+                ("fillcolor", "palegreen"),
+            ],
+            "R" => &[
+                ("shape", "box"),
+                ("style", "filled"),
+                ("fillcolor", "lightcoral"),
+            ],
+            _ => &[],
+        };
+        attributes
+            .iter()
+            .map(|&(name, value)| (name, value.to_owned()))
+            .collect()
+    }
+
+    fn edge_label(
+        &self,
+        _from: &&'s str,
+        _to: &&'s str,
+        (index, condition): &Self::Edge,
+    ) -> Option<String> {
+        Some(format!("{}: {}", index, condition))
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<19>,
+    /// Write the workflows to this file in the DOT format
+    #[arg(long, value_name = "PATH")]
+    emit_dot: Option<PathBuf>,
+    /// Check a part against the accepted region along with part 2, e.g. `{x=787,m=2655,a=1222,s=2876}`
+    #[arg(long = "is-accepted", value_name = "PART")]
+    queries: Vec<String>,
+    /// Also evaluate with the string-keyed optimized and original workflows, checking they agree with the
+    /// compiled ones and timing the compiled evaluator against the string-keyed one
+    #[arg(long)]
+    cross_check: bool,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+/// How many part lines are read (and then evaluated in parallel) at once
+const BATCH_SIZE: usize = 1 << 16;
+
+/// The original and optimized workflows, still keyed by name, to check the compiled ones against
+/// (`--cross-check`).
+#[derive(Debug, Clone, Copy)]
+struct CrossCheck<'w, 's> {
+    workflows: &'w HashMap<&'s str, Workflow<'s>>,
+    optimized: &'w HashMap<&'s str, Workflow<'s>>,
+}
+
+/// Streams the parts with `N` categories from `part_lines` (along with their index in the input) in
+/// batches and sums the ratings of the accepted ones with the compiled workflows. With `cross_check`,
+/// the string-keyed optimized and original workflows rate them too, checking they agree and timing
+/// the compiled evaluator against the string-keyed one.
+fn solve_part_1<const N: usize>(
+    compiled: &CompiledWorkflows,
+    cross_check: Option<CrossCheck<'_, '_>>,
+    part_lines: impl Iterator<Item = (io::Result<String>, usize)>,
+    categories: &Categories,
+) -> Result<u64, Box<dyn Error>> {
+    let mut part_lines = part_lines
+        .filter(|(line, _)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(line, index)| line.map(|line| (line, index)));
+    let mut part_count = 0;
+    let mut part1_answ = 0;
+    let mut string_keyed_part1_answ = 0;
+    let mut original_part1_answ = 0;
+    let mut compiled_time = Duration::ZERO;
+    let mut string_keyed_time = Duration::ZERO;
+    loop {
+        let batch: Vec<(String, usize)> = part_lines.by_ref().take(BATCH_SIZE).try_collect()?;
+        if batch.is_empty() {
+            break;
+        }
+
+        let parts: Vec<PartRatings<N>> = batch
+            .par_iter()
+            .map(|(line, index)| {
+                PartRatings::parse(line.trim(), categories).map_err(|err| err.in_line(*index, line))
+            })
+            .collect::<Result<_, AocError>>()?;
+
+        trace!(?parts, "batch parsed");
+
+        part_count += parts.len();
+
+        let start = Instant::now();
+        part1_answ += accepted_ratings(&parts, |part| compiled.is_accepted(part));
+        compiled_time += start.elapsed();
+
+        if let Some(CrossCheck {
+            workflows,
+            optimized,
+        }) = cross_check
+        {
+            let start = Instant::now();
+            string_keyed_part1_answ += accepted_ratings(&parts, |part| part.is_accepted(optimized));
+            string_keyed_time += start.elapsed();
+
+            original_part1_answ += accepted_ratings(&parts, |part| part.is_accepted(workflows));
+        }
+    }
+
+    println!("Parts rated: {}", part_count);
+    if cross_check.is_some() {
+        if string_keyed_part1_answ != part1_answ {
+            return Err(format!(
+                "The compiled workflows accepted ratings summing to {}, the optimized ones to {}",
+                part1_answ, string_keyed_part1_answ
+            )
+            .into());
+        }
+        if original_part1_answ != part1_answ {
+            return Err(format!(
+                "The optimized workflows accepted ratings summing to {}, the original ones to {}",
+                part1_answ, original_part1_answ
+            )
+            .into());
+        }
+
+        println!(
+            "Part 1 ({} parts): compiled evaluator in {:?}, string-keyed evaluator in {:?}",
+            part_count, compiled_time, string_keyed_time
+        );
+    }
+
+    Ok(part1_answ)
+}
+
+/// Counts the rating combinations accepted by the compiled workflows, checking the optimized and
+/// original ones agree with `cross_check` (and timing the compiled ones against the string-keyed
+/// ones). The `queries` parts are then answered from the accepted region.
+fn solve_part_2<const N: usize>(
+    compiled: &CompiledWorkflows,
+    cross_check: Option<CrossCheck<'_, '_>>,
+    categories: &Categories,
+    queries: &[String],
+) -> Result<u64, Box<dyn Error>> {
+    let start = Instant::now();
+    let accepted = AcceptedRegion::<N>::from_compiled(compiled);
+    let part2_answ = accepted.combinations();
+    let compiled_time = start.elapsed();
+
+    if let Some(CrossCheck {
+        workflows,
+        optimized,
+    }) = cross_check
+    {
+        let start = Instant::now();
+        let string_keyed_part2_answ = AcceptedRegion::<N>::from_workflows(optimized).combinations();
+        let string_keyed_time = start.elapsed();
+
+        if string_keyed_part2_answ != part2_answ {
+            return Err(format!(
+                "The compiled workflows accept {} combinations, the optimized ones {}",
+                part2_answ, string_keyed_part2_answ
+            )
+            .into());
+        }
+        let original_part2_answ = AcceptedRegion::<N>::from_workflows(workflows).combinations();
+        if original_part2_answ != part2_answ {
+            return Err(format!(
+                "The optimized workflows accept {} combinations, the original ones {}",
+                part2_answ, original_part2_answ
+            )
+            .into());
+        }
+
+        println!(
+            "Part 2: compiled evaluator in {:?}, string-keyed evaluator in {:?}",
+            compiled_time, string_keyed_time
+        );
+    }
+
+    match (accepted.min_rating_sum(), accepted.max_rating_sum()) {
+        (Some(min), Some(max)) => println!(
+            "Accepted region: {} ranges, total ratings from {} to {}",
+            accepted.ranges.len(),
+            min,
+            max
+        ),
+        _ => println!("Accepted region: no part can be accepted"),
+    }
+
+    for query in queries {
+        let part = PartRatings::<N>::parse(query, categories)?;
+        println!(
+            "{} is {}",
+            query,
+            if accepted.is_accepted(&part) {
+                "accepted"
+            } else {
+                "rejected"
+            }
+        );
+    }
+
+    Ok(part2_answ)
+}
+
+/// Parses the workflows (one per line) and keys them by name
+fn workflows_by_name<'s>(
+    workflow_text: &'s str,
+    categories: &Categories,
+) -> Result<HashMap<&'s str, Workflow<'s>>, Box<dyn Error>> {
+    let workflows = parse_lines(workflow_text, |line| {
+        Workflow::parse(line.trim(), categories)
+    })?;
+
+    trace!(?workflows, "workflows parsed");
+    let mut by_name: HashMap<&'_ str, Workflow<'_>> = HashMap::with_capacity(workflows.len());
+    for workflow in workflows {
+        let name = workflow.workflow_name;
+        if by_name.insert(name, workflow).is_some() {
+            return Err(WorkflowError::Duplicate {
+                workflow: name.to_owned(),
+            }
+            .into());
+        }
+    }
+
+    Ok(by_name)
+}
+
+/// Runs `$expr` with `N` set to the number of categories the parts are rated on.
+macro_rules! with_categories {
+    ($categories:expr, $N:ident => $expr:expr, $($count:literal)*) => {
+        match $categories.len() {
+            $($count => {
+                const $N: usize = $count;
+                $expr
+            })*
+            count => Err(format!("Unsupported number of categories: {}", count).into()),
+        }
+    };
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let mut reader = input.open()?;
+    let mut workflow_text = String::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line)? != 0 && !line.trim().is_empty() {
+        workflow_text.push_str(&line);
+        line.clear();
+    }
+
+    // the first part tells which categories the parts are rated on, the parts start after the workflows
+    // and the blank line
+    let mut part_lines = reader.lines().zip(workflow_text.lines().count() + 1..);
+    let first_part = match part_lines
+        .by_ref()
+        .find(|(line, _)| !matches!(line, Ok(line) if line.trim().is_empty()))
+    {
+        Some((line, index)) => Some((line?, index)),
+        None => None,
+    };
+    let categories = match &first_part {
+        Some((line, index)) => {
+            Categories::from_part_line(line.trim()).map_err(|err| err.in_line(*index, line))?
+        }
+        None => Categories::default(),
+    };
+
+    let workflows = workflows_by_name(&workflow_text, &categories)?;
+
+    if let Some(path) = &options.emit_dot {
+        RoutingGraph(&workflows).save_dot(path)?;
+        println!("Workflow graph written to {}", path.display());
+    }
+
+    validate_workflows(&workflows)?;
+
+    let (optimized, eliminated) = optimize_workflows(&workflows);
+    println!(
+        "Optimizer eliminated {} rules ({} workflows left out of {})",
+        eliminated,
+        optimized.len(),
+        workflows.len()
+    );
+
+    let compiled = CompiledWorkflows::compile(&optimized);
+    let cross_check = options.cross_check.then_some(CrossCheck {
+        workflows: &workflows,
+        optimized: &optimized,
+    });
+
+    if options.args.part.solves_part1() {
+        let part_lines = first_part
+            .map(|(line, index)| (Ok(line), index))
+            .into_iter()
+            .chain(part_lines);
+        let part1_answ = with_categories!(categories, N => {
+            solve_part_1::<N>(&compiled, cross_check, part_lines, &categories)
+        }, 1 2 3 4 5 6 7 8)?;
+        println!("Part 1 answer: {}", part1_answ);
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    let part2_answ = with_categories!(categories, N => {
+        solve_part_2::<N>(&compiled, cross_check, &categories, &options.queries)
+    }, 1 2 3 4 5 6 7 8)?;
+    Ok(Some(part2_answ))
+}
+
+/// The workflows, compiled once their names no longer need to borrow from the input, and the
+/// part lines left to rate.
+#[derive(Debug, Clone)]
+pub struct Day19 {
+    compiled: CompiledWorkflows,
+    categories: Categories,
+    part_lines: String,
+    /// the index of the first of `part_lines` in the input, to place the errors in their line
+    part_lines_start: usize,
+}
+
+aoc_utils::register_day!(
+    19,
+    Day19,
+    examples = [Example::both(
+        include_str!("../example"),
+        19114,
+        167409079868000
+    )]
+);
+
+impl Solution for Day19 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = input.split_inclusive('\n');
+        let workflow_text: String = lines
+            .by_ref()
+            .take_while(|line| !line.trim().is_empty())
+            .collect();
+        let part_lines: String = lines.collect();
+        let part_lines_start = workflow_text.lines().count() + 1;
+
+        // the first part tells which categories the parts are rated on
+        let categories = match part_lines
+            .lines()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty())
+        {
+            Some((index, line)) => Categories::from_part_line(line.trim())
+                .map_err(|err| err.in_line(part_lines_start + index, line))?,
+            None => Categories::default(),
+        };
+
+        let workflows = workflows_by_name(&workflow_text, &categories)?;
+        validate_workflows(&workflows)?;
+        let (optimized, _) = optimize_workflows(&workflows);
+
+        Ok(Self {
+            compiled: CompiledWorkflows::compile(&optimized),
+            categories,
+            part_lines,
+            part_lines_start,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        with_categories!(self.categories, N => {
+            let parts: Vec<PartRatings<N>> = self
+                .part_lines
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(index, line)| {
+                    PartRatings::parse(line.trim(), &self.categories)
+                        .map_err(|err| err.in_line(self.part_lines_start + index, line))
+                })
+                .try_collect()?;
+            Ok(accepted_ratings(&parts, |part| self.compiled.is_accepted(part)).into())
+        }, 1 2 3 4 5 6 7 8)
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        with_categories!(self.categories, N => {
+            Ok(AcceptedRegion::<N>::from_compiled(&self.compiled).combinations().into())
+        }, 1 2 3 4 5 6 7 8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(start, end)` of an interval of ratings
+    type Bounds = (u32, u32);
+
+    /// The non empty ranges of `m` that `condition` accepts and the one it rejects, after checking that every
+    /// rating of `m` lands where [`WorkflowCondition::is_condition_true`] sends it and that `x`, `a` and `s`
+    /// are left alone.
+    fn map_m(condition: &str) -> (Vec<Bounds>, Option<Bounds>) {
+        let categories = Categories::default();
+        let condition = WorkflowCondition::parse(condition, &categories).unwrap();
+        let ([first, second], rejected) = condition.map_range(PartRatingsRange::<4>::default());
+
+        for m in 1..=4000 {
+            let part = PartRatings {
+                ratings: [1, m, 1, 1],
+            };
+            let accepted = first.contains(&part) || second.contains(&part);
+            assert_eq!(
+                accepted,
+                condition.is_condition_true(&part),
+                "{} with m={}",
+                condition,
+                m
+            );
+            assert_eq!(
+                rejected.contains(&part),
+                !accepted,
+                "{} with m={}",
+                condition,
+                m
+            );
+        }
+
+        for range in [&first, &second, &rejected]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+        {
+            for index in [0, 2, 3] {
+                assert_eq!(range.ranges[index], Interval::new(1, 4001));
+            }
+        }
+
+        let m = categories.get("m").unwrap();
+        let accepted = [first, second]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| (range[m].start, range[m].end))
+            .collect();
+        (
+            accepted,
+            (!rejected.is_empty()).then(|| (rejected[m].start, rejected[m].end)),
+        )
+    }
+
+    #[test]
+    fn lesser() {
+        assert_eq!(map_m("m<1000"), (vec![(1, 1000)], Some((1000, 4001))));
+        assert_eq!(map_m("m<1"), (vec![], Some((1, 4001))));
+        assert_eq!(map_m("m<2"), (vec![(1, 2)], Some((2, 4001))));
+        assert_eq!(map_m("m<4000"), (vec![(1, 4000)], Some((4000, 4001))));
+        assert_eq!(map_m("m<4001"), (vec![(1, 4001)], None));
+    }
+
+    #[test]
+    fn greater() {
+        assert_eq!(map_m("m>1000"), (vec![(1001, 4001)], Some((1, 1001))));
+        assert_eq!(map_m("m>0"), (vec![(1, 4001)], None));
+        assert_eq!(map_m("m>1"), (vec![(2, 4001)], Some((1, 2))));
+        assert_eq!(map_m("m>3999"), (vec![(4000, 4001)], Some((1, 4000))));
+        assert_eq!(map_m("m>4000"), (vec![], Some((1, 4001))));
+    }
+
+    #[test]
+    fn lesser_or_equal() {
+        assert_eq!(map_m("m<=1000"), (vec![(1, 1001)], Some((1001, 4001))));
+        assert_eq!(map_m("m<=0"), (vec![], Some((1, 4001))));
+        assert_eq!(map_m("m<=1"), (vec![(1, 2)], Some((2, 4001))));
+        assert_eq!(map_m("m<=3999"), (vec![(1, 4000)], Some((4000, 4001))));
+        assert_eq!(map_m("m<=4000"), (vec![(1, 4001)], None));
+    }
+
+    #[test]
+    fn greater_or_equal() {
+        assert_eq!(map_m("m>=1000"), (vec![(1000, 4001)], Some((1, 1000))));
+        assert_eq!(map_m("m>=1"), (vec![(1, 4001)], None));
+        assert_eq!(map_m("m>=2"), (vec![(2, 4001)], Some((1, 2))));
+        assert_eq!(map_m("m>=4000"), (vec![(4000, 4001)], Some((1, 4000))));
+        assert_eq!(map_m("m>=4001"), (vec![], Some((1, 4001))));
+    }
+
+    #[test]
+    fn not_equal() {
+        assert_eq!(
+            map_m("m!=1000"),
+            (vec![(1, 1000), (1001, 4001)], Some((1000, 1001)))
+        );
+        assert_eq!(map_m("m!=1"), (vec![(2, 4001)], Some((1, 2))));
+        assert_eq!(map_m("m!=4000"), (vec![(1, 4000)], Some((4000, 4001))));
+        assert_eq!(map_m("m!=0"), (vec![(1, 4001)], None));
+        assert_eq!(map_m("m!=4001"), (vec![(1, 4001)], None));
+    }
+
+    #[test]
+    fn u32_max() {
+        assert_eq!(map_m("m>4294967295"), (vec![], Some((1, 4001))));
+        assert_eq!(map_m("m>=4294967295"), (vec![], Some((1, 4001))));
+        assert_eq!(map_m("m<4294967295"), (vec![(1, 4001)], None));
+        assert_eq!(map_m("m<=4294967295"), (vec![(1, 4001)], None));
+        assert_eq!(map_m("m!=4294967295"), (vec![(1, 4001)], None));
+    }
+}
@@ -0,0 +1,1194 @@
+use aoc_utils::{
+    dot::{Attributes, ToDot},
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::Parser;
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, VecDeque},
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    mem,
+    path::PathBuf,
+    str::FromStr,
+    time::Instant,
+};
+use tracing::trace;
+
+const BROADCAST: &str = "broadcaster";
+const RX: &str = "rx";
+/// Give up on conditions that are never met instead of pressing the button forever
+const MAX_PRESSES: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Pulse {
+    Low,
+    High,
+}
+
+impl FromStr for Pulse {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(Self::Low),
+            "high" => Ok(Self::High),
+            other => Err(format!(r#"Unknown pulse {:?} (expected "low" or "high")"#, other).into()),
+        }
+    }
+}
+
+/// Index of a module in System::modules, names are interned to those while parsing
+type ModuleId = u16;
+/// Stands for the button when it is the sender of a pulse, no module has this id
+const BUTTON: ModuleId = ModuleId::MAX;
+
+/// What a module does with the pulses it receives.
+///
+/// The built-in kinds are listed in MODULE_KINDS along with the prefix declaring them,
+/// a new kind only needs an implementation and an entry there.
+trait Module: fmt::Debug {
+    fn kind_name(&self) -> &'static str;
+
+    /// Called once the whole input is parsed, with how many modules send pulses to this one
+    fn connect(&mut self, _input_count: usize) {}
+
+    /// Handles `pulse` coming from the input in `slot` (in Node::inputs order),
+    /// returning the pulse to send to every destination
+    fn pulse_to_send(&mut self, pulse: Pulse, slot: usize) -> Option<Pulse>;
+
+    /// The state saved in snapshots, empty for modules without one
+    fn state(&self) -> Vec<Pulse> {
+        vec![]
+    }
+
+    /// Puts back a state returned by Module::state()
+    fn restore(&mut self, state: &[Pulse]) -> Result<(), Box<dyn Error>> {
+        if state.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("A {} has no state to restore", self.kind_name()).into())
+        }
+    }
+
+    /// The state in a human readable form, `inputs` are the names of the modules feeding this one
+    fn describe_state(&self, _inputs: &[&str]) -> String {
+        String::new()
+    }
+
+    /// The shape of the module in the DOT graph of the system
+    fn dot_shape(&self) -> &'static str {
+        "ellipse"
+    }
+
+    fn clone_box(&self) -> Box<dyn Module>;
+}
+
+impl Clone for Box<dyn Module> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Makes a module of some kind, in its initial state
+type ModuleConstructor = fn() -> Box<dyn Module>;
+
+/// The prefix declaring each kind of module in the input, and how to make one
+const MODULE_KINDS: [(char, ModuleConstructor); 2] = [
+    ('%', || Box::new(FlipFlop(false))),
+    ('&', || Box::new(Conjunction::default())),
+];
+
+/// Sends every pulse it receives as is, the button pulses it
+#[derive(Debug, Clone, Copy)]
+struct Broadcast;
+
+impl Module for Broadcast {
+    fn kind_name(&self) -> &'static str {
+        "broadcaster"
+    }
+
+    #[inline]
+    fn pulse_to_send(&mut self, pulse: Pulse, _slot: usize) -> Option<Pulse> {
+        Some(pulse)
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "doublecircle"
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(*self)
+    }
+}
+
+/// Toggles on every low pulse and sends high when turned on, low when turned off
+#[derive(Debug, Clone, Copy)]
+struct FlipFlop(bool);
+
+impl Module for FlipFlop {
+    fn kind_name(&self) -> &'static str {
+        "flip-flop"
+    }
+
+    #[inline]
+    fn pulse_to_send(&mut self, pulse: Pulse, _slot: usize) -> Option<Pulse> {
+        if matches!(pulse, Pulse::Low) {
+            self.0 = !self.0;
+            Some(if self.0 { Pulse::High } else { Pulse::Low })
+        } else {
+            None
+        }
+    }
+
+    fn state(&self) -> Vec<Pulse> {
+        vec![if self.0 { Pulse::High } else { Pulse::Low }]
+    }
+
+    fn restore(&mut self, state: &[Pulse]) -> Result<(), Box<dyn Error>> {
+        let &[state] = state else {
+            return Err(format!("A flip-flop state is a single pulse, got {:?}", state).into());
+        };
+
+        self.0 = state == Pulse::High;
+        Ok(())
+    }
+
+    fn describe_state(&self, _inputs: &[&str]) -> String {
+        (if self.0 { "on" } else { "off" }).to_string()
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "box"
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(*self)
+    }
+}
+
+/// Remembers the last pulse from each input and sends low once they are all high, high otherwise
+#[derive(Debug, Clone, Default)]
+struct Conjunction {
+    memory: Box<[Pulse]>,
+    high_count: usize,
+}
+
+impl Module for Conjunction {
+    fn kind_name(&self) -> &'static str {
+        "conjunction"
+    }
+
+    fn connect(&mut self, input_count: usize) {
+        self.memory = vec![Pulse::Low; input_count].into_boxed_slice();
+        self.high_count = 0;
+    }
+
+    #[inline]
+    fn pulse_to_send(&mut self, pulse: Pulse, slot: usize) -> Option<Pulse> {
+        match (mem::replace(&mut self.memory[slot], pulse), pulse) {
+            (Pulse::Low, Pulse::High) => self.high_count += 1,
+            (Pulse::High, Pulse::Low) => self.high_count -= 1,
+            _ => (),
+        }
+
+        Some(if self.high_count == self.memory.len() {
+            Pulse::Low
+        } else {
+            Pulse::High
+        })
+    }
+
+    fn state(&self) -> Vec<Pulse> {
+        self.memory.to_vec()
+    }
+
+    fn restore(&mut self, state: &[Pulse]) -> Result<(), Box<dyn Error>> {
+        if state.len() != self.memory.len() {
+            return Err(format!(
+                "This conjunction remembers {} inputs, got {:?}",
+                self.memory.len(),
+                state
+            )
+            .into());
+        }
+
+        self.memory.copy_from_slice(state);
+        self.high_count = state.iter().filter(|&&pulse| pulse == Pulse::High).count();
+        Ok(())
+    }
+
+    fn describe_state(&self, inputs: &[&str]) -> String {
+        format!(
+            "{}/{} high [{}]",
+            self.high_count,
+            self.memory.len(),
+            inputs
+                .iter()
+                .zip(self.memory.iter())
+                .map(|(name, pulse)| format!("{}={:?}", name, pulse))
+                .join(", ")
+        )
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "diamond"
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+}
+
+/// Only ever seen as a destination (like "rx"), receives pulses but never sends any
+#[derive(Debug, Clone, Copy)]
+struct Output;
+
+impl Module for Output {
+    fn kind_name(&self) -> &'static str {
+        "output"
+    }
+
+    #[inline]
+    fn pulse_to_send(&mut self, _pulse: Pulse, _slot: usize) -> Option<Pulse> {
+        None
+    }
+
+    fn clone_box(&self) -> Box<dyn Module> {
+        Box::new(*self)
+    }
+}
+
+/// A module with its place in the system
+#[derive(Debug, Clone)]
+struct Node {
+    module: Box<dyn Module>,
+    /// every destination along with the slot this module has in the destination's inputs
+    destinations: Box<[(ModuleId, u16)]>,
+    inputs: Box<[ModuleId]>,
+}
+
+#[derive(Debug, Clone)]
+struct System<'s> {
+    /// module names, indexed by ModuleId
+    names: Vec<&'s str>,
+    ids: FnvHashMap<&'s str, ModuleId>,
+    modules: Vec<Node>,
+    broadcaster: ModuleId,
+}
+
+/// A pulse in flight: destination, the sender's slot in the destination's inputs, pulse, sender
+type PendingPulse = (ModuleId, u16, Pulse, ModuleId);
+
+/// Pulses one module sent and received, indexed by `Pulse as usize` (low then high)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct PulseStats {
+    sent: [u64; 2],
+    received: [u64; 2],
+}
+
+/// The state of every module with one (flip-flops, conjunction memories...), see System::snapshot()
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+struct Snapshot {
+    modules: BTreeMap<String, Vec<Pulse>>,
+}
+
+impl<'s> System<'s> {
+    pub(crate) fn parse(input: &'s str) -> Result<Self, Box<dyn Error>> {
+        let mut names = vec![];
+        let mut ids = FnvHashMap::default();
+        let mut intern = |name: &'s str| -> Result<ModuleId, Box<dyn Error>> {
+            if let Some(&id) = ids.get(name) {
+                return Ok(id);
+            }
+
+            let id = ModuleId::try_from(names.len())
+                .ok()
+                .filter(|&id| id != BUTTON)
+                .ok_or("Too many modules to give each one an id")?;
+            names.push(name);
+            ids.insert(name, id);
+            Ok(id)
+        };
+
+        let mut declared = vec![];
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (label, destinations) = line
+                .split_once(" -> ")
+                .ok_or_else(|| format!(r#"Could not split {:?} at " -> ""#, line))?;
+
+            let (name, module): (&str, Box<dyn Module>) = if label == BROADCAST {
+                (label, Box::new(Broadcast))
+            } else if let Some((name, make)) = MODULE_KINDS
+                .iter()
+                .find_map(|&(prefix, make)| Some((label.strip_prefix(prefix)?, make)))
+            {
+                (name, make())
+            } else {
+                return Err(format!(
+                    "module name was neither {:?} nor started with any of {:?} ({:?})",
+                    BROADCAST,
+                    MODULE_KINDS.map(|(prefix, _)| prefix),
+                    label
+                )
+                .into());
+            };
+
+            let id = intern(name)?;
+            let destinations: Vec<ModuleId> = destinations
+                .split(',')
+                .map(|name| intern(name.trim()))
+                .try_collect()?;
+            declared.push((id, module, destinations));
+        }
+
+        let mut modules = vec![
+            Node {
+                module: Box::new(Output),
+                destinations: Box::new([]),
+                inputs: Box::new([]),
+            };
+            names.len()
+        ];
+
+        let mut declared_ids = vec![false; names.len()];
+        let mut inputs = vec![vec![]; names.len()];
+        for (id, module, destinations) in declared {
+            if mem::replace(&mut declared_ids[id as usize], true) {
+                return Err(
+                    format!("The module {:?} is declared twice", names[id as usize]).into(),
+                );
+            }
+
+            let node = &mut modules[id as usize];
+            node.module = module;
+            node.destinations = destinations
+                .into_iter()
+                .map(|destination| {
+                    let slot = inputs[destination as usize].len() as u16;
+                    inputs[destination as usize].push(id);
+                    (destination, slot)
+                })
+                .collect();
+        }
+
+        for (node, inputs) in modules.iter_mut().zip(inputs) {
+            node.module.connect(inputs.len());
+            node.inputs = inputs.into_boxed_slice();
+        }
+
+        let broadcaster = *ids
+            .get(BROADCAST)
+            .ok_or_else(|| format!("There is no {:?} module", BROADCAST))?;
+
+        Ok(Self {
+            names,
+            ids,
+            modules,
+            broadcaster,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn name(&self, id: ModuleId) -> &'s str {
+        if id == BUTTON {
+            "button"
+        } else {
+            self.names[id as usize]
+        }
+    }
+
+    #[inline]
+    /// First u64 is low pulse count, Second is high pulse count
+    pub(crate) fn push_button(&mut self) -> (u64, u64) {
+        let mut low_count = 0;
+        let mut high_count = 0;
+        self.push_button_observed(|_, pulse, _| match pulse {
+            Pulse::Low => low_count += 1,
+            Pulse::High => high_count += 1,
+        });
+
+        (low_count, high_count)
+    }
+
+    #[inline]
+    /// Pushes the button, calling `observe(from, pulse, to)` for every pulse in the order they are sent
+    pub(crate) fn push_button_observed(
+        &mut self,
+        mut observe: impl FnMut(ModuleId, Pulse, ModuleId),
+    ) {
+        self.push_button_rounds(|_, from, pulse, to| observe(from, pulse, to));
+    }
+
+    /// Pushes the button, calling `observe(round, from, pulse, to)` for every pulse in the order they are sent,
+    /// `round` being how many pulses led to it from the button: the pulses waiting in line, every one of a
+    /// round is delivered before those of the next
+    pub(crate) fn push_button_rounds(
+        &mut self,
+        mut observe: impl FnMut(u32, ModuleId, Pulse, ModuleId),
+    ) {
+        let mut pulse_backlog = VecDeque::new();
+        self.queue_button_press(&mut pulse_backlog);
+
+        let (mut round, mut left_in_round) = (0, pulse_backlog.len());
+        while let Some((from, pulse, to)) = self.deliver_pulse(&mut pulse_backlog) {
+            observe(round, from, pulse, to);
+            left_in_round -= 1;
+            if left_in_round == 0 {
+                round += 1;
+                left_in_round = pulse_backlog.len();
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn queue_button_press(&self, pulse_backlog: &mut VecDeque<PendingPulse>) {
+        pulse_backlog.push_back((self.broadcaster, 0, Pulse::Low, BUTTON));
+    }
+
+    #[inline]
+    /// Delivers the oldest pulse of `pulse_backlog`, queueing the pulses it makes its destination send.
+    /// Returns the delivered pulse as `(from, pulse, to)`, `None` once nothing is left in flight.
+    pub(crate) fn deliver_pulse(
+        &mut self,
+        pulse_backlog: &mut VecDeque<PendingPulse>,
+    ) -> Option<(ModuleId, Pulse, ModuleId)> {
+        let (id, slot, pulse, from) = pulse_backlog.pop_front()?;
+        trace!(
+            from = self.name(from),
+            ?pulse,
+            to = self.name(id),
+            "pulse delivered"
+        );
+
+        let node = &mut self.modules[id as usize];
+        if let Some(pulse) = node.module.pulse_to_send(pulse, slot as usize) {
+            for &(destination, slot) in node.destinations.iter() {
+                pulse_backlog.push_back((destination, slot, pulse, id));
+            }
+        }
+
+        Some((from, pulse, id))
+    }
+
+    /// One line describing the module and its state
+    pub(crate) fn describe(&self, id: ModuleId) -> String {
+        let node = &self.modules[id as usize];
+        let inputs = node
+            .inputs
+            .iter()
+            .map(|&input| self.name(input))
+            .collect_vec();
+        let description = format!("{} ({})", self.name(id), node.module.kind_name());
+        match node.module.describe_state(&inputs) {
+            state if state.is_empty() => description,
+            state => format!("{} {}", description, state),
+        }
+    }
+
+    /// Pushes the button `presses` times and counts the pulses each module sent and received
+    pub(crate) fn pulse_stats(&mut self, presses: u64) -> FnvHashMap<ModuleId, PulseStats> {
+        let mut stats: FnvHashMap<ModuleId, PulseStats> = FnvHashMap::default();
+        for _ in 0..presses {
+            self.push_button_observed(|from, pulse, to| {
+                stats.entry(from).or_default().sent[pulse as usize] += 1;
+                stats.entry(to).or_default().received[pulse as usize] += 1;
+            });
+        }
+
+        stats
+    }
+
+    /// Prints what pulse_stats() saw: the pulses of every module, how often each flip-flop toggles
+    /// and which conjunctions are the busiest
+    pub(crate) fn print_stats(&mut self, presses: u64) {
+        /// How many conjunctions make it in the busiest list
+        const BUSIEST: usize = 5;
+
+        let stats = self.pulse_stats(presses);
+        let kind = |id: ModuleId| match self.modules.get(id as usize) {
+            Some(node) => node.module.kind_name(),
+            None => "button",
+        };
+
+        let (low, high) = stats.values().fold((0, 0), |(low, high), stats| {
+            (low + stats.sent[0], high + stats.sent[1])
+        });
+        println!(
+            "{} presses: {} low pulses, {} high pulses",
+            presses, low, high
+        );
+
+        println!(
+            "{:<12} {:<12} {:>12} {:>12} {:>12} {:>12} {:>14}",
+            "module", "kind", "low in", "high in", "low out", "high out", "toggles/press"
+        );
+        for (&id, stats) in stats.iter().sorted_by_key(|(&id, _)| self.name(id)) {
+            let kind = kind(id);
+            // a flip-flop toggles on every low pulse it receives
+            let toggles = if kind == "flip-flop" {
+                format!("{:.4}", stats.received[0] as f64 / presses as f64)
+            } else {
+                String::new()
+            };
+
+            println!(
+                "{:<12} {:<12} {:>12} {:>12} {:>12} {:>12} {:>14}",
+                self.name(id),
+                kind,
+                stats.received[0],
+                stats.received[1],
+                stats.sent[0],
+                stats.sent[1],
+                toggles
+            );
+        }
+
+        println!("Busiest conjunctions:");
+        for (&id, stats) in stats
+            .iter()
+            .filter(|(&id, _)| kind(id) == "conjunction")
+            .sorted_by_key(|(&id, stats)| {
+                (Reverse(stats.received.iter().sum::<u64>()), self.name(id))
+            })
+            .take(BUSIEST)
+        {
+            println!(
+                "    {}: {} pulses received, sent {} low and {} high",
+                self.name(id),
+                stats.received.iter().sum::<u64>(),
+                stats.sent[0],
+                stats.sent[1]
+            );
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            modules: self
+                .modules
+                .iter()
+                .zip(self.names.iter())
+                .map(|(node, &name)| (name.to_string(), node.module.state()))
+                .filter(|(_, state)| !state.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Puts every module back in the state recorded by `snapshot`,
+    /// which must come from a system with the same modules
+    pub(crate) fn restore(&mut self, snapshot: &Snapshot) -> Result<(), Box<dyn Error>> {
+        for (node, &name) in self.modules.iter_mut().zip(self.names.iter()) {
+            let state = snapshot.modules.get(name).map_or(&[][..], Vec::as_slice);
+            node.module
+                .restore(state)
+                .map_err(|err| format!("Could not restore {:?}: {}", name, err))?;
+        }
+
+        Ok(())
+    }
+
+    /// Finds the conjunction which is the only input of `target` and the modules feeding that conjunction
+    pub(crate) fn single_conjunction_feeding(
+        &self,
+        target: ModuleId,
+    ) -> Option<(ModuleId, Vec<ModuleId>)> {
+        let &[feeder] = &*self.modules[target as usize].inputs else {
+            return None;
+        };
+
+        let feeder_module = &self.modules[feeder as usize];
+        if feeder_module.module.kind_name() != "conjunction" || feeder_module.inputs.is_empty() {
+            return None;
+        }
+
+        let feeder_inputs = feeder_module
+            .inputs
+            .iter()
+            .copied()
+            .sorted_by_key(|&input| self.name(input))
+            .collect_vec();
+        Some((feeder, feeder_inputs))
+    }
+
+    /// How many button presses it takes for `target` to receive a `pulse`, along with the cycles of the inputs of
+    /// its feeding conjunction when they were used to find it
+    pub(crate) fn presses_until(
+        self,
+        target: &str,
+        pulse: Pulse,
+    ) -> Result<(u64, Vec<FeederCycle<'s>>), Box<dyn Error>> {
+        let target = *self
+            .ids
+            .get(target)
+            .ok_or_else(|| format!("No module is named or sends pulses to {:?}", target))?;
+
+        // a lone conjunction sends a low pulse once it remembers a high pulse from all of its inputs,
+        // each input sends one every so many presses: those cycles are (way) shorter than the answer.
+        match (pulse, self.single_conjunction_feeding(target)) {
+            (Pulse::Low, Some((feeder, feeder_inputs))) => {
+                self.presses_until_all_high(feeder, feeder_inputs)
+            }
+            _ => Ok((self.simulate_until(target, pulse)?, vec![])),
+        }
+    }
+
+    /// Presses the button until `target` receives a `pulse`, giving up after MAX_PRESSES
+    fn simulate_until(mut self, target: ModuleId, wanted: Pulse) -> Result<u64, Box<dyn Error>> {
+        for cycles in 1..=MAX_PRESSES {
+            let mut received = false;
+            self.push_button_observed(|_, pulse, to| received |= to == target && pulse == wanted);
+            if received {
+                return Ok(cycles);
+            }
+        }
+
+        Err(format!(
+            "{:?} did not receive a {:?} pulse within {} presses",
+            self.name(target),
+            wanted,
+            MAX_PRESSES
+        )
+        .into())
+    }
+
+    /// Presses the button until every one of `feeder_inputs` went through a few cycles of sending
+    /// a high pulse to `feeder`, then finds the first press where they all send one, returned with the cycle of
+    /// every input.
+    ///
+    /// Every input must send its high pulses on evenly spaced presses, each time at the same round of the
+    /// press, and the conjunction must remember the high pulses of all its inputs at once: the last of them
+    /// has to arrive before the first input is reset by a low pulse. Those are only checked on the presses
+    /// simulated, the answer assumes the cycles go on the same way after them.
+    fn presses_until_all_high(
+        mut self,
+        feeder: ModuleId,
+        feeder_inputs: Vec<ModuleId>,
+    ) -> Result<(u64, Vec<FeederCycle<'s>>), Box<dyn Error>> {
+        /// How many presses with a high pulse are recorded per input before trusting its period
+        const FIRINGS_RECORDED: usize = 3;
+
+        let mut press = 0;
+        let mut firings_of: FnvHashMap<ModuleId, Vec<Firing>> = FnvHashMap::default();
+        let mut complete = 0;
+
+        while complete < feeder_inputs.len() {
+            if press == MAX_PRESSES {
+                return Err(format!(
+                    "Not every input of {:?} sent {} high pulses within {} presses (missing: {:?})",
+                    self.name(feeder),
+                    FIRINGS_RECORDED,
+                    MAX_PRESSES,
+                    feeder_inputs
+                        .iter()
+                        .filter(|input| firings_of
+                            .get(*input)
+                            .is_none_or(|firings| firings.len() < FIRINGS_RECORDED))
+                        .map(|&input| self.name(input))
+                        .collect_vec()
+                )
+                .into());
+            }
+
+            press += 1;
+            self.push_button_rounds(|round, from, pulse, to| {
+                if to != feeder {
+                    return;
+                }
+
+                let firings = firings_of.entry(from).or_default();
+                match pulse {
+                    Pulse::High if firings.last().is_none_or(|firing| firing.press != press) => {
+                        firings.push(Firing {
+                            press,
+                            high_round: round,
+                            reset_round: None,
+                        });
+                        if firings.len() == FIRINGS_RECORDED {
+                            complete += 1;
+                        }
+                    }
+                    Pulse::High => (),
+                    Pulse::Low => {
+                        let unreset = firings
+                            .last_mut()
+                            .filter(|firing| firing.press == press && firing.reset_round.is_none());
+                        if let Some(firing) = unreset {
+                            firing.reset_round = Some(round);
+                        }
+                    }
+                }
+            });
+        }
+
+        let mut combined = (0, 1);
+        let mut earliest = 0;
+        // the rounds of a press from the last high pulse received to the first reset
+        let (mut last_high, mut first_reset) = ((feeder, 0), (feeder, u32::MAX));
+        let mut cycles = vec![];
+        for input in feeder_inputs {
+            let firings = &firings_of[&input];
+            let presses = firings.iter().map(|firing| firing.press).collect_vec();
+            let period = presses[1] - presses[0];
+            if presses.windows(2).any(|pair| pair[1] - pair[0] != period) {
+                return Err(format!(
+                    "{:?} sent high pulses to {:?} on presses {:?}, which are not evenly spaced",
+                    self.name(input),
+                    self.name(feeder),
+                    presses
+                )
+                .into());
+            }
+
+            let first = firings[0];
+            let rounds = |firing: &Firing| (firing.high_round, firing.reset_round);
+            if firings
+                .iter()
+                .any(|firing| rounds(firing) != rounds(&first))
+            {
+                return Err(format!(
+                    "{:?} sent high pulses to {:?} at different rounds of the presses, (high, reset): {:?}",
+                    self.name(input),
+                    self.name(feeder),
+                    firings.iter().map(rounds).collect_vec()
+                )
+                .into());
+            }
+
+            if first.high_round > last_high.1 {
+                last_high = (input, first.high_round);
+            }
+            let reset_round = first.reset_round.unwrap_or(u32::MAX);
+            if reset_round < first_reset.1 {
+                first_reset = (input, reset_round);
+            }
+
+            cycles.push(FeederCycle {
+                input: self.name(input),
+                feeder: self.name(feeder),
+                first_press: first.press,
+                period,
+                high_round: first.high_round,
+                reset_round: first.reset_round,
+                checked_until: press,
+            });
+
+            earliest = earliest.max(first.press);
+            combined = crt(combined, (first.press % period, period)).ok_or_else(|| {
+                format!(
+                    "The inputs of {:?} never all send a high pulse on the same press",
+                    self.name(feeder)
+                )
+            })?;
+        }
+
+        // the pulses of a round being delivered in any order, a high pulse and a reset on the same round
+        // may or may not overlap
+        if last_high.1 >= first_reset.1 {
+            return Err(format!(
+                "The inputs of {:?} never send it high pulses all at once: {:?} sends its high pulse on round {} of \
+                 the press but {:?} is reset on round {}",
+                self.name(feeder),
+                self.name(last_high.0),
+                last_high.1,
+                self.name(first_reset.0),
+                first_reset.1
+            )
+            .into());
+        }
+
+        // the press must also come after every input started its cycle
+        let (residue, modulus) = combined;
+        let mut press = residue;
+        if press < earliest {
+            press += (earliest - press).div_ceil(modulus) * modulus;
+        }
+
+        Ok((press, cycles))
+    }
+}
+
+/// A press on which an input of the conjunction feeding the watched module sends it a high pulse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Firing {
+    press: u64,
+    /// the round of the press the high pulse is delivered on, see [`System::push_button_rounds`]
+    high_round: u32,
+    /// the round of the low pulse the input sends next in the same press, `None` if it stays high after it
+    reset_round: Option<u32>,
+}
+
+/// How often an input of the conjunction feeding the watched module sends it a high pulse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FeederCycle<'s> {
+    input: &'s str,
+    feeder: &'s str,
+    /// the first press on which `input` sends a high pulse to `feeder`
+    first_press: u64,
+    /// how many presses later it sends the next one, and so on
+    period: u64,
+    /// the round of the press the high pulse is delivered on
+    high_round: u32,
+    /// the round of the low pulse it sends next in the same press, `None` if it stays high after it
+    reset_round: Option<u32>,
+    /// the last press the cycle was checked on
+    checked_until: u64,
+}
+
+impl fmt::Display for FeederCycle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} -> {:?}: high on press {}, then every {} presses (checked up to press {}), from round {} ",
+            self.input, self.feeder, self.first_press, self.period, self.checked_until, self.high_round
+        )?;
+        match self.reset_round {
+            Some(round) => write!(f, "to round {} of the press", round),
+            None => write!(f, "to the end of the press"),
+        }
+    }
+}
+
+/// Every module drawn in the shape of its kind, pointing at its destinations.
+impl ToDot for System<'_> {
+    type Node = ModuleId;
+    type Edge = ();
+
+    fn nodes(&self) -> impl Iterator<Item = ModuleId> {
+        0..self.modules.len() as ModuleId
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (ModuleId, ModuleId, ())> {
+        self.modules.iter().enumerate().flat_map(|(id, node)| {
+            node.destinations
+                .iter()
+                .map(move |&(destination, _)| (id as ModuleId, destination, ()))
+        })
+    }
+
+    fn node_id(&self, &id: &ModuleId) -> String {
+        self.name(id).to_owned()
+    }
+
+    fn node_attributes(&self, &id: &ModuleId) -> Attributes {
+        vec![(
+            "shape",
+            self.modules[id as usize].module.dot_shape().to_owned(),
+        )]
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<20>,
+    /// Start pressing the button from this saved state instead of every module being off
+    #[arg(long, value_name = "PATH")]
+    load_snapshot: Option<PathBuf>,
+    /// Save the state reached after part 1's presses to this file
+    #[arg(long, value_name = "PATH")]
+    save_snapshot: Option<PathBuf>,
+    /// Count the presses of part 2 until this module receives this pulse (`rx:low` unless given)
+    #[arg(long, value_name = "MODULE:PULSE", value_parser = parse_watch)]
+    watch: Option<(String, Pulse)>,
+    /// Press the button this many times and print the pulse statistics before solving
+    #[arg(long, value_name = "PRESSES")]
+    stats: Option<u64>,
+    /// Press the button this many times and print how many presses a second that made before solving
+    #[arg(long, value_name = "PRESSES")]
+    throughput: Option<u64>,
+    /// Write the module network to this file in the DOT format
+    #[arg(long, value_name = "PATH")]
+    emit_dot: Option<PathBuf>,
+    /// Step through button presses and pulses from a prompt instead of solving
+    #[arg(long)]
+    pub interactive: bool,
+}
+
+/// Parses the `module:pulse` of `--watch`.
+fn parse_watch(watch: &str) -> Result<(String, Pulse), String> {
+    let (module, pulse) = watch
+        .split_once(':')
+        .ok_or_else(|| format!("Expected {:?} to be module:pulse", watch))?;
+    let pulse = pulse
+        .parse()
+        .map_err(|err: Box<dyn Error>| err.to_string())?;
+    Ok((module.to_owned(), pulse))
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+const STEP_THROUGH_HELP: &str = "\
+press [n]           push the button n times (1 by default), after delivering the pulses in flight
+step [n]            deliver the next n pulses (1 by default), pushing the button if none are in flight
+queue               list the pulses in flight
+show [module...]    the state of these modules (all of them by default)
+save                remember the current state
+rewind              go back to the remembered state, dropping the pulses in flight
+help                this message
+quit";
+
+/// Reads commands from stdin to press the button or deliver pulses one at a time,
+/// showing the pulses in flight and the state of the modules along the way
+pub fn step_through(input: &InputSource, options: &Options) -> Result<(), Box<dyn Error>> {
+    let input = input.read()?;
+    let mut modules = System::parse(&input)?;
+    if let Some(path) = &options.load_snapshot {
+        let snapshot: Snapshot = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        modules.restore(&snapshot)?;
+    }
+
+    let mut saved = (modules.snapshot(), 0);
+    let mut presses = 0;
+    let mut pulse_backlog = VecDeque::new();
+    let print_pulse = |modules: &System<'_>, (from, pulse, to): (ModuleId, Pulse, ModuleId)| {
+        println!("{} -{:?}-> {}", modules.name(from), pulse, modules.name(to));
+    };
+
+    println!("{}", STEP_THROUGH_HELP);
+    let stdin = io::stdin();
+    loop {
+        print!("[press {}, {} in flight]> ", presses, pulse_backlog.len());
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+
+        let count = words.clone().next().map(str::parse).transpose();
+        let run = || -> Result<bool, Box<dyn Error>> {
+            match command {
+                "press" | "p" => {
+                    for _ in 0..count?.unwrap_or(1) {
+                        while modules.deliver_pulse(&mut pulse_backlog).is_some() {}
+                        presses += 1;
+                        modules.push_button();
+                    }
+                }
+                "step" | "s" => {
+                    for _ in 0..count?.unwrap_or(1) {
+                        if pulse_backlog.is_empty() {
+                            presses += 1;
+                            modules.queue_button_press(&mut pulse_backlog);
+                        }
+
+                        let delivered = modules
+                            .deliver_pulse(&mut pulse_backlog)
+                            .expect("a pulse was just queued");
+                        print_pulse(&modules, delivered);
+                    }
+                }
+                "queue" | "q" => {
+                    for &(to, _, pulse, from) in pulse_backlog.iter() {
+                        print_pulse(&modules, (from, pulse, to));
+                    }
+                }
+                "show" => {
+                    let mut names = words.clone().peekable();
+                    let ids: Vec<ModuleId> = if names.peek().is_none() {
+                        (0..modules.names.len() as ModuleId)
+                            .sorted_by_key(|&id| modules.name(id))
+                            .collect()
+                    } else {
+                        names
+                            .map(|name| {
+                                modules
+                                    .ids
+                                    .get(name)
+                                    .copied()
+                                    .ok_or_else(|| format!("No module is named {:?}", name))
+                            })
+                            .try_collect()?
+                    };
+
+                    for id in ids {
+                        println!("{}", modules.describe(id));
+                    }
+                }
+                "save" => saved = (modules.snapshot(), presses),
+                "rewind" => {
+                    modules.restore(&saved.0)?;
+                    presses = saved.1;
+                    pulse_backlog.clear();
+                }
+                "help" => println!("{}", STEP_THROUGH_HELP),
+                "quit" => return Ok(true),
+                other => println!("Unknown command {:?}, try \"help\"", other),
+            }
+
+            Ok(false)
+        };
+
+        match run() {
+            Ok(true) => break,
+            Ok(false) => (),
+            Err(err) => println!("Error: {}", err),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let input = input.read()?;
+    let mut modules = System::parse(&input)?;
+
+    if let Some(path) = &options.load_snapshot {
+        let snapshot: Snapshot = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        modules.restore(&snapshot)?;
+        println!("Starting from the snapshot in {}", path.display());
+    }
+
+    if let Some(path) = &options.emit_dot {
+        modules.save_dot(path)?;
+        println!("Module network written to {}", path.display());
+    }
+
+    let initial = modules.snapshot();
+    if let Some(presses) = options.stats {
+        modules.print_stats(presses);
+        modules.restore(&initial)?;
+    }
+    if let Some(presses) = options.throughput {
+        let start = Instant::now();
+        for _ in 0..presses {
+            modules.push_button();
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "Button pressed {} times in {:?}, {:.0} times per second",
+            presses,
+            elapsed,
+            presses as f64 / elapsed.as_secs_f64()
+        );
+        modules.restore(&initial)?;
+    }
+
+    if options.args.part.solves_part1() {
+        let mut current_low = 0;
+        let mut current_high = 0;
+        for _i in 0..1000 {
+            let (low, high) = modules.push_button();
+            current_low += low;
+            current_high += high;
+        }
+
+        let part1_answ = current_low * current_high;
+
+        if let Some(path) = &options.save_snapshot {
+            let mut writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut writer, &modules.snapshot())?;
+            writer.flush()?;
+            println!("Snapshot after 1000 presses written to {}", path.display());
+        }
+
+        println!("Part 1 answer: {}", part1_answ);
+
+        modules.restore(&initial)?;
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    let (target, pulse) = match &options.watch {
+        Some((target, pulse)) => (target.as_str(), *pulse),
+        None => (RX, Pulse::Low),
+    };
+    let (part2_answ, cycles) = modules.presses_until(target, pulse)?;
+    for cycle in &cycles {
+        println!("{}", cycle);
+    }
+    if options.watch.is_some() {
+        println!(
+            "{:?} receives a {:?} pulse after {} presses",
+            target, pulse, part2_answ
+        );
+    }
+
+    Ok(Some(part2_answ))
+}
+
+/// Combines `x ≡ a.0 (mod a.1)` and `x ≡ b.0 (mod b.1)` into a single congruence,
+/// `None` if no `x` satisfies both
+fn crt(a: (u64, u64), b: (u64, u64)) -> Option<(u64, u64)> {
+    let (a_residue, a_modulus) = (a.0 as i128, a.1 as i128);
+    let (b_residue, b_modulus) = (b.0 as i128, b.1 as i128);
+    let (g, p, _) = extended_gcd(a_modulus, b_modulus);
+    if (b_residue - a_residue) % g != 0 {
+        return None;
+    }
+
+    let modulus = a_modulus / g * b_modulus;
+    let step = (b_residue - a_residue) / g * p % (b_modulus / g);
+    let residue = (a_residue + a_modulus * step).rem_euclid(modulus);
+    Some((residue as u64, modulus as u64))
+}
+
+/// Returns `(gcd(a, b), x, y)` such that `a * x + b * y == gcd(a, b)`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// The module configuration, whose modules borrow their names from it.
+#[derive(Debug, Clone)]
+pub struct Day20 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    20,
+    Day20,
+    examples = [
+        Example::part1_only(include_str!("../example1"), 32000000),
+        Example::part1_only(include_str!("../example2"), 11687500),
+    ]
+);
+
+impl Solution for Day20 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        System::parse(input)?;
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        let mut modules = System::parse(&self.input)?;
+        let (low, high) = (0..1000).fold((0, 0), |(low, high), _| {
+            let (pushed_low, pushed_high) = modules.push_button();
+            (low + pushed_low, high + pushed_high)
+        });
+
+        Ok((low * high).into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(System::parse(&self.input)?
+            .presses_until(RX, Pulse::Low)?
+            .0
+            .into())
+    }
+}
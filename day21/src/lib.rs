@@ -0,0 +1,289 @@
+use aoc_utils::grid::Grid;
+use fnv::FnvHashSet;
+use std::{collections::VecDeque, mem, ops::RangeInclusive};
+
+mod puzzle;
+
+pub use puzzle::{parse_options, solve, Day21, Options};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    GardenPlot(bool),
+    Rock,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'S' => Ok(Self::GardenPlot(true)),
+            '.' => Ok(Self::GardenPlot(false)),
+            '#' => Ok(Self::Rock),
+            other => Err(format!("{:?} was not any of ['S', '.', '#']", other)),
+        }
+    }
+}
+
+#[inline]
+pub fn find_start_pos(grid: &Grid<Tile>) -> (usize, usize) {
+    grid.position(|tile| matches!(tile, Tile::GardenPlot(true)))
+        .expect("Could not find 'S' in the grid")
+}
+
+/// Number of plots that can be stood on after exactly `steps` steps from `start`, either within
+/// the map alone or on the map repeated infinitely in every direction.
+///
+/// On the example garden that is 16 plots after 6 steps within the map, and 16, 50, 1594 and
+/// 6536 plots after 6, 10, 50 and 100 steps on the infinite grid.
+pub fn reachable_plots(
+    grid: &Grid<Tile>,
+    start: (usize, usize),
+    steps: usize,
+    infinite: bool,
+) -> u64 {
+    if infinite {
+        count_positions_infinite(grid, start, steps)
+    } else {
+        DistanceMap::new(grid, start).reachable(steps) as u64
+    }
+}
+
+/// Number of steps it takes to reach every tile of a (finite) map from a given start.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DistanceMap {
+    /// Row-major distances, `u32::MAX` for rocks and plots that cannot be reached.
+    distances: Vec<u32>,
+}
+
+impl DistanceMap {
+    const UNREACHABLE: u32 = u32::MAX;
+
+    /// Runs a single breadth-first search from `start` over the whole map.
+    pub fn new(map: &Grid<Tile>, start: (usize, usize)) -> Self {
+        let width = map.cols();
+        let mut distances = vec![Self::UNREACHABLE; map.rows() * width];
+        distances[start.0 * width + start.1] = 0;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            let new_distance = distances[position.0 * width + position.1] + 1;
+            for new_pos in map.neighbours4(position) {
+                if let Tile::GardenPlot(_) = map[new_pos] {
+                    let distance = &mut distances[new_pos.0 * width + new_pos.1];
+                    if *distance == Self::UNREACHABLE {
+                        *distance = new_distance;
+                        queue.push_back(new_pos);
+                    }
+                }
+            }
+        }
+
+        Self { distances }
+    }
+
+    /// Number of plots that can be stood on after exactly `steps` steps: those at most that
+    /// far away and with the same parity, since stepping back and forth wastes 2 steps.
+    #[inline]
+    pub fn reachable(&self, steps: usize) -> usize {
+        self.distances
+            .iter()
+            .filter(|&&distance| {
+                distance != Self::UNREACHABLE
+                    && distance as usize <= steps
+                    && distance as usize % 2 == steps % 2
+            })
+            .count()
+    }
+}
+
+/// Counts the plots reachable in exactly `steps` steps on the map repeated infinitely in
+/// every direction.
+#[inline]
+pub fn count_positions_infinite(map: &Grid<Tile>, start: (usize, usize), steps: usize) -> u64 {
+    reachable_counts_infinite(map, start, steps)[steps]
+}
+
+/// Row-major bitset over a rectangle of tiles, one bit per tile.
+///
+/// Every row is padded with an empty word on both sides, so that words can always borrow the
+/// bits shifted in from their neighbours.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BitGrid {
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    #[inline]
+    pub(crate) fn new(height: usize, width: usize) -> Self {
+        let words_per_row = width.div_ceil(64) + 2;
+        Self {
+            words_per_row,
+            words: vec![0; height * words_per_row],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set(&mut self, (y, x): (usize, usize)) {
+        self.words[y * self.words_per_row + x / 64 + 1] |= 1 << (x % 64);
+    }
+
+    /// Moves every set tile of the rows around `y` one step in each direction into row `y` of
+    /// `next`, keeping only the tiles also set in `open`, and returns how many tiles are set in it.
+    ///
+    /// Only the words holding `columns` are written: every tile set around the row must be inside
+    /// of them with a tile to spare, and nothing outside of them may be set in `next`.
+    pub(crate) fn step_row_into(
+        &self,
+        open: &BitGrid,
+        next: &mut BitGrid,
+        y: usize,
+        columns: RangeInclusive<usize>,
+    ) -> u64 {
+        let words_per_row = self.words_per_row;
+        let row_start = y * words_per_row;
+        let mut count = 0;
+        for index in row_start + columns.start() / 64 + 1..=row_start + columns.end() / 64 + 1 {
+            // bit `x` of a word is column `x`, so moving east shifts towards the high bits
+            let from_west = self.words[index] << 1 | self.words[index - 1] >> 63;
+            let from_east = self.words[index] >> 1 | self.words[index + 1] << 63;
+            let vertical = self.words[index - words_per_row] | self.words[index + words_per_row];
+
+            let word = (vertical | from_west | from_east) & open.words[index];
+            next.words[index] = word;
+            count += u64::from(word.count_ones());
+        }
+
+        count
+    }
+}
+
+/// Walks the map repeated infinitely in every direction, returning for every step count up to
+/// `max_steps` how many plots are reachable in exactly that many steps.
+///
+/// The plane is cut down to the square the walk can reach, the plots and the positions after
+/// each step being kept as bitsets so that a whole word of tiles moves at once.
+pub fn reachable_counts_infinite(
+    map: &Grid<Tile>,
+    start: (usize, usize),
+    max_steps: usize,
+) -> Vec<u64> {
+    let (height, width) = (map.rows() as i64, map.cols() as i64);
+
+    // the start sits in the middle, with a border of rocks one tile past the furthest reach
+    let center = max_steps + 1;
+    let side = 2 * center + 1;
+    let mut open = BitGrid::new(side, side);
+    for y in 1..side - 1 {
+        let row = map.row((start.0 as i64 + y as i64 - center as i64).rem_euclid(height) as usize);
+        for x in 1..side - 1 {
+            let column = (start.1 as i64 + x as i64 - center as i64).rem_euclid(width);
+            if row[column as usize] != Tile::Rock {
+                open.set((y, x));
+            }
+        }
+    }
+
+    let mut positions = BitGrid::new(side, side);
+    positions.set((center, center));
+    let mut next = BitGrid::new(side, side);
+
+    let mut counts = Vec::with_capacity(max_steps + 1);
+    counts.push(1);
+    for step in 1..=max_steps {
+        // only the diamond `step` steps around the start can be reached
+        let mut count = 0;
+        for y in center - step..=center + step {
+            let reach = step - y.abs_diff(center);
+            count += positions.step_row_into(&open, &mut next, y, center - reach..=center + reach);
+        }
+        counts.push(count);
+        mem::swap(&mut positions, &mut next);
+    }
+
+    counts
+}
+
+/// Same as [`reachable_counts_infinite`] with a breadth-first search keeping every plot seen in
+/// a hash set, only kept around to benchmark the bitsets against.
+pub fn reachable_counts_infinite_hashed(
+    map: &Grid<Tile>,
+    start: (usize, usize),
+    max_steps: usize,
+) -> Vec<u64> {
+    let height = map.rows() as i64;
+    let is_plot = |(y, x): (i64, i64)| {
+        let row = map.row(y.rem_euclid(height) as usize);
+        row[x.rem_euclid(row.len() as i64) as usize] != Tile::Rock
+    };
+
+    let start = (start.0 as i64, start.1 as i64);
+    let mut seen = FnvHashSet::default();
+    seen.insert(start);
+    let mut frontier = vec![start];
+
+    // a plot first reached after `n` steps stays reachable after `n + 2`, `n + 4`, ...
+    let mut counts = Vec::with_capacity(max_steps + 1);
+    counts.push(1);
+    for step in 1..=max_steps {
+        let mut next = Vec::new();
+        for (y, x) in frontier {
+            for position in [(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)] {
+                if is_plot(position) && seen.insert(position) {
+                    next.push(position);
+                }
+            }
+        }
+
+        let same_parity = if step >= 2 { counts[step - 2] } else { 0 };
+        counts.push(same_parity + next.len() as u64);
+        frontier = next;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> (Grid<Tile>, (usize, usize)) {
+        let grid = Grid::from_lines(include_str!("../example").lines(), Tile::try_from)
+            .expect("the example parses");
+        let start = find_start_pos(&grid);
+        (grid, start)
+    }
+
+    #[test]
+    fn finite_map() {
+        let (grid, start) = example();
+        assert_eq!(reachable_plots(&grid, start, 1, false), 2);
+        assert_eq!(reachable_plots(&grid, start, 2, false), 4);
+        assert_eq!(reachable_plots(&grid, start, 3, false), 6);
+        assert_eq!(reachable_plots(&grid, start, 6, false), 16);
+    }
+
+    #[test]
+    fn infinite_map() {
+        let (grid, start) = example();
+        for (steps, plots) in [(6, 16), (10, 50), (50, 1594), (100, 6536), (500, 167004)] {
+            assert_eq!(
+                reachable_plots(&grid, start, steps, true),
+                plots,
+                "after {} steps",
+                steps
+            );
+        }
+    }
+
+    #[test]
+    fn bitsets_match_hash_set() {
+        let (grid, start) = example();
+        assert_eq!(
+            reachable_counts_infinite(&grid, start, 100),
+            reachable_counts_infinite_hashed(&grid, start, 100)
+        );
+    }
+}
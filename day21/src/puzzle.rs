@@ -0,0 +1,493 @@
+use aoc_utils::{
+    grid::Grid,
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::{Parser, ValueEnum};
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{error::Error, fmt, time::Instant};
+
+use crate::{
+    find_start_pos, reachable_counts_infinite, reachable_counts_infinite_hashed, reachable_plots,
+    DistanceMap, Tile,
+};
+
+/// Step count asked for by the puzzle in part 2.
+const PART2_STEPS_REQUIRED: usize = 26501365;
+
+/// Largest step count the infinite grid is simulated for, by the fallback simulation as well as
+/// for the samples of the quadratic fit, its bitsets growing with the square of the steps.
+const SIMULATION_LIMIT: usize = 2000;
+
+/// Number of whole maps walked before sampling the quadratic, so the fit only sees the
+/// steady growth and not the first few maps being filled in.
+const FIT_WARMUP_PERIODS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+enum Algorithm {
+    /// split the diamond of maps into full maps, corners and diagonals and count each kind once
+    #[default]
+    Geometric,
+    /// simulate three step counts a map apart and extrapolate the quadratic they lie on
+    Fit,
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<21>,
+    /// The way part 2 counts the plots reached
+    #[arg(long = "algo", value_enum, default_value_t)]
+    algorithm: Algorithm,
+    /// Print what every kind of map adds to the part 2 answer of the geometric shortcut
+    #[arg(long)]
+    breakdown: bool,
+    /// Time the simulation of the infinite grid over that many steps instead of solving
+    #[arg(long, value_name = "STEPS")]
+    bench_simulation: Option<usize>,
+    /// Number of steps to walk in part 2, the puzzle's 26501365 when not given
+    #[arg(long, value_name = "STEPS")]
+    steps: Option<usize>,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let steps = options.steps.unwrap_or(PART2_STEPS_REQUIRED);
+
+    let grid = parse_grid(&input.read()?)?;
+
+    if let Some(steps) = options.bench_simulation {
+        return bench_simulation(&grid, steps).map(Some);
+    }
+
+    if options.args.part.solves_part1() {
+        println!("Part 1 answer: {}", solve_steps_part1(&grid, 64));
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    Ok(Some(part_2(&grid, steps, options)?))
+}
+
+/// Parses the map, making sure it has a start, which everything below relies on.
+fn parse_grid(input: &str) -> Result<Grid<Tile>, Box<dyn Error>> {
+    let grid = Grid::from_lines(
+        input.lines().map(str::trim).filter(|line| !line.is_empty()),
+        Tile::try_from,
+    )?;
+    if !grid
+        .iter()
+        .any(|tile| matches!(tile, Tile::GardenPlot(true)))
+    {
+        return Err("Could not find 'S' in the map".into());
+    }
+
+    Ok(grid)
+}
+
+/// Times the bitset simulation of the infinite grid against the hash set one it replaced,
+/// returning the number of plots reachable after `steps` steps both agree on.
+fn bench_simulation(grid: &Grid<Tile>, steps: usize) -> Result<u64, Box<dyn Error>> {
+    let start_pos = find_start_pos(grid);
+
+    let start = Instant::now();
+    let bitsets = reachable_counts_infinite(grid, start_pos, steps);
+    let bitsets_time = start.elapsed();
+
+    let start = Instant::now();
+    let hashed = reachable_counts_infinite_hashed(grid, start_pos, steps);
+    let hashed_time = start.elapsed();
+
+    println!("Time to simulate with bitsets: {:?}", bitsets_time);
+    println!("Time to simulate with a hash set: {:?}", hashed_time);
+    if bitsets != hashed {
+        return Err("The bitset and hash set simulations disagree".into());
+    }
+
+    Ok(bitsets[steps])
+}
+
+#[inline]
+fn solve_steps_part1(grid: &Grid<Tile>, steps: u32) -> u64 {
+    reachable_plots(grid, find_start_pos(grid), steps as usize, false)
+}
+
+/// A property of the garden that the geometric shortcut of [`solve_part2`] relies on, the
+/// clear lanes being also what makes the growth followed by [`fit_quadratic`] exactly quadratic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part2Assumption {
+    /// The map has to be a square of odd side.
+    OddSquare { rows: usize, columns: usize },
+    /// 'S' has to sit right in the middle of the map.
+    CenteredStart { start: (usize, usize), size: usize },
+    /// The row and column of 'S' must not contain any rock.
+    ClearStartLines { rock: (usize, usize) },
+    /// The outer rows and columns must not contain any rock.
+    ClearBorder { rock: (usize, usize) },
+    /// The walk has to end exactly on the edge of a map, an even number of maps away.
+    StepsOnEdge { steps: usize, size: usize },
+}
+
+impl fmt::Display for Part2Assumption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddSquare { rows, columns } => write!(
+                f,
+                "the map is {}x{} but has to be a square with an odd side",
+                rows, columns
+            ),
+            Self::CenteredStart { start, size } => write!(
+                f,
+                "'S' is at {:?} but has to be in the middle of the map ({:?})",
+                start,
+                (size / 2, size / 2)
+            ),
+            Self::ClearStartLines { rock } => write!(
+                f,
+                "the row and column of 'S' have to be free of rocks, found one at {:?}",
+                rock
+            ),
+            Self::ClearBorder { rock } => write!(
+                f,
+                "the border of the map has to be free of rocks, found one at {:?}",
+                rock
+            ),
+            Self::StepsOnEdge { steps, size } => write!(
+                f,
+                "{} steps does not end on the edge of a map, it has to be {} plus an even multiple (at least 2) of {}",
+                steps,
+                size / 2,
+                size
+            ),
+        }
+    }
+}
+
+impl Error for Part2Assumption {}
+
+/// Checks every assumption [`solve_part2`] makes, returning the first one that does not hold.
+fn check_part2_assumptions(map: &Grid<Tile>, steps: usize) -> Result<(), Part2Assumption> {
+    let size = map.rows();
+    if map.cols() != size {
+        return Err(Part2Assumption::OddSquare {
+            rows: size,
+            columns: map.cols(),
+        });
+    }
+    if size.is_multiple_of(2) {
+        return Err(Part2Assumption::OddSquare {
+            rows: size,
+            columns: size,
+        });
+    }
+
+    let start = find_start_pos(map);
+    let middle = size / 2;
+    if start != (middle, middle) {
+        return Err(Part2Assumption::CenteredStart { start, size });
+    }
+
+    check_clear_lanes(map)?;
+
+    let maps_walked = steps / size;
+    if steps % size != middle || maps_walked < 2 || !maps_walked.is_multiple_of(2) {
+        return Err(Part2Assumption::StepsOnEdge { steps, size });
+    }
+
+    Ok(())
+}
+
+/// Checks that the row and column of 'S' as well as the border of the map are free of rocks,
+/// so that every map of the infinite grid is entered at the distance of its closest point.
+fn check_clear_lanes(map: &Grid<Tile>) -> Result<(), Part2Assumption> {
+    let (height, width) = (map.rows(), map.cols());
+    let (start_row, start_column) = find_start_pos(map);
+
+    let find_rock = |mut positions: Box<dyn Iterator<Item = (usize, usize)>>| {
+        positions.find(|&(y, x)| map[(y, x)] == Tile::Rock)
+    };
+    let start_lines = (0..width)
+        .map(|x| (start_row, x))
+        .chain((0..height).map(|y| (y, start_column)));
+    if let Some(rock) = find_rock(Box::new(start_lines)) {
+        return Err(Part2Assumption::ClearStartLines { rock });
+    }
+    let border = (0..width)
+        .flat_map(|x| [(0, x), (height - 1, x)])
+        .chain((0..height).flat_map(|y| [(y, 0), (y, width - 1)]));
+    if let Some(rock) = find_rock(Box::new(border)) {
+        return Err(Part2Assumption::ClearBorder { rock });
+    }
+
+    Ok(())
+}
+
+/// Solves part 2 with the chosen algorithm.
+///
+/// When the garden does not allow the geometric shortcut (an off-center start, a rectangular
+/// map, ...), it is simulated if the step count is small enough and extrapolated with the
+/// quadratic fit otherwise, as long as the lanes the fit relies on are clear. The fit is cross
+/// checked against the shortcut whenever the latter applies.
+fn part_2(map: &Grid<Tile>, steps: usize, options: &Options) -> Result<u64, Box<dyn Error>> {
+    let assumptions = check_part2_assumptions(map, steps);
+    match (options.algorithm, assumptions) {
+        (Algorithm::Geometric, Ok(())) if options.breakdown => {
+            let regions = part2_regions(map, steps);
+            print_breakdown(&regions);
+            Ok(regions.iter().map(Region::contribution).sum())
+        }
+        (Algorithm::Geometric, Ok(())) => Ok(solve_part2(map, steps)),
+        (Algorithm::Geometric, Err(assumption)) if steps <= SIMULATION_LIMIT => {
+            println!(
+                "Cannot use the geometric shortcut ({}), simulating the infinite grid instead",
+                assumption
+            );
+            Ok(reachable_plots(map, find_start_pos(map), steps, true))
+        }
+        (Algorithm::Geometric, Err(assumption)) => match check_clear_lanes(map) {
+            Ok(()) => {
+                println!(
+                    "Cannot use the geometric shortcut ({}), extrapolating a quadratic fit instead",
+                    assumption
+                );
+                fit_quadratic(map, steps)
+            }
+            Err(lanes) if lanes == assumption => Err(format!(
+                "Cannot use the geometric shortcut nor an exact quadratic fit: {}; and {} steps are too many to simulate (at most {})",
+                lanes, steps, SIMULATION_LIMIT
+            )
+            .into()),
+            Err(lanes) => Err(format!(
+                "Cannot use the geometric shortcut: {}; the quadratic fit would not be exact either since {}; and {} steps are too many to simulate (at most {})",
+                assumption, lanes, steps, SIMULATION_LIMIT
+            )
+            .into()),
+        },
+        (Algorithm::Fit, assumptions) => {
+            let fitted = fit_quadratic(map, steps)?;
+            if assumptions.is_ok() {
+                let geometric = solve_part2(map, steps);
+                if geometric != fitted {
+                    return Err(format!(
+                        "The quadratic fit found {} plots but the geometric shortcut found {}",
+                        fitted, geometric
+                    )
+                    .into());
+                }
+                println!("The geometric shortcut agrees with the quadratic fit");
+            } else if let Err(lanes) = check_clear_lanes(map) {
+                println!("Warning: the quadratic fit may not be exact since {}", lanes);
+            }
+
+            Ok(fitted)
+        }
+    }
+}
+
+/// Counts the plots reachable in exactly `steps` steps on the map repeated infinitely in
+/// every direction.
+///
+/// Once the walk has spread over a few maps, the count at `offset + k * period` steps grows as
+/// a quadratic of `k`, so three simulated samples are enough to extrapolate any step count.
+/// The period spans two maps so that the parity of the maps being entered stays the same.
+///
+/// Fails rather than simulating more than [`SIMULATION_LIMIT`] steps, which a rectangular map
+/// quickly asks for since its period is twice the least common multiple of its sides.
+fn fit_quadratic(map: &Grid<Tile>, steps: usize) -> Result<u64, Box<dyn Error>> {
+    let (height, width) = (map.rows(), map.cols());
+    let period = 2 * (height / gcd(height, width) * width);
+    let start = find_start_pos(map);
+
+    let (offset, periods) = (steps % period, steps / period);
+    if periods < FIT_WARMUP_PERIODS + 2 {
+        if steps > SIMULATION_LIMIT {
+            return Err(format!(
+                "{} steps are too few for the quadratic fit (its period is {}) and too many to simulate (at most {})",
+                steps, period, SIMULATION_LIMIT
+            )
+            .into());
+        }
+
+        return Ok(reachable_plots(map, start, steps, true));
+    }
+
+    let first_sample = offset + FIT_WARMUP_PERIODS * period;
+    let simulated = first_sample + 2 * period;
+    if simulated > SIMULATION_LIMIT {
+        return Err(format!(
+            "The quadratic fit of a {}x{} map would simulate {} steps (a period of {}), at most {} are allowed",
+            height, width, simulated, period, SIMULATION_LIMIT
+        )
+        .into());
+    }
+
+    let counts = reachable_counts_infinite(map, start, simulated);
+    let [a, b, c] = [0, 1, 2].map(|i| counts[first_sample + i * period] as i128);
+
+    let k = (periods - FIT_WARMUP_PERIODS) as i128;
+    let first_difference = b - a;
+    let second_difference = c - 2 * b + a;
+    Ok((a + k * first_difference + k * (k - 1) / 2 * second_difference) as u64)
+}
+
+#[inline]
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// One kind of map making up the diamond walked in part 2, along with what it adds to the total.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Region {
+    name: &'static str,
+    /// Where the walk enters maps of this kind
+    entry: (usize, usize),
+    /// Steps left when entering such a map
+    steps: usize,
+    /// How many maps of this kind the diamond contains
+    maps: usize,
+    /// Plots reachable within one such map
+    plots: usize,
+}
+
+impl Region {
+    #[inline]
+    pub(crate) fn contribution(&self) -> u64 {
+        (self.maps * self.plots) as u64
+    }
+}
+
+/// Splits the diamond of maps walked in `steps` steps into full maps, the four tips and the
+/// small and big diagonal maps along its edges, then counts the plots of every kind.
+fn part2_regions(map: &Grid<Tile>, steps: usize) -> Vec<Region> {
+    let starting_point = find_start_pos(map);
+
+    let map_size = map.rows();
+    let grid_size = steps / map_size - 1;
+
+    let even_maps_in_grid = (grid_size.div_ceil(2) * 2).pow(2);
+    let odd_maps_in_grid = (grid_size / 2 * 2 + 1).pow(2);
+
+    let region = |name, entry, steps, maps| Region {
+        name,
+        entry,
+        steps,
+        maps,
+        plots: 0,
+    };
+    let (last, middle_row, middle_column) = (map_size - 1, starting_point.0, starting_point.1);
+    let (small, big) = (map_size / 2 - 1, map_size * 3 / 2 - 1);
+    let mut regions = vec![
+        region(
+            "full odd",
+            starting_point,
+            map_size * 2 + 1,
+            odd_maps_in_grid,
+        ),
+        region("full even", starting_point, map_size * 2, even_maps_in_grid),
+        region("corner top", (last, middle_column), last, 1),
+        region("corner right", (middle_row, 0), last, 1),
+        region("corner bottom", (0, middle_column), last, 1),
+        region("corner left", (middle_row, last), last, 1),
+        region("small diag top right", (last, 0), small, grid_size + 1),
+        region("small diag bottom right", (0, 0), small, grid_size + 1),
+        region("small diag bottom left", (0, last), small, grid_size + 1),
+        region("small diag top left", (last, last), small, grid_size + 1),
+        region("big diag top right", (last, 0), big, grid_size),
+        region("big diag bottom right", (0, 0), big, grid_size),
+        region("big diag bottom left", (0, last), big, grid_size),
+        region("big diag top left", (last, last), big, grid_size),
+    ];
+
+    // regions entered from the same point (e.g. both diagonals of a quadrant) share a distance map
+    let distances: FnvHashMap<_, _> = regions
+        .iter()
+        .map(|region| region.entry)
+        .unique()
+        .collect_vec()
+        .into_par_iter()
+        .map(|entry| (entry, DistanceMap::new(map, entry)))
+        .collect();
+
+    for region in &mut regions {
+        region.plots = distances[&region.entry].reachable(region.steps);
+    }
+
+    regions
+}
+
+#[inline]
+fn solve_part2(map: &Grid<Tile>, steps: usize) -> u64 {
+    part2_regions(map, steps)
+        .iter()
+        .map(Region::contribution)
+        .sum()
+}
+
+fn print_breakdown(regions: &[Region]) {
+    println!(
+        "{:<24} {:>12} {:>8} {:>16} {:>8} {:>20}",
+        "region", "entry", "steps", "maps", "plots", "contribution"
+    );
+    for region in regions {
+        println!(
+            "{:<24} {:>12} {:>8} {:>16} {:>8} {:>20}",
+            region.name,
+            format!("{:?}", region.entry),
+            region.steps,
+            region.maps,
+            region.plots,
+            region.contribution()
+        );
+    }
+}
+
+/// The garden map, checked to be a rectangle with a start.
+#[derive(Debug, Clone)]
+pub struct Day21 {
+    grid: Grid<Tile>,
+    /// 64, but only 6 in the example
+    part1_steps: u32,
+}
+
+aoc_utils::register_day!(
+    21,
+    Day21,
+    examples = [Example::part1_only(include_str!("../example"), 16)]
+);
+
+impl Solution for Day21 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            grid: parse_grid(input)?,
+            part1_steps: 64,
+        })
+    }
+
+    fn parse_example(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            grid: parse_grid(input)?,
+            part1_steps: 6,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(solve_steps_part1(&self.grid, self.part1_steps).into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(part_2(&self.grid, PART2_STEPS_REQUIRED, &Options::default())?.into())
+    }
+}
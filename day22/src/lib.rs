@@ -0,0 +1,499 @@
+use aoc_utils::{
+    dot::{Attributes, ToDot},
+    error::{parse_field, parse_lines, AocError},
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::Parser;
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{error::Error, fmt, ops, path::PathBuf, str::FromStr};
+use tracing::{debug, trace};
+
+type PositionMember = u16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Position {
+    x: PositionMember,
+    y: PositionMember,
+    z: PositionMember,
+}
+
+impl Position {
+    #[inline]
+    fn create_x_range(&self, other: &Position) -> ops::RangeInclusive<PositionMember> {
+        if self.x > other.x {
+            other.x..=self.x
+        } else {
+            self.x..=other.x
+        }
+    }
+
+    #[inline]
+    fn create_y_range(&self, other: &Position) -> ops::RangeInclusive<PositionMember> {
+        if self.y > other.y {
+            other.y..=self.y
+        } else {
+            self.y..=other.y
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = s.split(',');
+        let mut next_field = || {
+            split
+                .next()
+                .ok_or_else(|| AocError::parse(s, format!("{:?} did not contain 2 ','", s)))
+                .and_then(parse_field)
+        };
+        Ok(Self {
+            x: next_field()?,
+            y: next_field()?,
+            z: next_field()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Brick {
+    brick_ends: (Position, Position),
+}
+
+impl Brick {
+    #[inline]
+    fn create_x_range(&self) -> ops::RangeInclusive<PositionMember> {
+        let (left, right) = &self.brick_ends;
+        left.create_x_range(right)
+    }
+
+    #[inline]
+    fn create_y_range(&self) -> ops::RangeInclusive<PositionMember> {
+        let (left, right) = &self.brick_ends;
+        left.create_y_range(right)
+    }
+
+    #[inline]
+    fn lower_z_position(&self) -> PositionMember {
+        let (left, right) = &self.brick_ends;
+        left.z.min(right.z)
+    }
+
+    #[inline]
+    fn higher_z_position(&self) -> PositionMember {
+        let (left, right) = &self.brick_ends;
+        left.z.max(right.z)
+    }
+
+    #[inline]
+    fn sort_by_lower_height_key(&self) -> PositionMember {
+        self.lower_z_position()
+    }
+
+    #[inline]
+    fn fall_to_lower_z(&mut self, target_lower_z: PositionMember) {
+        let (ref mut left, ref mut right) = self.brick_ends;
+        let z_diff = left.z.abs_diff(right.z);
+        if left.z > right.z {
+            right.z = target_lower_z;
+            left.z = target_lower_z + z_diff;
+        } else {
+            left.z = target_lower_z;
+            right.z = target_lower_z + z_diff;
+        }
+    }
+
+    /// every (x, y) column the brick covers, i.e. what it looks like from above
+    #[inline]
+    fn footprint(&self) -> impl Iterator<Item = (PositionMember, PositionMember)> + Clone {
+        self.create_x_range()
+            .cartesian_product(self.create_y_range())
+    }
+}
+
+/// Index of a brick in the [`SupportGraph`]
+type BrickIndex = usize;
+
+/// The pile seen from above: for every (x, y) column, the highest z occupied and the brick occupying it.
+#[derive(Debug, Clone, Default)]
+struct HeightMap {
+    columns: FnvHashMap<(PositionMember, PositionMember), (PositionMember, BrickIndex)>,
+}
+
+impl HeightMap {
+    /// Lets the brick fall onto the pile, returning it once settled along with the bricks it rests on.
+    fn drop_brick(&mut self, mut brick: Brick, index: BrickIndex) -> (Brick, Vec<BrickIndex>) {
+        let footprint = brick.footprint();
+        let top = footprint
+            .clone()
+            .filter_map(|column| self.columns.get(&column).map(|&(z, _)| z))
+            .max()
+            .unwrap_or(0);
+        trace!(%brick, from = brick.lower_z_position(), to = top + 1, "brick falls");
+        brick.fall_to_lower_z(top + 1);
+
+        // the ground (z = 0) is not in the map, so bricks resting on it have no supports
+        let supports = footprint
+            .clone()
+            .filter_map(|column| match self.columns.get(&column) {
+                Some(&(z, below)) if z == top => Some(below),
+                _ => None,
+            })
+            .sorted_unstable()
+            .dedup()
+            .collect();
+
+        let new_top = brick.higher_z_position();
+        for column in footprint {
+            self.columns.insert(column, (new_top, index));
+        }
+
+        (brick, supports)
+    }
+}
+
+/// The settled pile, bricks being indexed in the order they came to rest: a brick always comes
+/// after the ones it rests on.
+#[derive(Debug, Clone, Default)]
+struct SupportGraph {
+    bricks: Vec<Brick>,
+    /// for every brick, the line of the snapshot it came from
+    lines: Vec<usize>,
+    /// for every brick, the bricks resting directly on top of it
+    supports: Vec<Vec<BrickIndex>>,
+    /// for every brick, the bricks it rests directly on (none when it is on the ground)
+    supported_by: Vec<Vec<BrickIndex>>,
+}
+
+/// The support graph, each brick pointing at the bricks resting on it. Bricks lying on the ground are drawn as
+/// boxes and the ones that can be safely removed are filled in green.
+impl ToDot for SupportGraph {
+    type Node = BrickIndex;
+    type Edge = ();
+
+    fn nodes(&self) -> impl Iterator<Item = BrickIndex> {
+        0..self.len()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (BrickIndex, BrickIndex, ())> {
+        self.supports
+            .iter()
+            .enumerate()
+            .flat_map(|(index, above)| above.iter().map(move |&above| (index, above, ())))
+    }
+
+    fn node_id(&self, &index: &BrickIndex) -> String {
+        self.label(index)
+    }
+
+    fn node_label(&self, &index: &BrickIndex) -> Option<String> {
+        Some(format!("{}\n{}", self.label(index), self.bricks[index]))
+    }
+
+    fn node_attributes(&self, &index: &BrickIndex) -> Attributes {
+        let shape = if self.supported_by[index].is_empty() {
+            "box"
+        } else {
+            "ellipse"
+        };
+        let mut attributes = vec![("shape", shape.to_owned())];
+        if self.can_safely_remove(index) {
+            attributes.push(("style", "filled".to_owned()));
+            attributes.push(("fillcolor", "palegreen".to_owned()));
+        }
+
+        attributes
+    }
+
+    fn graph_attributes(&self) -> Attributes {
+        vec![("layout", "dot".to_owned()), ("rankdir", "BT".to_owned())]
+    }
+}
+
+impl SupportGraph {
+    fn settle(raw_bricks: Vec<Brick>) -> Self {
+        let mut graph = Self::default();
+        let mut height_map = HeightMap::default();
+        let falling_order = raw_bricks
+            .into_iter()
+            .enumerate()
+            .sorted_by_key(|(_, brick)| brick.sort_by_lower_height_key());
+        for (index, (line, brick)) in falling_order.enumerate() {
+            let (brick, below) = height_map.drop_brick(brick, index);
+            debug!(line = line + 1, %brick, resting_on = below.len(), "brick settled");
+            for &support in &below {
+                graph.supports[support].push(index);
+            }
+
+            graph.bricks.push(brick);
+            graph.lines.push(line);
+            graph.supports.push(vec![]);
+            graph.supported_by.push(below);
+        }
+
+        graph
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.bricks.len()
+    }
+
+    /// name of the brick in the puzzle's own style: `A` to `Z`, then `AA`, `AB`, ... following the
+    /// order of the snapshot
+    fn label(&self, index: BrickIndex) -> String {
+        let mut line = self.lines[index];
+        let mut label = vec![];
+        loop {
+            label.push(b'A' + (line % 26) as u8);
+            if line < 26 {
+                break;
+            }
+            line = line / 26 - 1;
+        }
+
+        label.iter().rev().map(|&letter| letter as char).collect()
+    }
+
+    #[inline]
+    fn find_label(&self, label: &str) -> Option<BrickIndex> {
+        (0..self.len()).find(|&index| self.label(index) == label)
+    }
+
+    /// Prints which bricks fall, and in what order, if the brick labeled `label` is removed.
+    fn report_removal(&self, label: &str) -> Result<(), Box<dyn Error>> {
+        let index = self
+            .find_label(label)
+            .ok_or_else(|| format!("No brick is labeled {:?}", label))?;
+
+        let fallen = self.falling_bricks(index);
+        if fallen.is_empty() {
+            println!(
+                "Removing {} ({}) makes no other brick fall",
+                label, self.bricks[index]
+            );
+            return Ok(());
+        }
+
+        println!(
+            "Removing {} ({}) makes {} other brick(s) fall, in this order:",
+            label,
+            self.bricks[index],
+            fallen.len()
+        );
+        for (rank, &brick) in fallen.iter().enumerate() {
+            let below = self.supported_by[brick]
+                .iter()
+                .map(|&below| self.label(below))
+                .join(", ");
+            println!(
+                "{:>6}. {} ({}), which rested on {}",
+                rank + 1,
+                self.label(brick),
+                self.bricks[brick],
+                below
+            );
+        }
+
+        Ok(())
+    }
+
+    /// a brick can go if everything it supports rests on at least one other brick
+    #[inline]
+    fn can_safely_remove(&self, index: BrickIndex) -> bool {
+        self.supports[index]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// how many bricks can go without any other falling (part 1)
+    fn safely_removable_count(&self) -> usize {
+        (0..self.len())
+            .filter(|&brick| self.can_safely_remove(brick))
+            .count()
+    }
+
+    /// the sum, over every brick, of the other bricks falling when it is removed (part 2)
+    fn chain_reactions_total(&self) -> usize {
+        (0..self.len())
+            .into_par_iter()
+            .map_init(
+                || self.fall_buffers(),
+                |buffers, brick| self.falling_bricks_in(brick, buffers).len(),
+            )
+            .sum()
+    }
+
+    #[inline]
+    fn fall_buffers(&self) -> FallBuffers {
+        FallBuffers {
+            remaining_supports: self.supported_by.iter().map(Vec::len).collect(),
+            touched: vec![],
+            fallen: vec![],
+        }
+    }
+
+    /// the other bricks falling when `index` is removed, in the order they start to fall
+    ///
+    /// Every brick keeps a count of its supports still standing, a brick falling once the last
+    /// one underneath it is gone. The buffers are left as they were given, ready for the next brick.
+    fn falling_bricks_in<'b>(
+        &self,
+        index: BrickIndex,
+        buffers: &'b mut FallBuffers,
+    ) -> &'b [BrickIndex] {
+        let FallBuffers {
+            remaining_supports,
+            touched,
+            fallen,
+        } = buffers;
+        for &brick in touched.iter() {
+            remaining_supports[brick] = self.supported_by[brick].len();
+        }
+        touched.clear();
+        fallen.clear();
+
+        // the fallen bricks double as the queue of bricks whose weight is taken off the ones above
+        let mut next = 0;
+        let mut brick = index;
+        loop {
+            for &above in &self.supports[brick] {
+                if remaining_supports[above] == self.supported_by[above].len() {
+                    touched.push(above);
+                }
+
+                remaining_supports[above] -= 1;
+                if remaining_supports[above] == 0 {
+                    fallen.push(above);
+                }
+            }
+
+            let Some(&falling) = fallen.get(next) else {
+                break;
+            };
+            brick = falling;
+            next += 1;
+        }
+
+        fallen
+    }
+
+    #[inline]
+    fn falling_bricks(&self, index: BrickIndex) -> Vec<BrickIndex> {
+        self.falling_bricks_in(index, &mut self.fall_buffers())
+            .to_vec()
+    }
+}
+
+/// Scratch space of [`SupportGraph::falling_bricks_in`], so that a worker going through many
+/// bricks only allocates it once.
+#[derive(Debug, Clone, Default)]
+struct FallBuffers {
+    /// for every brick, how many of its supports are still standing
+    remaining_supports: Vec<usize>,
+    /// bricks whose count was changed by the last removal, to be restored by the next one
+    touched: Vec<BrickIndex>,
+    fallen: Vec<BrickIndex>,
+}
+
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (left, right) = &self.brick_ends;
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            left.x, left.y, left.z, right.x, right.y, right.z
+        )
+    }
+}
+
+impl FromStr for Brick {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = s
+            .split_once('~')
+            .ok_or_else(|| AocError::parse(s, format!("{:?} could not be split on '~'", s)))?;
+        Ok(Self {
+            brick_ends: (left.parse()?, right.parse()?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<22>,
+    /// Write the support graph of the settled bricks to this file in the DOT format
+    #[arg(long, value_name = "PATH")]
+    emit_dot: Option<PathBuf>,
+    /// Report the chain reaction of removing the brick with this label, e.g. `A`
+    #[arg(long = "remove", value_name = "LABEL")]
+    removals: Vec<String>,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<usize>, Box<dyn Error>> {
+    let input = input.read()?;
+    let raw_bricks: Vec<Brick> = parse_lines(&input, str::parse)?;
+
+    let graph = SupportGraph::settle(raw_bricks);
+
+    if let Some(path) = &options.emit_dot {
+        graph.save_dot(path)?;
+        println!("Support graph written to {}", path.display());
+    }
+
+    for label in &options.removals {
+        graph.report_removal(label)?;
+    }
+
+    if options.args.part.solves_part1() {
+        println!("Part 1 answer: {}", graph.safely_removable_count());
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    Ok(Some(graph.chain_reactions_total()))
+}
+
+/// The bricks of the snapshot, once settled.
+#[derive(Debug, Clone)]
+pub struct Day22 {
+    graph: SupportGraph,
+}
+
+aoc_utils::register_day!(
+    22,
+    Day22,
+    examples = [Example::both(include_str!("../example"), 5, 7)]
+);
+
+impl Solution for Day22 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let raw_bricks: Vec<Brick> = parse_lines(input, str::parse)?;
+        Ok(Self {
+            graph: SupportGraph::settle(raw_bricks),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self.graph.safely_removable_count().into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(self.graph.chain_reactions_total().into())
+    }
+}
@@ -0,0 +1,1174 @@
+use aoc_utils::{
+    dot::ToDot,
+    geometry::Direction,
+    grid::{directed_neighbours4, Grid},
+    input::{Args, InputSource},
+    progress::Progress,
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::{Parser, ValueEnum};
+use core::fmt;
+use fnv::{FnvHashMap, FnvHashSet};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{
+    cmp,
+    collections::{hash_map::Entry, VecDeque},
+    error::Error,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Tile {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forest => write!(f, "#"),
+            Self::Path => write!(f, "."),
+            Self::Slope(direction) => match direction {
+                Direction::North => write!(f, "^"),
+                Direction::South => write!(f, "v"),
+                Direction::East => write!(f, ">"),
+                Direction::West => write!(f, "<"),
+            },
+        }
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Path),
+            '#' => Ok(Self::Forest),
+            '^' | '>' | 'v' | '<' => Direction::try_from(value).map(Self::Slope),
+            other => Err(format!("Unrecognized tile char {:?}", other)),
+        }
+    }
+}
+
+type Position = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Graph {
+    adj_list: FnvHashMap<Position, FnvHashMap<Position, u64>>,
+}
+
+/// The junctions labeled with their position, the corridors with their length.
+impl ToDot for Graph {
+    type Node = Position;
+    type Edge = u64;
+
+    fn nodes(&self) -> impl Iterator<Item = Position> {
+        self.adj_list.keys().copied().sorted()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Position, Position, u64)> {
+        self.adj_list
+            .iter()
+            .flat_map(|(&from, edges)| {
+                edges
+                    .iter()
+                    .map(move |(&to, &distance)| (from, to, distance))
+            })
+            .sorted()
+    }
+
+    fn node_id(&self, &(row, col): &Position) -> String {
+        format!("v{}_{}", row, col)
+    }
+
+    fn node_label(&self, junction: &Position) -> Option<String> {
+        Some(format!("{:?}", junction))
+    }
+
+    fn edge_label(&self, _from: &Position, _to: &Position, distance: &u64) -> Option<String> {
+        Some(distance.to_string())
+    }
+}
+
+impl Graph {
+    /// Contracts the grid into a graph of its intersections (plus the start and the end), each edge
+    /// being a corridor labeled with its length.
+    ///
+    /// With `respect_slopes`, corridors can only be walked down their slopes, making the edges of
+    /// corridors with a slope one-way.
+    fn from_grid(
+        tile_grid: &Grid<Tile>,
+        start: Position,
+        end: Position,
+        respect_slopes: bool,
+    ) -> Self {
+        let mut graph = Self {
+            adj_list: FnvHashMap::default(),
+        };
+        let mut queue = VecDeque::from([(start, start, Direction::North)]);
+        let can_enter = |position: Position, from: Direction| match tile_grid[position] {
+            // a slope is only walked down, entered from the side opposite to where it leads
+            Tile::Slope(slope_dir) if respect_slopes => from == slope_dir.opposite(),
+            _ => true,
+        };
+
+        'outer: while let Some((intersect, original_pos, original_from)) = queue.pop_front() {
+            let mut pos = original_pos;
+            let mut from = original_from;
+            let mut has_slope = false;
+            let mut distance = 0;
+
+            let neighbours = loop {
+                if pos == end {
+                    break vec![];
+                }
+
+                if !can_enter(pos, from) {
+                    continue 'outer;
+                }
+                has_slope |= respect_slopes && matches!(tile_grid[pos], Tile::Slope(_));
+
+                let neighbours = open_neighbours(tile_grid, pos, from).collect_vec();
+
+                if neighbours.is_empty() {
+                    warn!(?pos, tile = %tile_grid[pos], ?from, "dead end, no neighbour apart from the one entered from");
+                    continue 'outer;
+                }
+
+                if neighbours.len() > 1 {
+                    break neighbours;
+                }
+
+                let (new_pos, new_from) = neighbours[0];
+                distance += 1;
+                pos = new_pos;
+                from = new_from;
+            };
+
+            if intersect != original_pos {
+                distance += 1;
+            }
+
+            let adj = graph.adj_list.entry(intersect).or_default();
+            // pos is an intersection
+            if let Entry::Vacant(entry) = adj.entry(pos) {
+                entry.insert(distance);
+
+                // a corridor without slopes can be walked back
+                let pos_adj = graph.adj_list.entry(pos).or_default();
+                if !has_slope {
+                    pos_adj.insert(intersect, distance);
+                }
+
+                for (neighbour, neighbour_from) in neighbours {
+                    if can_enter(neighbour, neighbour_from) {
+                        queue.push_back((pos, neighbour, neighbour_from));
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Depth-first search with an explicit stack, so that the path length is not bound by the
+    /// size of the call stack: a junction is marked visited when pushed and unmarked when popped.
+    fn longest_simple_path(&self, start: Position, end: Position) -> Option<u64> {
+        if start == end {
+            return Some(0);
+        }
+
+        let mut longest = None;
+        let mut visited = FnvHashSet::default();
+        visited.insert(start);
+        // the junctions on the current path, with the length of the path up to them and the
+        // corridors leaving them that are left to try
+        let mut stack = vec![(start, 0, self.adj_list.get(&start)?.iter())];
+        while let Some((current, length, corridors)) = stack.last_mut() {
+            let Some((&next, &distance)) = corridors.next() else {
+                let current = *current;
+                visited.remove(&current);
+                stack.pop();
+                continue;
+            };
+
+            let length = *length + distance;
+            if next == end {
+                longest = cmp::max(longest, Some(length));
+            } else if visited.insert(next) {
+                stack.push((next, length, self.adj_list[&next].iter()));
+            }
+        }
+
+        longest
+    }
+}
+
+/// The tiles next to `pos` that are not forest, apart from the one it is entered from, each with the direction
+/// it is entered from when stepping onto it.
+fn open_neighbours(
+    tile_grid: &Grid<Tile>,
+    pos: Position,
+    from: Direction,
+) -> impl Iterator<Item = (Position, Direction)> + '_ {
+    directed_neighbours4(pos, tile_grid.bounds())
+        .filter(move |&(next, going)| going != from && tile_grid[next] != Tile::Forest)
+        .map(|(next, going)| (next, going.opposite()))
+}
+
+/// Walks the grid from the junction `from_junction`, entered from `from`, along the corridor of
+/// `distance` steps leading to the junction `to`.
+///
+/// Returns the tiles of the corridor (`to` included) along with the direction `to` is entered from.
+fn walk_corridor(
+    tile_grid: &Grid<Tile>,
+    graph: &Graph,
+    from_junction: Position,
+    from: Direction,
+    to: Position,
+    distance: u64,
+) -> Option<(Vec<Position>, Direction)> {
+    'corridors: for (mut pos, mut from) in open_neighbours(tile_grid, from_junction, from) {
+        let mut tiles = vec![pos];
+        while pos != to {
+            if graph.adj_list.contains_key(&pos) {
+                continue 'corridors; // another junction
+            }
+
+            let mut neighbours = open_neighbours(tile_grid, pos, from);
+            match (neighbours.next(), neighbours.next()) {
+                (Some((new_pos, new_from)), None) => {
+                    pos = new_pos;
+                    from = new_from;
+                    tiles.push(pos);
+                }
+                _ => continue 'corridors,
+            }
+        }
+
+        if tiles.len() as u64 == distance {
+            return Some((tiles, from));
+        }
+    }
+
+    None
+}
+
+impl Graph {
+    /// Makes sure the contraction matches the map: every junction apart from the start and the
+    /// end is an intersection, and every edge is a corridor of the map of that exact length.
+    fn check_against_grid(
+        &self,
+        tile_grid: &Grid<Tile>,
+        start: Position,
+        end: Position,
+    ) -> Result<(), Box<dyn Error>> {
+        for (&junction, edges) in self
+            .adj_list
+            .iter()
+            .sorted_by_key(|&(junction, _)| junction)
+        {
+            let open_neighbours = tile_grid
+                .neighbours4(junction)
+                .filter(|&pos| tile_grid[pos] != Tile::Forest)
+                .count();
+            if junction != start && junction != end && open_neighbours < 3 {
+                return Err(format!(
+                    "Junction {:?} only has {} open neighbours",
+                    junction, open_neighbours
+                )
+                .into());
+            }
+
+            for (&to, &distance) in edges.iter().sorted() {
+                if !Direction::ALL.iter().any(|&from| {
+                    walk_corridor(tile_grid, self, junction, from, to, distance).is_some()
+                }) {
+                    return Err(format!(
+                        "There is no corridor of {} steps from {:?} to {:?} on the map",
+                        distance, junction, to
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of junctions an [`IndexedGraph`] can hold, one per bit of the visited mask.
+const MAX_INDEXED_NODES: usize = u64::BITS as usize;
+
+/// Number of junctions [`IndexedGraph::longest_simple_path_dp`] accepts: 2^20 sets of 20 lengths
+/// already take 80 MiB.
+const MAX_DP_JUNCTIONS: usize = 20;
+
+/// The graph with its junctions numbered, so that a set of them fits in a `u64` bitmask.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IndexedGraph {
+    positions: Vec<Position>,
+    /// for every junction, the junctions reachable from it along with the length of the corridor,
+    /// longest corridor first so that searches find long paths early
+    adjacency: Vec<Vec<(usize, u64)>>,
+    /// for every junction, the length of the longest corridor leading into it
+    longest_in: Vec<u64>,
+}
+
+impl IndexedGraph {
+    fn new(graph: &Graph) -> Result<Self, Box<dyn Error>> {
+        let positions = graph.adj_list.keys().copied().sorted().collect_vec();
+        if positions.len() > MAX_INDEXED_NODES {
+            return Err(format!(
+                "The graph has {} junctions, more than the {} a bitmask can track",
+                positions.len(),
+                MAX_INDEXED_NODES
+            )
+            .into());
+        }
+
+        let index_of: FnvHashMap<_, _> = positions
+            .iter()
+            .enumerate()
+            .map(|(index, &position)| (position, index))
+            .collect();
+        let adjacency = positions
+            .iter()
+            .map(|position| {
+                graph.adj_list[position]
+                    .iter()
+                    .map(|(next, &distance)| (index_of[next], distance))
+                    .sorted_by_key(|&(next, distance)| (cmp::Reverse(distance), next))
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let mut longest_in = vec![0; positions.len()];
+        for &(next, distance) in adjacency.iter().flatten() {
+            longest_in[next] = longest_in[next].max(distance);
+        }
+
+        Ok(Self {
+            positions,
+            adjacency,
+            longest_in,
+        })
+    }
+
+    #[inline]
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.positions.binary_search(&position).ok()
+    }
+
+    /// `explored` is increased by the number of search nodes (partial paths) looked at.
+    fn longest_simple_path(
+        &self,
+        start: Position,
+        end: Position,
+        explored: &mut u64,
+    ) -> Option<u64> {
+        let start = self.index_of(start)?;
+        let end = self.index_of(end)?;
+        *explored += 1;
+        if start == end {
+            return Some(0);
+        }
+
+        let mut longest = None;
+        // the junctions on the current path, each with a bit set for every junction up to it, the length of the
+        // path up to it and the corridors leaving it that are left to try
+        let mut stack = vec![(1u64 << start, 0, self.adjacency[start].iter())];
+        while let Some((visited, length, corridors)) = stack.last_mut() {
+            let Some(&(next, distance)) = corridors.next() else {
+                stack.pop();
+                continue;
+            };
+            let visited = *visited;
+            if visited & 1 << next != 0 {
+                continue;
+            }
+
+            *explored += 1;
+            let length = *length + distance;
+            if next == end {
+                longest = cmp::max(longest, Some(length));
+            } else {
+                stack.push((visited | 1 << next, length, self.adjacency[next].iter()));
+            }
+        }
+
+        longest
+    }
+
+    /// Same as [`Self::longest_simple_path`], skipping the branches that cannot beat the best path
+    /// found so far.
+    fn longest_simple_path_pruned(
+        &self,
+        start: Position,
+        end: Position,
+        explored: &mut u64,
+    ) -> Option<Route> {
+        let start = self.index_of(start)?;
+        let end = self.index_of(end)?;
+
+        let best = AtomicU64::new(0);
+        let mut search = PrunedSearch::new(self, end, &best);
+        search.visit(SearchState::start(self, start));
+
+        *explored += search.explored;
+        search.longest
+    }
+
+    /// Same as [`Self::longest_simple_path_pruned`], the first levels of the search being
+    /// expanded breadth-first and the branches left explored in parallel, all of them sharing
+    /// the best path found so far.
+    fn longest_simple_path_parallel(
+        &self,
+        start: Position,
+        end: Position,
+        explored: &mut u64,
+    ) -> Option<Route> {
+        /// How many branches to hand out to the threads, so that uneven branches even out
+        const MIN_BRANCHES: usize = 256;
+        /// How deep the expansion may go looking for enough branches
+        const MAX_SPLIT_DEPTH: usize = 8;
+
+        let start = self.index_of(start)?;
+        let end = self.index_of(end)?;
+
+        let best = AtomicU64::new(0);
+        let mut search = PrunedSearch::new(self, end, &best);
+        // every branch along with the junctions leading to it
+        let mut branches = vec![(SearchState::start(self, start), vec![])];
+        for _ in 0..MAX_SPLIT_DEPTH {
+            if branches.len() >= MIN_BRANCHES {
+                break;
+            }
+
+            let mut next_branches = vec![];
+            for (state, path) in branches {
+                search.explored += 1;
+                search.path = path;
+                search.path.push(state.current);
+                if state.current == end {
+                    search.record(state.length);
+                }
+
+                next_branches.extend(
+                    search
+                        .branches(state)
+                        .map(|next| (next, search.path.clone())),
+                );
+            }
+            branches = next_branches;
+        }
+
+        let progress = Progress::new("Branches", branches.len() as u64);
+        let (parallel_explored, parallel_longest) = branches
+            .into_par_iter()
+            .map(|(state, path)| {
+                let mut search = PrunedSearch::new(self, end, &best);
+                search.path = path;
+                search.visit(state);
+                progress.inc();
+                (search.explored, search.longest)
+            })
+            .reduce(
+                || (0, None),
+                |(explored, longest), (other_explored, other_longest)| {
+                    (
+                        explored + other_explored,
+                        Route::longest(longest, other_longest),
+                    )
+                },
+            );
+        progress.finish();
+
+        *explored += search.explored + parallel_explored;
+        Route::longest(search.longest, parallel_longest)
+    }
+
+    /// Held-Karp style dynamic programming: the longest path from the start going through exactly
+    /// a given set of junctions and ending on a given one of them, for every set in increasing
+    /// order, a set only ever growing into larger ones.
+    ///
+    /// Takes `2^n * n` lengths of memory, hence [`MAX_DP_JUNCTIONS`].
+    fn longest_simple_path_dp(&self, start: Position, end: Position) -> Option<u64> {
+        const UNREACHED: u32 = u32::MAX;
+
+        let start = self.index_of(start)?;
+        let end = self.index_of(end)?;
+        let nodes = self.len();
+
+        // longest[visited * nodes + current]
+        let mut longest = vec![UNREACHED; nodes << nodes];
+        longest[(1 << start) * nodes + start] = 0;
+        let mut best = None;
+        for visited in (1..1usize << nodes).filter(|visited| visited & 1 << start != 0) {
+            for current in 0..nodes {
+                let length = longest[visited * nodes + current];
+                if length == UNREACHED {
+                    continue;
+                }
+
+                if current == end {
+                    best = cmp::max(best, Some(u64::from(length)));
+                    continue;
+                }
+
+                for &(next, distance) in self.adjacency[current]
+                    .iter()
+                    .filter(|&&(next, _)| visited & 1 << next == 0)
+                {
+                    let slot = &mut longest[(visited | 1 << next) * nodes + next];
+                    let length = length + distance as u32;
+                    if *slot == UNREACHED || *slot < length {
+                        *slot = length;
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+}
+
+/// A path through an [`IndexedGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Route {
+    length: u64,
+    /// the indices of the junctions along the path, from the start to the end
+    junctions: Vec<usize>,
+}
+
+impl Route {
+    #[inline]
+    fn longest(route: Option<Self>, other: Option<Self>) -> Option<Self> {
+        [route, other]
+            .into_iter()
+            .flatten()
+            .max_by_key(|route| route.length)
+    }
+}
+
+/// A partial path of a [`PrunedSearch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SearchState {
+    current: usize,
+    /// a bit set for every junction on the path
+    visited: u64,
+    length: u64,
+    /// upper bound of what can still be added to the path
+    remaining: u64,
+}
+
+impl SearchState {
+    #[inline]
+    fn start(graph: &IndexedGraph, start: usize) -> Self {
+        Self {
+            current: start,
+            visited: 1 << start,
+            length: 0,
+            remaining: graph.longest_in.iter().sum::<u64>() - graph.longest_in[start],
+        }
+    }
+}
+
+/// Branch-and-bound search of the longest path to `end`.
+///
+/// A path can only go on by entering junctions it has not visited yet, each at most once and
+/// through a corridor no longer than the longest one leading into it: the sum of those is an
+/// upper bound of what can still be added to the path, and a branch is dropped as soon as that
+/// bound cannot beat the best path found so far.
+#[derive(Debug)]
+struct PrunedSearch<'g> {
+    graph: &'g IndexedGraph,
+    end: usize,
+    /// the only junction the end can be reached from, if there is a single one: a path leaving
+    /// it for anywhere else can never reach the end anymore
+    last_before_end: Option<usize>,
+    /// length of the longest path found so far, possibly shared with other searches; 0 until one
+    /// is found
+    best: &'g AtomicU64,
+    /// the junctions of the path being explored
+    path: Vec<usize>,
+    /// the longest path this search found, if it was at least as long as any other search's
+    longest: Option<Route>,
+    explored: u64,
+}
+
+impl<'g> PrunedSearch<'g> {
+    fn new(graph: &'g IndexedGraph, end: usize, best: &'g AtomicU64) -> Self {
+        let mut entries_to_end = (0..graph.len())
+            .filter(|&node| graph.adjacency[node].iter().any(|&(next, _)| next == end));
+        let last_before_end = match (entries_to_end.next(), entries_to_end.next()) {
+            (Some(node), None) => Some(node),
+            _ => None,
+        };
+
+        Self {
+            graph,
+            end,
+            last_before_end,
+            best,
+            path: Vec::with_capacity(graph.len()),
+            longest: None,
+            explored: 0,
+        }
+    }
+
+    /// the paths one corridor longer than `state` worth looking at
+    fn branches(&self, state: SearchState) -> impl Iterator<Item = SearchState> + 'g {
+        let (graph, end) = (self.graph, self.end);
+        let can_go_on = state.current != end
+            && state.length + state.remaining > self.best.load(Ordering::Relaxed);
+        let leaving_end_behind = Some(state.current) == self.last_before_end;
+
+        graph.adjacency[state.current]
+            .iter()
+            .filter(move |&&(next, _)| {
+                can_go_on && state.visited & 1 << next == 0 && (!leaving_end_behind || next == end)
+            })
+            .map(move |&(next, distance)| SearchState {
+                current: next,
+                visited: state.visited | 1 << next,
+                length: state.length + distance,
+                remaining: state.remaining - graph.longest_in[next],
+            })
+    }
+
+    /// Explores every path going on from `state`, depth first.
+    fn visit(&mut self, state: SearchState) {
+        // the branches left to try from every junction of `self.path`
+        let mut stack = vec![];
+        let mut entered = Some(state);
+        loop {
+            if let Some(state) = entered.take() {
+                self.explored += 1;
+                self.path.push(state.current);
+                if state.current == self.end {
+                    self.record(state.length);
+                    self.path.pop();
+                } else {
+                    stack.push(self.branches(state));
+                }
+            }
+
+            let Some(branches) = stack.last_mut() else {
+                break;
+            };
+            entered = branches.next();
+            if entered.is_none() {
+                stack.pop();
+                self.path.pop();
+            }
+        }
+    }
+
+    /// `self.path` reached the end with `length`
+    fn record(&mut self, length: u64) {
+        let best = self.best.fetch_max(length, Ordering::Relaxed);
+        if length >= best
+            && self
+                .longest
+                .as_ref()
+                .is_none_or(|longest| longest.length < length)
+        {
+            self.longest = Some(Route {
+                length,
+                junctions: self.path.clone(),
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+enum Algorithm {
+    /// pruned search with its first levels handed out to a thread pool
+    #[default]
+    Parallel,
+    /// bitmask search pruning the branches that cannot beat the best path found so far
+    Pruned,
+    /// depth-first search over numbered junctions, keeping the visited ones in a bitmask
+    Bitmask,
+    /// depth-first search cloning a hash set of the visited junctions at every branch
+    #[value(name = "hashset")]
+    HashSet,
+    /// dynamic programming over (visited junctions, current junction), for small graphs only
+    #[value(name = "dp")]
+    SubsetDp,
+}
+
+impl Algorithm {
+    const ALL: [Self; 5] = [
+        Self::Parallel,
+        Self::Pruned,
+        Self::Bitmask,
+        Self::HashSet,
+        Self::SubsetDp,
+    ];
+
+    /// Whether the algorithm can handle `graph` at all.
+    #[inline]
+    fn supports(self, graph: &Graph) -> bool {
+        match self {
+            Self::SubsetDp => graph.adj_list.len() <= MAX_DP_JUNCTIONS,
+            _ => true,
+        }
+    }
+
+    fn longest_path(
+        self,
+        graph: &Graph,
+        start: Position,
+        end: Position,
+    ) -> Result<u64, Box<dyn Error>> {
+        let length = match self {
+            Self::Parallel => IndexedGraph::new(graph)?
+                .longest_simple_path_parallel(start, end, &mut 0)
+                .map(|route| route.length),
+            Self::Pruned => IndexedGraph::new(graph)?
+                .longest_simple_path_pruned(start, end, &mut 0)
+                .map(|route| route.length),
+            Self::Bitmask => IndexedGraph::new(graph)?.longest_simple_path(start, end, &mut 0),
+            Self::HashSet => graph.longest_simple_path(start, end),
+            Self::SubsetDp => {
+                if !self.supports(graph) {
+                    return Err(format!(
+                        "The graph has {} junctions, too many for the subset DP (at most {})",
+                        graph.adj_list.len(),
+                        MAX_DP_JUNCTIONS
+                    )
+                    .into());
+                }
+
+                IndexedGraph::new(graph)?.longest_simple_path_dp(start, end)
+            }
+        };
+
+        length.ok_or_else(|| "There is no path from the start to the end".into())
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parallel => write!(f, "parallel"),
+            Self::Pruned => write!(f, "pruned"),
+            Self::Bitmask => write!(f, "bitmask"),
+            Self::HashSet => write!(f, "hashset"),
+            Self::SubsetDp => write!(f, "dp"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<23>,
+    /// The search finding the longest path
+    #[arg(long = "algo", value_enum, default_value_t)]
+    algorithm: Algorithm,
+    /// Run every algorithm on both parts, checking they agree, instead of only the chosen one
+    #[arg(long)]
+    compare: bool,
+    /// Print how many search nodes the bitmask search explores with and without pruning
+    #[arg(long)]
+    stats: bool,
+    /// Write the part 1 graph to this file in the DOT format, the part 2 one being written next to it
+    #[arg(long, value_name = "PATH")]
+    emit_dot: Option<PathBuf>,
+    /// Draw the longest path of each part over the map and list its corridors
+    #[arg(long)]
+    show_path: bool,
+    /// Walk every corridor of both graphs again on the map to make sure the contraction is right
+    #[arg(long)]
+    check_graph: bool,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+/// `graph.gv` for part 1 becomes `graph.part2.gv` for part 2.
+fn dot_path_for_part(path: &Path, part: u32) -> PathBuf {
+    if part == 1 {
+        return path.to_path_buf();
+    }
+
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!(".part{}", part));
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+
+    path.with_file_name(file_name)
+}
+
+fn emit_dot(graph: &Graph, path: &Path, part: u32) -> Result<(), Box<dyn Error>> {
+    let path = dot_path_for_part(path, part);
+    graph.save_dot(&path)?;
+    println!("Graph for part {} written to {}", part, path.display());
+
+    Ok(())
+}
+
+/// Finds the longest path with the parallel search (whichever algorithm was chosen) and prints
+/// its corridors, then the map with the tiles it goes through drawn as `O`.
+fn show_path(
+    tile_grid: &Grid<Tile>,
+    graph: &Graph,
+    start: Position,
+    end: Position,
+    part: u32,
+) -> Result<(), Box<dyn Error>> {
+    let indexed_graph = IndexedGraph::new(graph)?;
+    let route = indexed_graph
+        .longest_simple_path_parallel(start, end, &mut 0)
+        .ok_or("There is no path from the start to the end")?;
+
+    println!("Longest path for part {} ({} steps):", part, route.length);
+    let mut on_path = FnvHashSet::from_iter([start]);
+    let mut from = Direction::North;
+    let mut total = 0;
+    for (from_junction, to) in route
+        .junctions
+        .iter()
+        .map(|&index| indexed_graph.positions[index])
+        .tuple_windows()
+    {
+        let distance = graph.adj_list[&from_junction][&to];
+        let (tiles, to_from) = walk_corridor(tile_grid, graph, from_junction, from, to, distance)
+            .ok_or_else(|| {
+            format!(
+                "No corridor of {} steps from {:?} to {:?}",
+                distance, from_junction, to
+            )
+        })?;
+
+        total += distance;
+        println!(
+            "    {:?} -> {:?}: {} steps ({} so far)",
+            from_junction, to, distance, total
+        );
+        on_path.extend(tiles);
+        from = to_from;
+    }
+
+    for (row, line) in tile_grid.iter_rows().enumerate() {
+        let line: String = line
+            .iter()
+            .enumerate()
+            .map(|(col, tile)| {
+                if (row, col) == start {
+                    "S".to_owned()
+                } else if on_path.contains(&(row, col)) {
+                    "O".to_owned()
+                } else {
+                    tile.to_string()
+                }
+            })
+            .collect();
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+fn print_search_stats(
+    graph: &Graph,
+    start: Position,
+    end: Position,
+    part: u32,
+) -> Result<(), Box<dyn Error>> {
+    let graph = IndexedGraph::new(graph)?;
+    let (mut pruned, mut exhaustive) = (0, 0);
+    graph.longest_simple_path_pruned(start, end, &mut pruned);
+    graph.longest_simple_path(start, end, &mut exhaustive);
+    println!(
+        "Search nodes explored for part {}: {} with pruning, {} without ({:.2}%)",
+        part,
+        pruned,
+        exhaustive,
+        pruned as f64 / exhaustive as f64 * 100.0
+    );
+
+    Ok(())
+}
+
+/// Runs every algorithm on the graph, printing how long each took, and makes sure they all agree.
+fn compare_algorithms(
+    graph: &Graph,
+    start: Position,
+    end: Position,
+    part: u32,
+) -> Result<u64, Box<dyn Error>> {
+    let mut answer = None;
+    for algorithm in Algorithm::ALL {
+        if !algorithm.supports(graph) {
+            println!(
+                "Skipping {} for part {}: the graph has too many junctions",
+                algorithm, part
+            );
+            continue;
+        }
+
+        let start_time = Instant::now();
+        let length = algorithm.longest_path(graph, start, end)?;
+        println!(
+            "Time for part {} ({}): {:?}",
+            part,
+            algorithm,
+            start_time.elapsed()
+        );
+
+        match answer {
+            Some(expected) if expected != length => {
+                return Err(format!(
+                    "{} found a path of length {} for part {} but {} found {}",
+                    algorithm,
+                    length,
+                    part,
+                    Algorithm::ALL[0],
+                    expected
+                )
+                .into())
+            }
+            _ => answer = Some(length),
+        }
+    }
+
+    Ok(answer.expect("there is at least one algorithm"))
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    let Day23 {
+        grid,
+        start_pos,
+        end_pos,
+    } = Day23::parse(&input.read()?)?;
+
+    if options.args.part.solves_part1() {
+        let part1_answ = solve_part(&grid, start_pos, end_pos, 1, options)?;
+        println!("Part 1 answer: {}", part1_answ);
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    solve_part(&grid, start_pos, end_pos, 2, options).map(Some)
+}
+
+/// Contracts the map into a graph, taking the slopes into account in part 1 only, and finds the longest hike
+/// through it, along with everything `options` asked to check or show about that part.
+fn solve_part(
+    grid: &Grid<Tile>,
+    start_pos: Position,
+    end_pos: Position,
+    part: u32,
+    options: &Options,
+) -> Result<u64, Box<dyn Error>> {
+    let respect_slopes = part == 1;
+
+    let graph = Graph::from_grid(grid, start_pos, end_pos, respect_slopes);
+    if options.check_graph {
+        graph.check_against_grid(grid, start_pos, end_pos)?;
+        println!(
+            "Graph for part {} checked: {} junctions, {} edges",
+            part,
+            graph.adj_list.len(),
+            graph
+                .adj_list
+                .values()
+                .map(|edges| edges.len())
+                .sum::<usize>()
+        );
+    }
+    if let Some(path) = &options.emit_dot {
+        emit_dot(&graph, path, part)?;
+    }
+
+    if options.stats {
+        print_search_stats(&graph, start_pos, end_pos, part)?;
+    }
+
+    let answer = if options.compare {
+        compare_algorithms(&graph, start_pos, end_pos, part)?
+    } else {
+        options.algorithm.longest_path(&graph, start_pos, end_pos)?
+    };
+
+    if options.show_path {
+        show_path(grid, &graph, start_pos, end_pos, part)?;
+    }
+
+    Ok(answer)
+}
+
+/// The hiking trails map, along with the path tiles of its first and last rows the hike goes between.
+#[derive(Debug, Clone)]
+pub struct Day23 {
+    grid: Grid<Tile>,
+    start_pos: Position,
+    end_pos: Position,
+}
+
+impl Day23 {
+    fn longest_hike(&self, respect_slopes: bool) -> Result<Answer, Box<dyn Error>> {
+        let graph = Graph::from_grid(&self.grid, self.start_pos, self.end_pos, respect_slopes);
+        Ok(Algorithm::default()
+            .longest_path(&graph, self.start_pos, self.end_pos)?
+            .into())
+    }
+}
+
+aoc_utils::register_day!(
+    23,
+    Day23,
+    examples = [Example::both(include_str!("../example"), 94, 154)]
+);
+
+impl Solution for Day23 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let grid = Grid::from_lines(input.lines(), Tile::try_from)?;
+
+        let path_in_row = |row: usize| {
+            grid.row(row)
+                .iter()
+                .find_position(|&tile| matches!(tile, Tile::Path))
+                .map(|(col, _)| (row, col))
+                .ok_or_else(|| format!("Row {} has no path tile", row))
+        };
+        let start_pos = path_in_row(0)?;
+        let end_pos = path_in_row(grid.rows() - 1)?;
+
+        Ok(Self {
+            grid,
+            start_pos,
+            end_pos,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        self.longest_hike(true)
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        self.longest_hike(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Day23 {
+        Day23::parse(include_str!("../example")).expect("the example parses")
+    }
+
+    fn edge(graph: &Graph, from: Position, to: Position) -> Option<u64> {
+        graph.adj_list.get(&from)?.get(&to).copied()
+    }
+
+    fn edge_count(graph: &Graph) -> usize {
+        graph.adj_list.values().map(|edges| edges.len()).sum()
+    }
+
+    #[test]
+    fn contraction_with_slopes() {
+        let day = example();
+        let graph = Graph::from_grid(&day.grid, day.start_pos, day.end_pos, true);
+
+        // 7 intersections plus the start and the end
+        assert_eq!(graph.adj_list.len(), 9);
+        assert_eq!(edge_count(&graph), 12);
+        assert_eq!(edge(&graph, (0, 1), (5, 3)), Some(15));
+        assert_eq!(edge(&graph, (5, 3), (3, 11)), Some(22));
+        assert_eq!(edge(&graph, (3, 11), (11, 21)), Some(30));
+        assert_eq!(edge(&graph, (13, 5), (19, 13)), Some(38));
+        assert_eq!(edge(&graph, (19, 19), (22, 21)), Some(5));
+        // the corridors with a slope are one-way
+        assert_eq!(edge(&graph, (5, 3), (0, 1)), None);
+        assert_eq!(edge(&graph, (22, 21), (19, 19)), None);
+
+        graph
+            .check_against_grid(&day.grid, day.start_pos, day.end_pos)
+            .expect("the graph matches the map");
+        assert_eq!(
+            graph.longest_simple_path(day.start_pos, day.end_pos),
+            Some(94)
+        );
+    }
+
+    #[test]
+    fn contraction_without_slopes() {
+        let day = example();
+        let graph = Graph::from_grid(&day.grid, day.start_pos, day.end_pos, false);
+
+        assert_eq!(graph.adj_list.len(), 9);
+        assert_eq!(edge_count(&graph), 24);
+        for (from, to, distance) in [
+            ((0, 1), (5, 3), 15),
+            ((5, 3), (13, 5), 22),
+            ((19, 19), (22, 21), 5),
+        ] {
+            assert_eq!(edge(&graph, from, to), Some(distance));
+            assert_eq!(edge(&graph, to, from), Some(distance));
+        }
+
+        graph
+            .check_against_grid(&day.grid, day.start_pos, day.end_pos)
+            .expect("the graph matches the map");
+        assert_eq!(
+            graph.longest_simple_path(day.start_pos, day.end_pos),
+            Some(154)
+        );
+    }
+
+    #[test]
+    fn every_search_agrees() {
+        let day = example();
+        for (respect_slopes, expected) in [(true, 94), (false, 154)] {
+            let graph = Graph::from_grid(&day.grid, day.start_pos, day.end_pos, respect_slopes);
+            for algorithm in Algorithm::ALL {
+                assert!(
+                    algorithm.supports(&graph),
+                    "{} does not support the example",
+                    algorithm
+                );
+                assert_eq!(
+                    algorithm
+                        .longest_path(&graph, day.start_pos, day.end_pos)
+                        .unwrap(),
+                    expected,
+                    "{} with respect_slopes = {}",
+                    algorithm,
+                    respect_slopes
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn example_answers() {
+        let day = example();
+        assert_eq!(day.part1().unwrap(), Answer::from(94u64));
+        assert_eq!(day.part2().unwrap(), Answer::from(154u64));
+    }
+}
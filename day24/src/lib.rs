@@ -0,0 +1,1124 @@
+use aoc_utils::{
+    error::{parse_field, parse_lines, AocError},
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::{Parser, ValueEnum};
+use core::fmt;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{ToPrimitive, Zero};
+use std::{error::Error, ops::RangeInclusive, str::FromStr, time::Instant};
+
+/// Where the paths of two hailstones meet in the (x, y) plane, from t = 0 on.
+///
+/// Times are those at which each hailstone gets there, a hailstone not moving in the plane being
+/// there from t = 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Crossing {
+    /// the paths cross at `point`
+    At {
+        point: (f64, f64),
+        times: (f64, f64),
+    },
+    /// the paths only meet in the past of at least one of the hailstones
+    InThePast,
+    /// the paths are parallel and apart
+    Parallel,
+    /// one of the hailstones does not move in the plane, and the other never goes through it
+    Missed,
+    /// the hailstones go along the same line, sharing the points `origin + t * velocity` for `t`
+    /// within `times` (up to infinity), `t` being the time of the first hailstone if it moves
+    Overlapping {
+        origin: (f64, f64),
+        velocity: (f64, f64),
+        times: (f64, f64),
+    },
+}
+
+impl Crossing {
+    /// Whether the paths meet within `area` along both the x and y axes.
+    fn is_inside(&self, area: &RangeInclusive<f64>) -> bool {
+        match *self {
+            Self::At { point: (x, y), .. } => area.contains(&x) && area.contains(&y),
+            Self::Overlapping {
+                origin,
+                velocity,
+                times: (mut from, mut to),
+            } => {
+                // clip the times to those the shared points are inside the area along each axis
+                for (origin, velocity) in [(origin.0, velocity.0), (origin.1, velocity.1)] {
+                    if velocity == 0. {
+                        if !area.contains(&origin) {
+                            return false;
+                        }
+                        continue;
+                    }
+
+                    let (enter, leave) = (
+                        (area.start() - origin) / velocity,
+                        (area.end() - origin) / velocity,
+                    );
+                    from = from.max(enter.min(leave));
+                    to = to.min(enter.max(leave));
+                }
+
+                from <= to
+            }
+            Self::InThePast | Self::Parallel | Self::Missed => false,
+        }
+    }
+}
+
+impl fmt::Display for Crossing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::At { point, times } => write!(
+                f,
+                "cross at ({:.3}, {:.3}), at t = {:.3} and t = {:.3}",
+                point.0, point.1, times.0, times.1
+            ),
+            Self::InThePast => write!(f, "crossed in the past"),
+            Self::Parallel => write!(f, "parallel"),
+            Self::Missed => write!(f, "never meet"),
+            Self::Overlapping {
+                origin,
+                velocity,
+                times,
+            } => write!(
+                f,
+                "overlap along ({}, {}) + t * ({}, {}) for t from {:.3} to {:.3}",
+                origin.0, origin.1, velocity.0, velocity.1, times.0, times.1
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HailStonePath {
+    px: i64,
+    py: i64,
+    pz: i64,
+
+    vx: i64,
+    vy: i64,
+    vz: i64,
+}
+
+impl HailStonePath {
+    #[inline]
+    fn new(px: i64, py: i64, pz: i64, vx: i64, vy: i64, vz: i64) -> Self {
+        Self {
+            px,
+            py,
+            pz,
+            vx,
+            vy,
+            vz,
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> [i64; 3] {
+        [self.px, self.py, self.pz]
+    }
+
+    #[inline]
+    fn velocity(&self) -> [i64; 3] {
+        [self.vx, self.vy, self.vz]
+    }
+
+    #[inline]
+    fn position_at(&self, time: f64) -> [f64; 3] {
+        [0, 1, 2].map(|axis| self.position()[axis] as f64 + time * self.velocity()[axis] as f64)
+    }
+
+    /// How the paths of both hailstones relate in 3D, whenever each of them goes through them.
+    fn path_approach(&self, other: &Self) -> PathApproach {
+        let offset = sub(other.position(), self.position());
+        let normal = cross(self.velocity(), other.velocity());
+        if normal == [0; 3] {
+            let off_path = cross(offset, self.velocity().map(i128::from));
+            return if off_path == [0; 3] {
+                PathApproach::Same
+            } else {
+                PathApproach::Parallel {
+                    distance: norm(off_path) / norm(self.velocity().map(i128::from)),
+                }
+            };
+        }
+
+        // the line joining the closest points is along the normal of both paths
+        let normal_squared = dot(normal, normal) as f64;
+        let times = (
+            dot(cross(offset, other.velocity().map(i128::from)), normal) as f64 / normal_squared,
+            dot(cross(offset, self.velocity().map(i128::from)), normal) as f64 / normal_squared,
+        );
+        match dot(offset, normal) {
+            0 => PathApproach::Crossing {
+                times,
+                point: self.position_at(times.0),
+            },
+            separation => PathApproach::Skew {
+                times,
+                distance: separation.abs() as f64 / normal_squared.sqrt(),
+            },
+        }
+    }
+
+    /// When both hailstones, moving at the same time, get the closest (from now on) and how far
+    /// apart they are then.
+    fn closest_encounter(&self, other: &Self) -> (f64, f64) {
+        let offset = sub(other.position(), self.position());
+        let relative_velocity = sub(other.velocity(), self.velocity());
+        let time = match dot(relative_velocity, relative_velocity) {
+            0 => 0.,
+            speed_squared => {
+                (-dot(offset, relative_velocity) as f64 / speed_squared as f64).max(0.)
+            }
+        };
+
+        let distance = [0, 1, 2]
+            .map(|axis| offset[axis] as f64 + time * relative_velocity[axis] as f64)
+            .iter()
+            .map(|coordinate| coordinate * coordinate)
+            .sum::<f64>()
+            .sqrt();
+        (time, distance)
+    }
+
+    /// Where the paths of both hailstones meet when only looking at the x and y axes.
+    ///
+    /// Computed over exact integers, only the results being converted to floating point.
+    fn crossing(&self, other: &Self) -> Crossing {
+        let cross_2d = |a: [i128; 3], b: [i128; 3]| a[0] * b[1] - a[1] * b[0];
+        let dot_2d = |a: [i128; 3], b: [i128; 3]| a[0] * b[0] + a[1] * b[1];
+        let point = |position: [i128; 3]| (position[0] as f64, position[1] as f64);
+
+        let (position, other_position) = (
+            self.position().map(i128::from),
+            other.position().map(i128::from),
+        );
+        let (velocity, other_velocity) = (
+            self.velocity().map(i128::from),
+            other.velocity().map(i128::from),
+        );
+        let offset = sub(other_position, position);
+
+        let determinant = cross_2d(velocity, other_velocity);
+        if determinant != 0 {
+            // position + t * velocity = other_position + s * other_velocity
+            let times = (
+                cross_2d(offset, other_velocity) as f64 / determinant as f64,
+                cross_2d(offset, velocity) as f64 / determinant as f64,
+            );
+            if times.0 < 0. || times.1 < 0. {
+                return Crossing::InThePast;
+            }
+
+            let (x, y) = point(position);
+            return Crossing::At {
+                point: (
+                    x + times.0 * velocity[0] as f64,
+                    y + times.0 * velocity[1] as f64,
+                ),
+                times,
+            };
+        }
+
+        let (moving, other_moving) = (
+            dot_2d(velocity, velocity) != 0,
+            dot_2d(other_velocity, other_velocity) != 0,
+        );
+        match (moving, other_moving) {
+            (false, false) if offset[..2] == [0, 0] => Crossing::Overlapping {
+                origin: point(position),
+                velocity: (0., 0.),
+                times: (0., f64::INFINITY),
+            },
+            (false, false) => Crossing::Missed,
+            (false, true) | (true, false) => {
+                // the moving one has to go through the other
+                let (still_position, moving_position, moving_velocity) = if moving {
+                    (other_position, position, velocity)
+                } else {
+                    (position, other_position, other_velocity)
+                };
+                let to_still = sub(still_position, moving_position);
+                if cross_2d(to_still, moving_velocity) != 0 {
+                    return Crossing::Missed;
+                }
+
+                let time = dot_2d(to_still, moving_velocity) as f64
+                    / dot_2d(moving_velocity, moving_velocity) as f64;
+                if time < 0. {
+                    return Crossing::InThePast;
+                }
+
+                Crossing::At {
+                    point: point(still_position),
+                    times: if moving { (time, 0.) } else { (0., time) },
+                }
+            }
+            (true, true) if cross_2d(offset, velocity) != 0 => Crossing::Parallel,
+            (true, true) => {
+                // on the same line: where the other starts and how fast it goes, in time of the first
+                let speed_squared = dot_2d(velocity, velocity) as f64;
+                let other_start = dot_2d(offset, velocity) as f64 / speed_squared;
+                let other_rate = dot_2d(other_velocity, velocity) as f64 / speed_squared;
+                let times = if other_rate > 0. {
+                    (other_start.max(0.), f64::INFINITY)
+                } else if other_start >= 0. {
+                    (0., other_start)
+                } else {
+                    return Crossing::InThePast;
+                };
+
+                Crossing::Overlapping {
+                    origin: point(position),
+                    velocity: (velocity[0] as f64, velocity[1] as f64),
+                    times,
+                }
+            }
+        }
+    }
+}
+
+/// How two paths relate in 3D, times being those at which each hailstone is on the point(s) of
+/// interest of its path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathApproach {
+    /// the paths cross at `point`
+    Crossing { times: (f64, f64), point: [f64; 3] },
+    /// the paths never meet, their closest points being `distance` apart
+    Skew { times: (f64, f64), distance: f64 },
+    /// the paths are parallel, `distance` apart
+    Parallel { distance: f64 },
+    /// both hailstones go along the same line
+    Same,
+}
+
+impl fmt::Display for PathApproach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Crossing { times, point } => write!(
+                f,
+                "paths cross at ({:.1}, {:.1}, {:.1}), at t = {:.1} and t = {:.1}",
+                point[0], point[1], point[2], times.0, times.1
+            ),
+            Self::Skew { times, distance } => write!(
+                f,
+                "paths never meet, closest ({:.1} apart) at t = {:.1} and t = {:.1}",
+                distance, times.0, times.1
+            ),
+            Self::Parallel { distance } => write!(f, "paths are parallel, {:.1} apart", distance),
+            Self::Same => write!(f, "paths are the same line"),
+        }
+    }
+}
+
+impl FromStr for HailStonePath {
+    type Err = AocError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((position, speed)) = s.split_once('@') else {
+            return Err(AocError::parse(
+                s,
+                format!("Could not split {:?} on '@'", s),
+            ));
+        };
+
+        let Some((px, py, pz)) = position.split(',').collect_tuple() else {
+            return Err(AocError::parse(
+                position,
+                format!("Could not split {:?} on ',' into 3 fields", position),
+            ));
+        };
+
+        let Some((vx, vy, vz)) = speed.split(',').collect_tuple() else {
+            return Err(AocError::parse(
+                speed,
+                format!("Could not split {:?} on ',' into 3 fields", speed),
+            ));
+        };
+
+        Ok(Self::new(
+            parse_field(px.trim())?,
+            parse_field(py.trim())?,
+            parse_field(pz.trim())?,
+            parse_field(vx.trim())?,
+            parse_field(vy.trim())?,
+            parse_field(vz.trim())?,
+        ))
+    }
+}
+
+/// The rock thrown in part 2, along with the velocity it is thrown at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Rock {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+impl Rock {
+    /// The rock hitting every hailstone, from the 6 linear equations given by 3 of them.
+    ///
+    /// A hailstone `(p, v)` is hit at some time `t` when `P + t V = p + t v`, so `P - p` and
+    /// `V - v` are colinear: `(P - p) × (V - v) = 0`, that is `P × V - P × v - p × V + p × v = 0`.
+    /// The only non-linear term `P × V` is the same for every hailstone, subtracting the equations
+    /// of two of them leaves 3 linear ones:
+    ///
+    /// `P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i`
+    ///
+    /// The system is solved over exact rationals (the determinants do not fit in an `i128`).
+    fn find_gaussian(hailstones: &[HailStonePath]) -> Result<Self, Box<dyn Error>> {
+        let Some(first) = hailstones.first() else {
+            return Err("There are no hailstones to hit".into());
+        };
+
+        // hailstones with parallel paths make the system singular, look for some that do not
+        for (second, third) in hailstones[1..].iter().tuple_combinations() {
+            let (mut matrix, mut constants) = (vec![], vec![]);
+            for other in [second, third] {
+                let (rows, other_constants) = Self::pair_equations(first, other);
+                matrix.extend(rows);
+                constants.extend(other_constants);
+            }
+
+            let Some(solution) = solve_linear_system(matrix, constants) else {
+                continue;
+            };
+
+            let integers: Vec<i64> = solution
+                .iter()
+                .map(|value| {
+                    value
+                        .is_integer()
+                        .then(|| value.to_integer().to_i64())
+                        .flatten()
+                })
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    format!(
+                        "The rock would not be thrown from integer coordinates: {:?}",
+                        solution
+                    )
+                })?;
+
+            return Ok(Self {
+                position: [integers[0], integers[1], integers[2]],
+                velocity: [integers[3], integers[4], integers[5]],
+            });
+        }
+
+        Err("Every set of 3 hailstones gives a singular system".into())
+    }
+
+    /// The 3 equations `P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i` over
+    /// `(P, V)`, as the rows of the system and their constants.
+    fn pair_equations(
+        first: &HailStonePath,
+        second: &HailStonePath,
+    ) -> ([[i128; 6]; 3], [i128; 3]) {
+        let [dvx, dvy, dvz] = sub(second.velocity(), first.velocity());
+        let [dpx, dpy, dpz] = sub(second.position(), first.position());
+        let rows = [
+            [0, dvz, -dvy, 0, -dpz, dpy],
+            [-dvz, 0, dvx, dpz, 0, -dpx],
+            [dvy, -dvx, 0, -dpy, dpx, 0],
+        ];
+        let constants = sub(
+            cross(second.position(), second.velocity()),
+            cross(first.position(), first.velocity()),
+        );
+
+        (rows, constants)
+    }
+
+    /// The rock hitting every hailstone, guessing its velocity one axis at a time.
+    ///
+    /// Two hailstones moving at the same speed `v` along an axis stay `d` apart along it, and the
+    /// rock closes in on each of them at `V - v` along it, so the time between the two hits makes
+    /// `d` a multiple of `V - v`: the only speeds left after checking every such pair (within
+    /// [`MAX_ROCK_SPEED`]) make the rock's velocity. Seen from the rock, every hailstone then goes
+    /// through the rock's position, where the paths of two hailstones meet.
+    fn find_velocity_search(hailstones: &[HailStonePath]) -> Result<Self, Box<dyn Error>> {
+        let mut velocity = [0; 3];
+        for (axis, speed) in velocity.iter_mut().enumerate() {
+            let mut candidates = (-MAX_ROCK_SPEED..=MAX_ROCK_SPEED).collect_vec();
+            for (first, second) in hailstones
+                .iter()
+                .tuple_combinations()
+                .filter(|(first, second)| first.velocity()[axis] == second.velocity()[axis])
+            {
+                let hailstone_speed = first.velocity()[axis];
+                let distance = second.position()[axis] - first.position()[axis];
+                candidates.retain(|&candidate| match candidate - hailstone_speed {
+                    0 => distance == 0,
+                    closing_speed => distance % closing_speed == 0,
+                });
+            }
+
+            *speed = match candidates[..] {
+                [candidate] => candidate,
+                [] => return Err(format!("No rock speed along axis {} can hit every hailstone", axis).into()),
+                _ => {
+                    return Err(format!(
+                        "{} rock speeds along axis {} are still possible, not enough hailstones share a speed",
+                        candidates.len(),
+                        axis
+                    )
+                    .into())
+                }
+            };
+        }
+
+        let position = Self::meeting_point(hailstones, velocity)
+            .ok_or("The paths of the hailstones do not meet when seen from the rock")??;
+        Ok(Self { position, velocity })
+    }
+
+    /// Where the paths of the hailstones meet, in the frame of a rock moving at `velocity`:
+    /// `None` if no two paths are found crossing, an error if they cross between coordinates.
+    fn meeting_point(
+        hailstones: &[HailStonePath],
+        velocity: [i64; 3],
+    ) -> Option<Result<[i64; 3], Box<dyn Error>>> {
+        let first = hailstones.first()?;
+        let first_velocity = sub(first.velocity(), velocity);
+        for other in &hailstones[1..] {
+            let other_velocity = sub(other.velocity(), velocity);
+            let offset = sub(other.position(), first.position());
+            for (x, y) in [(0, 1), (0, 2), (1, 2)] {
+                // 2D cross products of the plane
+                let denominator =
+                    first_velocity[x] * other_velocity[y] - first_velocity[y] * other_velocity[x];
+                if denominator == 0 {
+                    continue;
+                }
+
+                let numerator = offset[x] * other_velocity[y] - offset[y] * other_velocity[x];
+                if numerator % denominator != 0 {
+                    return Some(Err(format!(
+                        "The paths of {:?} and {:?} cross between coordinates when seen from the rock",
+                        first.position(),
+                        other.position()
+                    )
+                    .into()));
+                }
+
+                let time = numerator / denominator;
+                let position = [0, 1, 2]
+                    .map(|axis| i128::from(first.position()[axis]) + time * first_velocity[axis]);
+                return Some(
+                    position
+                        .iter()
+                        .map(|&coordinate| i64::try_from(coordinate))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(|position| [position[0], position[1], position[2]])
+                        .map_err(|err| err.into()),
+                );
+            }
+        }
+
+        None
+    }
+
+    /// When the rock hits `hailstone`, checked along every axis in exact integer arithmetic.
+    ///
+    /// A hailstone moving along with the rock is hit at t = 0 if they start together, never otherwise.
+    fn hit_time(&self, hailstone: &HailStonePath) -> Option<i128> {
+        let offset = sub(hailstone.position(), self.position);
+        let closing_velocity = sub(self.velocity, hailstone.velocity());
+        let time = match (0..3).find(|&axis| closing_velocity[axis] != 0) {
+            Some(axis) if offset[axis] % closing_velocity[axis] == 0 => {
+                offset[axis] / closing_velocity[axis]
+            }
+            Some(_) => return None,
+            None => 0,
+        };
+
+        (time >= 0 && (0..3).all(|axis| offset[axis] == time * closing_velocity[axis]))
+            .then_some(time)
+    }
+
+    /// Makes sure the rock hits every hailstone.
+    fn verify(&self, hailstones: &[HailStonePath]) -> Result<(), MissedHailstones> {
+        let missed = hailstones
+            .iter()
+            .enumerate()
+            .filter(|(_, hailstone)| self.hit_time(hailstone).is_none())
+            .map(|(index, _)| index)
+            .collect_vec();
+
+        if missed.is_empty() {
+            Ok(())
+        } else {
+            Err(MissedHailstones {
+                rock: *self,
+                missed,
+            })
+        }
+    }
+
+    /// The rock moving along like a hailstone would.
+    #[inline]
+    fn as_hailstone(&self) -> HailStonePath {
+        let ([px, py, pz], [vx, vy, vz]) = (self.position, self.velocity);
+        HailStonePath::new(px, py, pz, vx, vy, vz)
+    }
+
+    #[inline]
+    fn coordinates_sum(&self) -> i64 {
+        self.position.iter().sum()
+    }
+}
+
+/// The hailstones a rock does not hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MissedHailstones {
+    rock: Rock,
+    /// the indices of the hailstones missed
+    missed: Vec<usize>,
+}
+
+impl fmt::Display for MissedHailstones {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The rock thrown from {:?} at {:?} misses {} hailstone(s): {}",
+            self.rock.position,
+            self.rock.velocity,
+            self.missed.len(),
+            self.missed.iter().join(", ")
+        )
+    }
+}
+
+impl Error for MissedHailstones {}
+
+/// Fastest the rock can be thrown along an axis for [`Rock::find_velocity_search`].
+const MAX_ROCK_SPEED: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+enum Algorithm {
+    /// solve the linear system given by 3 hailstones
+    #[default]
+    Gaussian,
+    /// narrow down the rock's velocity from hailstones sharing a speed
+    #[value(name = "velocity")]
+    VelocitySearch,
+}
+
+impl Algorithm {
+    const ALL: [Self; 2] = [Self::Gaussian, Self::VelocitySearch];
+
+    #[inline]
+    fn find_rock(self, hailstones: &[HailStonePath]) -> Result<Rock, Box<dyn Error>> {
+        match self {
+            Self::Gaussian => Rock::find_gaussian(hailstones),
+            Self::VelocitySearch => Rock::find_velocity_search(hailstones),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gaussian => write!(f, "gaussian"),
+            Self::VelocitySearch => write!(f, "velocity"),
+        }
+    }
+}
+
+/// Either a hailstone, by its index in the input, or the rock of part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Stone {
+    Hailstone(usize),
+    Rock,
+}
+
+impl Stone {
+    fn get(
+        self,
+        hailstones: &[HailStonePath],
+        rock: &Rock,
+    ) -> Result<HailStonePath, Box<dyn Error>> {
+        match self {
+            Self::Hailstone(index) => hailstones.get(index).copied().ok_or_else(|| {
+                format!(
+                    "There is no hailstone {} (only {})",
+                    index,
+                    hailstones.len()
+                )
+                .into()
+            }),
+            Self::Rock => Ok(rock.as_hailstone()),
+        }
+    }
+}
+
+impl FromStr for Stone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rock" => Ok(Self::Rock),
+            index => index
+                .parse()
+                .map(Self::Hailstone)
+                .map_err(|_| format!("Expected a hailstone index or \"rock\", got {:?}", index)),
+        }
+    }
+}
+
+impl fmt::Display for Stone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hailstone(index) => write!(f, "hailstone {}", index),
+            Self::Rock => write!(f, "the rock"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<24>,
+    /// The way part 2 finds the rock
+    #[arg(long = "algo", value_enum, default_value_t)]
+    algorithm: Algorithm,
+    /// Run every part 2 algorithm, checking they find the same rock, instead of only the chosen one
+    #[arg(long)]
+    compare: bool,
+    /// Print the 3D closest approach of these two stones, each a hailstone index or `rock`
+    #[arg(long = "approach", num_args = 2, value_names = ["STONE", "STONE"])]
+    approaches: Vec<Stone>,
+    /// List the pairs of hailstones crossing inside the test area of part 1
+    #[arg(long)]
+    report: bool,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+/// Prints every pair of hailstones whose paths cross inside `area`, with where and when.
+fn print_crossings_report(hailstones: &[HailStonePath], area: &RangeInclusive<f64>) {
+    let mut count = 0;
+    for ((first_index, first), (second_index, second)) in
+        hailstones.iter().enumerate().tuple_combinations()
+    {
+        let crossing = first.crossing(second);
+        if crossing.is_inside(area) {
+            count += 1;
+            println!(
+                "{} and {}: {}",
+                Stone::Hailstone(first_index),
+                Stone::Hailstone(second_index),
+                crossing
+            );
+        }
+    }
+
+    println!("{} pairs of hailstones cross inside {:?}", count, area);
+}
+
+/// Runs every algorithm, printing how long each took, and makes sure they all find the same rock.
+fn compare_algorithms(hailstones: &[HailStonePath]) -> Result<Rock, Box<dyn Error>> {
+    let mut found = None;
+    for algorithm in Algorithm::ALL {
+        let start_time = Instant::now();
+        let rock = algorithm.find_rock(hailstones)?;
+        println!(
+            "Time for part 2 ({}): {:?}",
+            algorithm,
+            start_time.elapsed()
+        );
+
+        match found {
+            Some(expected) if expected != rock => {
+                return Err(format!(
+                    "{} found {:?} but {} found {:?}",
+                    algorithm,
+                    rock,
+                    Algorithm::ALL[0],
+                    expected
+                )
+                .into())
+            }
+            _ => found = Some(rock),
+        }
+    }
+
+    Ok(found.expect("there is at least one algorithm"))
+}
+
+#[inline]
+fn sub<T: Into<i128> + Copy>(a: [T; 3], b: [T; 3]) -> [i128; 3] {
+    [0, 1, 2].map(|axis| a[axis].into() - b[axis].into())
+}
+
+#[inline]
+fn cross<T: Into<i128> + Copy>(a: [T; 3], b: [T; 3]) -> [i128; 3] {
+    let [a, b] = [a, b].map(|vector| vector.map(Into::into));
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline]
+fn dot(a: [i128; 3], b: [i128; 3]) -> i128 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[inline]
+fn norm(a: [i128; 3]) -> f64 {
+    (dot(a, a) as f64).sqrt()
+}
+
+/// Gaussian elimination of `matrix * x = constants`, `None` if the matrix is singular.
+fn solve_linear_system<const N: usize>(
+    matrix: Vec<[i128; N]>,
+    constants: Vec<i128>,
+) -> Option<Vec<BigRational>> {
+    let to_rational = |value: i128| BigRational::from_integer(BigInt::from(value));
+    let mut rows = matrix
+        .into_iter()
+        .zip(constants)
+        .map(|(row, constant)| {
+            row.into_iter()
+                .chain([constant])
+                .map(to_rational)
+                .collect_vec()
+        })
+        .collect_vec();
+    let size = rows.len();
+
+    for column in 0..size {
+        let pivot = (column..size).find(|&row| !rows[row][column].is_zero())?;
+        rows.swap(column, pivot);
+
+        let pivot_row = rows[column].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index == column || row[column].is_zero() {
+                continue;
+            }
+
+            let factor = &row[column] / &pivot_row[column];
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(column) {
+                *value -= &factor * pivot_value;
+            }
+        }
+    }
+
+    Some(
+        rows.into_iter()
+            .enumerate()
+            .map(|(index, row)| &row[size] / &row[index])
+            .collect(),
+    )
+}
+
+const TEST_AREA_PART_1: RangeInclusive<f64> = 200000000000000.0..=400000000000000.;
+/// The test area of the example, far smaller than that of the real input
+const EXAMPLE_TEST_AREA: RangeInclusive<f64> = 7.0..=27.;
+
+/// How many pairs of hailstones have paths crossing inside `area` (part 1).
+fn crossings_inside(hailstones: &[HailStonePath], area: &RangeInclusive<f64>) -> usize {
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter(|(first, second)| first.crossing(second).is_inside(area))
+        .count()
+}
+
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<i64>, Box<dyn Error>> {
+    let input = input.read()?;
+    let hailstones: Vec<HailStonePath> = parse_lines(&input, str::parse)?;
+
+    if options.args.part.solves_part1() {
+        let part1_answ = crossings_inside(&hailstones, &TEST_AREA_PART_1);
+
+        if options.report {
+            print_crossings_report(&hailstones, &TEST_AREA_PART_1);
+        }
+        println!("Part 1 answer: {}", part1_answ);
+    }
+
+    if !options.args.part.solves_part2() {
+        return Ok(None);
+    }
+
+    let rock = if options.compare {
+        compare_algorithms(&hailstones)?
+    } else {
+        options.algorithm.find_rock(&hailstones)?
+    };
+    let part2_answ = rock.coordinates_sum();
+
+    rock.verify(&hailstones)?;
+
+    println!(
+        "Rock thrown from {:?} at {:?}",
+        rock.position, rock.velocity
+    );
+    // every --approach gives exactly two stones
+    for pair in options.approaches.chunks_exact(2) {
+        let (first, second) = (pair[0], pair[1]);
+        let (first_stone, second_stone) = (
+            first.get(&hailstones, &rock)?,
+            second.get(&hailstones, &rock)?,
+        );
+        let (time, distance) = first_stone.closest_encounter(&second_stone);
+        println!(
+            "{} and {}: {}; closest at t = {:.1}, {:.1} apart",
+            first,
+            second,
+            first_stone.path_approach(&second_stone),
+            time,
+            distance
+        );
+    }
+    Ok(Some(part2_answ))
+}
+
+/// The hailstones as they are at t = 0.
+#[derive(Debug, Clone)]
+pub struct Day24 {
+    hailstones: Vec<HailStonePath>,
+    /// where the crossings of part 1 are looked for
+    test_area: RangeInclusive<f64>,
+}
+
+aoc_utils::register_day!(
+    24,
+    Day24,
+    examples = [Example::both(include_str!("../example"), 2, 47)]
+);
+
+impl Solution for Day24 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            hailstones: parse_lines(input, str::parse)?,
+            test_area: TEST_AREA_PART_1,
+        })
+    }
+
+    fn parse_example(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            hailstones: parse_lines(input, str::parse)?,
+            test_area: EXAMPLE_TEST_AREA,
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(crossings_inside(&self.hailstones, &self.test_area).into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        let rock = Algorithm::default().find_rock(&self.hailstones)?;
+        rock.verify(&self.hailstones)?;
+        Ok(rock.coordinates_sum().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(line: &str) -> HailStonePath {
+        line.parse().unwrap()
+    }
+
+    fn crossing(first: &str, second: &str) -> Crossing {
+        path(first).crossing(&path(second))
+    }
+
+    /// Asserts that the paths cross at `expected_point` at `expected_times`, up to rounding.
+    fn assert_at(crossing: Crossing, expected_point: (f64, f64), expected_times: (f64, f64)) {
+        let Crossing::At { point, times } = crossing else {
+            panic!(
+                "Expected a crossing at {:?}, got {:?}",
+                expected_point, crossing
+            );
+        };
+
+        for (value, expected) in [
+            (point.0, expected_point.0),
+            (point.1, expected_point.1),
+            (times.0, expected_times.0),
+            (times.1, expected_times.1),
+        ] {
+            assert!(
+                (value - expected).abs() < 1e-9,
+                "{:?} instead of {:?}",
+                crossing,
+                expected_point
+            );
+        }
+    }
+
+    #[test]
+    fn crossing_at() {
+        assert_at(
+            crossing("19, 13, 30 @ -2, 1, -2", "18, 19, 22 @ -1, -1, -2"),
+            (14. + 1. / 3., 15. + 1. / 3.),
+            (2. + 1. / 3., 3. + 2. / 3.),
+        );
+        // no speed along x, the determinant is not 0 for all that
+        assert_at(
+            crossing("5, 0, 0 @ 0, 1, 0", "0, 5, 0 @ 1, 0, 7"),
+            (5., 5.),
+            (5., 5.),
+        );
+    }
+
+    #[test]
+    fn crossing_in_the_past() {
+        assert_eq!(
+            crossing("19, 13, 30 @ -2, 1, -2", "20, 19, 15 @ 1, -5, -3"),
+            Crossing::InThePast
+        );
+        assert_eq!(
+            crossing("20, 19, 15 @ 1, -5, -3", "19, 13, 30 @ -2, 1, -2"),
+            Crossing::InThePast
+        );
+    }
+
+    #[test]
+    fn crossing_parallel() {
+        assert_eq!(
+            crossing("18, 19, 22 @ -1, -1, -2", "20, 25, 34 @ -2, -2, -4"),
+            Crossing::Parallel
+        );
+        assert_eq!(
+            crossing("0, 0, 0 @ 0, 3, 0", "1, 0, 0 @ 0, -1, 0"),
+            Crossing::Parallel
+        );
+    }
+
+    #[test]
+    fn crossing_without_moving() {
+        // the first one only moves along z, the second goes through it
+        assert_at(
+            crossing("3, 3, 0 @ 0, 0, 1", "0, 0, 0 @ 1, 1, 1"),
+            (3., 3.),
+            (0., 3.),
+        );
+        assert_at(
+            crossing("0, 0, 0 @ 1, 1, 1", "3, 3, 0 @ 0, 0, 1"),
+            (3., 3.),
+            (3., 0.),
+        );
+        assert_eq!(
+            crossing("3, 4, 0 @ 0, 0, 1", "0, 0, 0 @ 1, 1, 1"),
+            Crossing::Missed
+        );
+        assert_eq!(
+            crossing("3, 3, 0 @ 0, 0, 1", "5, 5, 0 @ 1, 1, 1"),
+            Crossing::InThePast
+        );
+        assert_eq!(
+            crossing("3, 3, 0 @ 0, 0, 1", "4, 3, 0 @ 0, 0, 0"),
+            Crossing::Missed
+        );
+        assert_eq!(
+            crossing("3, 3, 0 @ 0, 0, 1", "3, 3, 5 @ 0, 0, -1"),
+            Crossing::Overlapping {
+                origin: (3., 3.),
+                velocity: (0., 0.),
+                times: (0., f64::INFINITY),
+            }
+        );
+    }
+
+    #[test]
+    fn crossing_overlapping() {
+        // same way: from when the first one gets where the second starts on
+        assert_eq!(
+            crossing("0, 0, 0 @ 1, 1, 0", "2, 2, 0 @ 2, 2, 0"),
+            Crossing::Overlapping {
+                origin: (0., 0.),
+                velocity: (1., 1.),
+                times: (2., f64::INFINITY),
+            }
+        );
+        // opposite ways: until the first one gets where the second starts
+        assert_eq!(
+            crossing("0, 0, 0 @ 1, 1, 0", "4, 4, 0 @ -1, -1, 0"),
+            Crossing::Overlapping {
+                origin: (0., 0.),
+                velocity: (1., 1.),
+                times: (0., 4.),
+            }
+        );
+        assert_eq!(
+            crossing("0, 0, 0 @ 1, 1, 0", "-4, -4, 0 @ -1, -1, 0"),
+            Crossing::InThePast
+        );
+    }
+
+    fn example() -> Vec<HailStonePath> {
+        parse_lines(include_str!("../example"), str::parse).unwrap()
+    }
+
+    /// The rock of the example, thrown from (24, 13, 10) at (-3, 1, 2).
+    const EXAMPLE_ROCK: Rock = Rock {
+        position: [24, 13, 10],
+        velocity: [-3, 1, 2],
+    };
+
+    #[test]
+    fn gaussian_example() {
+        let hailstones = example();
+        let rock = Rock::find_gaussian(&hailstones).unwrap();
+        assert_eq!(rock, EXAMPLE_ROCK);
+        assert!(rock.verify(&hailstones).is_ok());
+        assert_eq!(rock.coordinates_sum(), 47);
+    }
+
+    #[test]
+    fn velocity_search_example() {
+        let mut hailstones = example();
+        // too few of them share a speed to pin the rock's down
+        assert!(Rock::find_velocity_search(&hailstones).is_err());
+        assert_eq!(
+            Rock::meeting_point(&hailstones, EXAMPLE_ROCK.velocity)
+                .unwrap()
+                .unwrap(),
+            EXAMPLE_ROCK.position
+        );
+
+        // along each axis, two hailstones hit a time apart going 1 slower than the rock only leave it the
+        // speeds `v` and `v - 2`, and two going 1 faster `v` and `v + 2`
+        for axis in 0..3 {
+            for (offset, time) in [(-1, 1), (-1, 2), (1, 3), (1, 4)] {
+                let time = time + 4 * axis as i64;
+                let velocity = [0, 1, 2].map(|other| {
+                    EXAMPLE_ROCK.velocity[other] + if other == axis { offset } else { time }
+                });
+                let position = [0, 1, 2].map(|other| {
+                    EXAMPLE_ROCK.position[other]
+                        + time * (EXAMPLE_ROCK.velocity[other] - velocity[other])
+                });
+                let [px, py, pz] = position;
+                let [vx, vy, vz] = velocity;
+                hailstones.push(HailStonePath::new(px, py, pz, vx, vy, vz));
+            }
+        }
+
+        let rock = Rock::find_velocity_search(&hailstones).unwrap();
+        assert_eq!(rock, EXAMPLE_ROCK);
+        assert!(rock.verify(&hailstones).is_ok());
+        assert_eq!(rock.coordinates_sum(), 47);
+        assert_eq!(Rock::find_gaussian(&hailstones).unwrap(), EXAMPLE_ROCK);
+    }
+}
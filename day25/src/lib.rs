@@ -0,0 +1,565 @@
+use aoc_utils::{
+    dot::{Attributes, ToDot},
+    graph::DisjointSet,
+    input::{Args, InputSource},
+    registry::Example,
+    solution::{Answer, Solution},
+};
+use clap::{Parser, ValueEnum};
+use core::fmt;
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use rand::Rng;
+use std::{
+    cmp,
+    collections::VecDeque,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// Number of wires to disconnect.
+const CUT_SIZE: usize = 3;
+/// How many random pairs of components [`Graph::find_cut_max_flow`] tries before giving up.
+const MAX_FLOW_ATTEMPTS: usize = 100;
+/// How many random components [`Graph::find_cut_betweenness`] starts searches from.
+const BETWEENNESS_SOURCES: usize = 100;
+
+/// Index of a component in [`Graph::names`] and [`Graph::adjacency`].
+type VertexId = u32;
+
+#[derive(Debug, Clone, Default)]
+struct Graph<'s> {
+    /// the label of every component
+    names: Vec<&'s str>,
+    ids: FnvHashMap<&'s str, VertexId>,
+    /// the components every component is wired to, sorted
+    adjacency: Vec<Vec<VertexId>>,
+}
+
+/// The components named after themselves, laid out by `neato` as there is no hierarchy among them.
+impl ToDot for Graph<'_> {
+    type Node = VertexId;
+    type Edge = ();
+
+    const DIRECTED: bool = false;
+
+    fn nodes(&self) -> impl Iterator<Item = VertexId> {
+        0..self.len() as VertexId
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (VertexId, VertexId, ())> {
+        self.adjacency.iter().enumerate().flat_map(|(src, dests)| {
+            dests
+                .iter()
+                .filter(move |&&dst| src < dst as usize)
+                .map(move |&dst| (src as VertexId, dst, ()))
+        })
+    }
+
+    fn node_id(&self, &vertex: &VertexId) -> String {
+        self.name(vertex).to_owned()
+    }
+
+    fn graph_attributes(&self) -> Attributes {
+        vec![("layout", "neato".to_owned())]
+    }
+}
+
+impl<'s> Graph<'s> {
+    #[inline]
+    fn intern(&mut self, name: &'s str) -> VertexId {
+        *self.ids.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            self.adjacency.push(vec![]);
+            (self.names.len() - 1) as VertexId
+        })
+    }
+
+    #[inline]
+    fn name(&self, vertex: VertexId) -> &'s str {
+        self.names[vertex as usize]
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[inline]
+    fn remove_undirected_edge(&mut self, src_vertex: VertexId, dst_vertex: VertexId) {
+        for (vertex, other) in [(src_vertex, dst_vertex), (dst_vertex, src_vertex)] {
+            let adjacency = &mut self.adjacency[vertex as usize];
+            if let Ok(slot) = adjacency.binary_search(&other) {
+                adjacency.remove(slot);
+            }
+        }
+    }
+
+    #[inline]
+    fn add_undirected_edge(&mut self, src_label: &'s str, dst_label: &'s str) {
+        let (src_vertex, dst_vertex) = (self.intern(src_label), self.intern(dst_label));
+        for (vertex, other) in [(src_vertex, dst_vertex), (dst_vertex, src_vertex)] {
+            let adjacency = &mut self.adjacency[vertex as usize];
+            if let Err(slot) = adjacency.binary_search(&other) {
+                adjacency.insert(slot, other);
+            }
+        }
+    }
+
+    /// Looks for [`CUT_SIZE`] edges splitting the graph in two by computing the maximum flow
+    /// between random pairs of vertices: a pair on both sides of the cut cannot push more flow than
+    /// there are edges in the cut, any other pair can.
+    fn find_cut_max_flow(&self) -> Result<Vec<(VertexId, VertexId)>, Box<dyn Error>> {
+        if self.len() < 2 {
+            return Err("The graph needs at least 2 components to be cut".into());
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..MAX_FLOW_ATTEMPTS {
+            let source = rng.gen_range(0..self.len()) as VertexId;
+            let sink = rng.gen_range(0..self.len()) as VertexId;
+            if source == sink {
+                continue;
+            }
+
+            if let Some(cut) = FlowNetwork::new(&self.adjacency).min_cut(source, sink, CUT_SIZE) {
+                return Ok(cut);
+            }
+        }
+
+        Err(format!(
+            "No pair of components out of {} random ones is separated by {} wires or less",
+            MAX_FLOW_ATTEMPTS, CUT_SIZE
+        )
+        .into())
+    }
+
+    /// Looks for [`CUT_SIZE`] edges splitting the graph in two as the edges most shortest paths go
+    /// through: every path from one side to the other has to take one of them.
+    ///
+    /// The most used edge is removed, one at a time so that the paths it carried are spread over
+    /// the others left in the cut, and the result is only kept if it does split the graph in two.
+    fn find_cut_betweenness(&self) -> Result<Vec<(VertexId, VertexId)>, Box<dyn Error>> {
+        let sources = if self.len() <= BETWEENNESS_SOURCES {
+            (0..self.len() as VertexId).collect_vec()
+        } else {
+            let mut rng = rand::thread_rng();
+            (0..BETWEENNESS_SOURCES)
+                .map(|_| rng.gen_range(0..self.len()) as VertexId)
+                .collect_vec()
+        };
+
+        let mut cut = vec![];
+        for _ in 0..CUT_SIZE {
+            let Some((most_used, _)) = edge_usage(&self.adjacency, &cut, &sources)
+                .into_iter()
+                .max_by_key(|&(edge, count)| (count, cmp::Reverse(edge)))
+            else {
+                break;
+            };
+            cut.push(most_used);
+        }
+
+        let components = self.groups_without(&cut).set_count();
+        if components != 2 {
+            return Err(format!(
+                "Removing the {} most used wires leaves {} group(s) of components instead of 2",
+                cut.len(),
+                components
+            )
+            .into());
+        }
+
+        Ok(cut)
+    }
+
+    /// Makes sure removing `cut` splits the graph in two, every one of its [`CUT_SIZE`] edges
+    /// being needed for that, and returns the size of both groups.
+    fn verify_cut(&self, cut: &[(VertexId, VertexId)]) -> Result<(usize, usize), CutError> {
+        if cut.len() != CUT_SIZE {
+            return Err(CutError::WrongSize(cut.len()));
+        }
+
+        let edge_name = |&(src, dst): &(VertexId, VertexId)| {
+            (self.name(src).to_owned(), self.name(dst).to_owned())
+        };
+        if let Some(edge) = cut
+            .iter()
+            .find(|&&(src, dst)| self.adjacency[src as usize].binary_search(&dst).is_err())
+        {
+            return Err(CutError::NotAWire(edge_name(edge)));
+        }
+
+        let mut groups = self.groups_without(cut);
+        if groups.set_count() != 2 {
+            return Err(CutError::WrongGroupCount(groups.set_count()));
+        }
+
+        if let Some(edge) = cut
+            .iter()
+            .find(|&&(src, dst)| groups.same_set(src as usize, dst as usize))
+        {
+            return Err(CutError::NotMinimal(edge_name(edge)));
+        }
+
+        let first_size = groups.size_of(cut[0].0 as usize);
+        Ok((first_size, self.len() - first_size))
+    }
+
+    /// The connected groups of vertices once the edges of `removed` are taken out.
+    fn groups_without(&self, removed: &[(VertexId, VertexId)]) -> DisjointSet {
+        let is_removed = |src, dst| removed.contains(&(src, dst)) || removed.contains(&(dst, src));
+        let mut groups = DisjointSet::new(self.len());
+        for (src, dests) in self.adjacency.iter().enumerate() {
+            for &dst in dests
+                .iter()
+                .filter(|&&dst| !is_removed(src as VertexId, dst))
+            {
+                groups.union(src, dst as usize);
+            }
+        }
+
+        groups
+    }
+}
+
+/// For every edge (smallest vertex first) not in `removed`, how many vertices the breadth-first
+/// searches from `sources` reached through it.
+fn edge_usage(
+    adjacency: &[Vec<VertexId>],
+    removed: &[(VertexId, VertexId)],
+    sources: &[VertexId],
+) -> FnvHashMap<(VertexId, VertexId), u64> {
+    let mut usage = FnvHashMap::default();
+    for &source in sources {
+        let mut parents = vec![None; adjacency.len()];
+        let mut visited = vec![false; adjacency.len()];
+        visited[source as usize] = true;
+        let mut order = vec![source];
+        let mut next = 0;
+        while let Some(&vertex) = order.get(next) {
+            next += 1;
+            for &dest in &adjacency[vertex as usize] {
+                if !visited[dest as usize]
+                    && !removed.contains(&(vertex.min(dest), vertex.max(dest)))
+                {
+                    visited[dest as usize] = true;
+                    parents[dest as usize] = Some(vertex);
+                    order.push(dest);
+                }
+            }
+        }
+
+        // the farthest vertices first, so that subtrees are complete before their root
+        let mut subtree_sizes = vec![1; adjacency.len()];
+        for &vertex in order.iter().rev() {
+            if let Some(parent) = parents[vertex as usize] {
+                let size = subtree_sizes[vertex as usize];
+                subtree_sizes[parent as usize] += size;
+                *usage
+                    .entry((parent.min(vertex), parent.max(vertex)))
+                    .or_default() += size;
+            }
+        }
+    }
+
+    usage
+}
+
+/// Why a cut is not the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CutError {
+    /// the cut does not have [`CUT_SIZE`] edges
+    WrongSize(usize),
+    /// the cut has an edge that is not in the graph
+    NotAWire((String, String)),
+    /// removing the cut leaves that many groups instead of 2
+    WrongGroupCount(usize),
+    /// the cut has an edge within one of the groups, which did not need to be cut
+    NotMinimal((String, String)),
+}
+
+impl fmt::Display for CutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongSize(size) => {
+                write!(f, "The cut has {} wires instead of {}", size, CUT_SIZE)
+            }
+            Self::NotAWire((src, dst)) => write!(
+                f,
+                "The cut has a wire between {} and {} which are not wired",
+                src, dst
+            ),
+            Self::WrongGroupCount(count) => write!(
+                f,
+                "The cut leaves {} group(s) of components instead of 2",
+                count
+            ),
+            Self::NotMinimal((src, dst)) => write!(
+                f,
+                "The cut is not minimal: {} and {} are still connected without their wire",
+                src, dst
+            ),
+        }
+    }
+}
+
+impl Error for CutError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, ValueEnum)]
+enum Algorithm {
+    /// maximum flow between random pairs of components
+    #[default]
+    #[value(name = "maxflow")]
+    MaxFlow,
+    /// the edges on the most shortest paths from random components
+    Betweenness,
+}
+
+impl Algorithm {
+    #[inline]
+    fn find_cut(self, graph: &Graph) -> Result<Vec<(VertexId, VertexId)>, Box<dyn Error>> {
+        match self {
+            Self::MaxFlow => graph.find_cut_max_flow(),
+            Self::Betweenness => graph.find_cut_betweenness(),
+        }
+    }
+}
+
+/// Where `--emit-dot` writes the graph when not given a path.
+const DEFAULT_DOT_PATH: &str = "input.gv";
+
+#[derive(Debug, Clone, Default, Parser)]
+pub struct Options {
+    #[command(flatten)]
+    pub args: Args<25>,
+    /// The way the three wires to cut are found
+    #[arg(long = "algo", value_enum, default_value_t)]
+    algorithm: Algorithm,
+    /// Write the graph before the cut to this file in the DOT format, the graph after the cut being written next
+    /// to it
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = DEFAULT_DOT_PATH)]
+    emit_dot: Option<PathBuf>,
+}
+
+pub fn parse_options() -> Result<Options, Box<dyn Error>> {
+    let options = Options::parse();
+    options.args.init_tracing();
+    Ok(options)
+}
+
+/// Unit capacity flow through an undirected graph, every edge letting one unit through either way.
+#[derive(Debug, Clone)]
+struct FlowNetwork<'a> {
+    adjacency: &'a [Vec<VertexId>],
+    /// the flow along every edge of `adjacency`, from the vertex to its neighbour (-1, 0 or 1)
+    flow: Vec<Vec<i8>>,
+}
+
+impl<'a> FlowNetwork<'a> {
+    #[inline]
+    fn new(adjacency: &'a [Vec<VertexId>]) -> Self {
+        Self {
+            adjacency,
+            flow: adjacency
+                .iter()
+                .map(|neighbours| vec![0; neighbours.len()])
+                .collect(),
+        }
+    }
+
+    /// Edmonds-Karp: pushes flow from `source` to `sink` along the shortest paths with capacity
+    /// left, giving up once more than `max_size` units went through.
+    ///
+    /// Returns the edges of the minimum cut between them (from the side of `source`) if it has at
+    /// most `max_size` edges.
+    fn min_cut(
+        &mut self,
+        source: VertexId,
+        sink: VertexId,
+        max_size: usize,
+    ) -> Option<Vec<(VertexId, VertexId)>> {
+        for _ in 0..=max_size {
+            let Some(parents) = self.augmenting_path(source, sink) else {
+                // the vertices still reachable are the side of the source
+                let reachable = self.residual_reachable(source);
+                return Some(
+                    (0..self.adjacency.len() as VertexId)
+                        .filter(|&vertex| reachable[vertex as usize])
+                        .flat_map(|vertex| {
+                            self.adjacency[vertex as usize]
+                                .iter()
+                                .map(move |&dest| (vertex, dest))
+                        })
+                        .filter(|&(_, dest)| !reachable[dest as usize])
+                        .collect(),
+                );
+            };
+
+            let mut vertex = sink;
+            while let Some((parent, slot)) = parents[vertex as usize] {
+                self.flow[parent as usize][slot] += 1;
+                let back_slot = self.slot_of(vertex, parent);
+                self.flow[vertex as usize][back_slot] -= 1;
+                vertex = parent;
+            }
+        }
+
+        None
+    }
+
+    /// For every vertex reached by a breadth-first search through edges with capacity left, the
+    /// vertex it was reached from and the index of the edge in its neighbours.
+    fn augmenting_path(
+        &self,
+        source: VertexId,
+        sink: VertexId,
+    ) -> Option<Vec<Option<(VertexId, usize)>>> {
+        let mut parents = vec![None; self.adjacency.len()];
+        let mut visited = vec![false; self.adjacency.len()];
+        visited[source as usize] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(vertex) = queue.pop_front() {
+            for (slot, &dest) in self.adjacency[vertex as usize].iter().enumerate() {
+                if visited[dest as usize] || self.flow[vertex as usize][slot] >= 1 {
+                    continue;
+                }
+
+                visited[dest as usize] = true;
+                parents[dest as usize] = Some((vertex, slot));
+                if dest == sink {
+                    return Some(parents);
+                }
+                queue.push_back(dest);
+            }
+        }
+
+        None
+    }
+
+    fn residual_reachable(&self, source: VertexId) -> Vec<bool> {
+        let mut reachable = vec![false; self.adjacency.len()];
+        reachable[source as usize] = true;
+        let mut queue = VecDeque::from([source]);
+        while let Some(vertex) = queue.pop_front() {
+            for (slot, &dest) in self.adjacency[vertex as usize].iter().enumerate() {
+                if !reachable[dest as usize] && self.flow[vertex as usize][slot] < 1 {
+                    reachable[dest as usize] = true;
+                    queue.push_back(dest);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    #[inline]
+    fn slot_of(&self, vertex: VertexId, dest: VertexId) -> usize {
+        self.adjacency[vertex as usize]
+            .binary_search(&dest)
+            .expect("the graph is undirected")
+    }
+}
+
+impl<'s> FromIterator<&'s str> for Graph<'s> {
+    fn from_iter<T: IntoIterator<Item = &'s str>>(iter: T) -> Self {
+        let mut graph = Self::default();
+        for line in iter {
+            let Some((src_label, dests)) = line.split_once(':') else {
+                panic!("{:?} could not be split on a ':'", line);
+            };
+
+            for dest in dests.split_whitespace() {
+                graph.add_undirected_edge(src_label, dest);
+            }
+        }
+
+        graph
+    }
+}
+
+/// `graph.gv` for the whole graph becomes `graph.cut.gv` once cut.
+fn cut_dot_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(".cut");
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+
+    path.with_file_name(file_name)
+}
+
+fn emit_dot(graph: &Graph, path: &Path) -> Result<(), Box<dyn Error>> {
+    graph.save_dot(path)?;
+    println!("Graph written to {}", path.display());
+
+    Ok(())
+}
+
+/// Solves the only part of the day, unless `--part 2` asked for the part there is no puzzle for.
+pub fn solve(input: &InputSource, options: &Options) -> Result<Option<u64>, Box<dyn Error>> {
+    if !options.args.part.solves_part1() {
+        return Ok(None);
+    }
+
+    let input = input.read()?;
+    let mut graph = input.lines().collect::<Graph>();
+
+    if let Some(path) = &options.emit_dot {
+        emit_dot(&graph, path)?;
+    }
+
+    let to_cut = options.algorithm.find_cut(&graph)?;
+
+    let (first_size, second_size) = graph.verify_cut(&to_cut)?;
+    println!(
+        "Wires to disconnect: {}",
+        to_cut
+            .iter()
+            .map(|&(src, dst)| format!("{}/{}", graph.name(src), graph.name(dst)))
+            .join(", ")
+    );
+    println!("Groups of components: {} and {}", first_size, second_size);
+
+    to_cut
+        .iter()
+        .for_each(|&(src, dst)| graph.remove_undirected_edge(src, dst));
+
+    if let Some(path) = &options.emit_dot {
+        emit_dot(&graph, &cut_dot_path(path))?;
+    }
+
+    Ok(Some((first_size * second_size) as u64))
+}
+
+/// The wiring diagram, whose components borrow their labels from it.
+#[derive(Debug, Clone)]
+pub struct Day25 {
+    input: String,
+}
+
+aoc_utils::register_day!(
+    25,
+    Day25,
+    examples = [Example::part1_only(include_str!("../example"), 54)]
+);
+
+impl Solution for Day25 {
+    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part1(&self) -> Result<Answer, Box<dyn Error>> {
+        let graph = self.input.lines().collect::<Graph>();
+        let to_cut = Algorithm::default().find_cut(&graph)?;
+        let (first_size, second_size) = graph.verify_cut(&to_cut)?;
+        Ok((first_size * second_size).into())
+    }
+
+    fn part2(&self) -> Result<Answer, Box<dyn Error>> {
+        Ok(Answer::NoPuzzle)
+    }
+}